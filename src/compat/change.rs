@@ -0,0 +1,223 @@
+use std::fmt::{Display, Formatter};
+
+use crate::tree::TypeTag;
+
+/// A change between two versions of a schema.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Change {
+    location: String,
+    kind: ChangeKind,
+}
+
+impl Change {
+    //! Construction
+
+    /// Creates a new change.
+    ///
+    /// The location is the declaration name, optionally followed by `.` & the element name.
+    pub fn new<L>(location: L, kind: ChangeKind) -> Self
+    where
+        L: Into<String>,
+    {
+        Self {
+            location: location.into(),
+            kind,
+        }
+    }
+}
+
+impl Change {
+    //! Properties
+
+    /// Gets the location of the change. (`Message` or `Message.field`)
+    pub fn location(&self) -> &str {
+        self.location.as_str()
+    }
+
+    /// Gets the kind of change.
+    pub fn kind(&self) -> &ChangeKind {
+        &self.kind
+    }
+
+    /// Checks if the change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.kind.is_breaking()
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity: &str = if self.is_breaking() {
+            "breaking"
+        } else {
+            "safe"
+        };
+        write!(f, "{} `{}`: {}", severity, self.location, self.kind)
+    }
+}
+
+/// A kind of change between two versions of a schema.
+///
+/// The field kinds apply to the fields of messages & the cases of variants.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum ChangeKind {
+    /// The declaration was added.
+    DeclarationAdded,
+
+    /// The declaration was removed.
+    DeclarationRemoved,
+
+    /// The declaration changed between a message, enum, variant or service.
+    DeclarationKindChanged,
+
+    /// The message was renamed. (declared with the same fields under the new name)
+    MessageRenamed { new_name: String },
+
+    /// The optional field was added.
+    FieldAdded,
+
+    /// The optional field was removed.
+    FieldRemoved,
+
+    /// The field was renamed keeping its field number & wire format.
+    FieldRenamed { new_name: String },
+
+    /// The field number of a removed or reserved field was reused by a different field.
+    FieldNumberReused { field_number: u32 },
+
+    /// The field type changed to a type with a different wire format.
+    FieldTypeChanged {
+        old_type: TypeTag,
+        new_type: TypeTag,
+    },
+
+    /// The field type changed to a type with the same wire format. (enums & `u32` share a format)
+    FieldTypeEquivalent {
+        old_type: TypeTag,
+        new_type: TypeTag,
+    },
+
+    /// The fixed-width encoding option changed.
+    FieldFixedChanged,
+
+    /// The packed encoding option changed. (decoders accept both encodings)
+    FieldPackedChanged,
+
+    /// The required field was added.
+    RequiredFieldAdded,
+
+    /// The required field was removed.
+    RequiredFieldRemoved,
+
+    /// The enum case was added.
+    CaseAdded,
+
+    /// The enum case was removed. (decoded as unrecognized)
+    CaseRemoved,
+
+    /// The enum case number changed.
+    CaseNumberChanged { old_number: u32, new_number: u32 },
+
+    /// The rpc was added.
+    RpcAdded,
+
+    /// The rpc was removed.
+    RpcRemoved,
+
+    /// The request or response message of the rpc changed.
+    RpcMessagesChanged,
+
+    /// The method id of the rpc changed. (the rpcs were reordered)
+    RpcMethodIdChanged {
+        old_method_id: u32,
+        new_method_id: u32,
+    },
+}
+
+impl ChangeKind {
+    //! Breaking
+
+    /// Checks if the change is breaking.
+    ///
+    /// A change is breaking when old readers cannot decode new writers, or new readers cannot
+    /// decode old writers, or when generated code for the declaration no longer exists.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Self::DeclarationAdded => false,
+            Self::DeclarationRemoved => true,
+            Self::DeclarationKindChanged => true,
+            Self::MessageRenamed { .. } => true,
+            Self::FieldAdded => false,
+            Self::FieldRemoved => false,
+            Self::FieldRenamed { .. } => false,
+            Self::FieldNumberReused { .. } => true,
+            Self::FieldTypeChanged { .. } => true,
+            Self::FieldTypeEquivalent { .. } => false,
+            Self::FieldFixedChanged => true,
+            Self::FieldPackedChanged => false,
+            Self::RequiredFieldAdded => true,
+            Self::RequiredFieldRemoved => true,
+            Self::CaseAdded => false,
+            Self::CaseRemoved => false,
+            Self::CaseNumberChanged { .. } => true,
+            Self::RpcAdded => false,
+            Self::RpcRemoved => true,
+            Self::RpcMessagesChanged => true,
+            Self::RpcMethodIdChanged { .. } => true,
+        }
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DeclarationAdded => write!(f, "declaration added"),
+            Self::DeclarationRemoved => write!(f, "declaration removed"),
+            Self::DeclarationKindChanged => write!(f, "declaration kind changed"),
+            Self::MessageRenamed { new_name } => write!(f, "message renamed to `{}`", new_name),
+            Self::FieldAdded => write!(f, "field added"),
+            Self::FieldRemoved => write!(f, "field removed"),
+            Self::FieldRenamed { new_name } => write!(f, "field renamed to `{}`", new_name),
+            Self::FieldNumberReused { field_number } => {
+                write!(f, "field number {} reused", field_number)
+            }
+            Self::FieldTypeChanged { old_type, new_type } => {
+                write!(
+                    f,
+                    "field type changed from `{}` to `{}`",
+                    old_type, new_type
+                )
+            }
+            Self::FieldTypeEquivalent { old_type, new_type } => write!(
+                f,
+                "field type changed from `{}` to the equivalent `{}`",
+                old_type, new_type
+            ),
+            Self::FieldFixedChanged => write!(f, "fixed-width encoding changed"),
+            Self::FieldPackedChanged => write!(f, "packed encoding changed"),
+            Self::RequiredFieldAdded => write!(f, "required field added"),
+            Self::RequiredFieldRemoved => write!(f, "required field removed"),
+            Self::CaseAdded => write!(f, "case added"),
+            Self::CaseRemoved => write!(f, "case removed"),
+            Self::CaseNumberChanged {
+                old_number,
+                new_number,
+            } => write!(
+                f,
+                "case number changed from {} to {}",
+                old_number, new_number
+            ),
+            Self::RpcAdded => write!(f, "rpc added"),
+            Self::RpcRemoved => write!(f, "rpc removed"),
+            Self::RpcMessagesChanged => write!(f, "rpc messages changed"),
+            Self::RpcMethodIdChanged {
+                old_method_id,
+                new_method_id,
+            } => write!(
+                f,
+                "method id changed from {} to {}",
+                old_method_id, new_method_id
+            ),
+        }
+    }
+}
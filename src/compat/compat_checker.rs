@@ -0,0 +1,441 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::discriminant;
+
+use code_gen::WithName;
+
+use crate::compat::{Change, ChangeKind, CompatReport};
+use crate::tree::{
+    Enum, Message, MessageField, PrimitiveType, Service, SourceDec, SourceFile, TypeTag, Variant,
+    WithTypeTag, WithVar,
+};
+
+/// Checks the compatibility of the new version of the source file with the old version.
+///
+/// Returns all the changes found, both breaking & safe.
+pub fn check_compat(old: &SourceFile, new: &SourceFile) -> CompatReport {
+    let mut checker: CompatChecker = CompatChecker::new(old, new);
+    checker.check_source_file();
+    checker.finish()
+}
+
+/// A field as seen on the wire. (the fields of messages & the cases of variants)
+#[derive(Copy, Clone, Debug)]
+struct WireField<'a> {
+    name: &'a str,
+    field_number: u32,
+    type_tag: &'a TypeTag,
+    fixed: bool,
+    packed: bool,
+}
+
+impl<'a> From<&'a MessageField> for WireField<'a> {
+    fn from(field: &'a MessageField) -> Self {
+        Self {
+            name: field.name(),
+            field_number: field.field_number().unwrap_or_default(),
+            type_tag: field.type_tag(),
+            fixed: field.is_fixed(),
+            packed: field.is_packed(),
+        }
+    }
+}
+
+/// Responsible for checking the compatibility of two versions of a source file.
+#[derive(Debug)]
+pub struct CompatChecker<'a> {
+    old: &'a SourceFile,
+    new: &'a SourceFile,
+    renames: HashMap<&'a str, &'a str>,
+    changes: Vec<Change>,
+}
+
+impl<'a> CompatChecker<'a> {
+    //! Construction
+
+    /// Creates a new compat checker for the old & new versions of the source file.
+    ///
+    /// Messages removed from the old version are treated as renamed when the new version adds a
+    /// message with the same fields & reserved statements.
+    pub fn new(old: &'a SourceFile, new: &'a SourceFile) -> Self {
+        Self {
+            old,
+            new,
+            renames: Self::find_renames(old, new),
+            changes: Vec::default(),
+        }
+    }
+
+    /// Finishes the check. Returns the changes found as a report.
+    pub fn finish(self) -> CompatReport {
+        self.changes.into()
+    }
+
+    fn find_renames(old: &'a SourceFile, new: &'a SourceFile) -> HashMap<&'a str, &'a str> {
+        let mut renames: HashMap<&str, &str> = HashMap::default();
+        let mut renamed: HashSet<&str> = HashSet::default();
+        for old_message in old.messages() {
+            if Self::declaration(new, old_message.name()).is_some() {
+                continue;
+            }
+            let new_message: Option<&Message> = new.messages().find(|new_message| {
+                Self::declaration(old, new_message.name()).is_none()
+                    && !renamed.contains(new_message.name())
+                    && Self::has_same_fields(old_message, new_message)
+            });
+            if let Some(new_message) = new_message {
+                renamed.insert(new_message.name());
+                renames.insert(old_message.name(), new_message.name());
+            }
+        }
+        renames
+    }
+
+    /// Checks if the messages have the same fields & reserved statements. (ignoring comments)
+    fn has_same_fields(a: &Message, b: &Message) -> bool {
+        a.fields().len() == b.fields().len()
+            && a.reserved().eq(b.reserved())
+            && a.fields().iter().zip(b.fields()).all(|(a, b)| {
+                a.var() == b.var()
+                    && a.field_number() == b.field_number()
+                    && a.is_fixed() == b.is_fixed()
+                    && a.is_packed() == b.is_packed()
+            })
+    }
+}
+
+impl<'a> CompatChecker<'a> {
+    //! Changes
+
+    /// Adds a change.
+    fn report<L>(&mut self, location: L, kind: ChangeKind)
+    where
+        L: Into<String>,
+    {
+        self.changes.push(Change::new(location, kind));
+    }
+
+    /// Gets the declaration with the name.
+    fn declaration<'s>(source_file: &'s SourceFile, name: &str) -> Option<&'s SourceDec> {
+        source_file
+            .declarations()
+            .iter()
+            .find(|declaration| Self::declaration_name(declaration) == name)
+    }
+
+    /// Gets the name of the declaration.
+    fn declaration_name(declaration: &SourceDec) -> &str {
+        match declaration {
+            SourceDec::MessageDec(message) => message.name(),
+            SourceDec::EnumDec(e) => e.name(),
+            SourceDec::VariantDec(variant) => variant.name(),
+            SourceDec::ServiceDec(service) => service.name(),
+        }
+    }
+
+    /// Checks if the old message name refers to the new message name. (directly or renamed)
+    fn is_same_message(&self, old_name: &str, new_name: &str) -> bool {
+        old_name == new_name || self.renames.get(old_name) == Some(&new_name)
+    }
+
+    /// Checks if the old type is encoded the same way on the wire as the new type.
+    ///
+    /// Enums are encoded as `u32` varints, so enums & `u32` are interchangeable.
+    fn is_wire_compatible(&self, old_type: &TypeTag, new_type: &TypeTag) -> bool {
+        match (old_type, new_type) {
+            (TypeTag::Named(old_name), TypeTag::Named(new_name)) => {
+                self.is_same_message(old_name, new_name)
+                    || (self.old.enumeration(old_name).is_some()
+                        && self.new.enumeration(new_name).is_some())
+            }
+            (TypeTag::Named(name), TypeTag::Primitive(PrimitiveType::UnsignedInt32)) => {
+                self.old.enumeration(name).is_some()
+            }
+            (TypeTag::Primitive(PrimitiveType::UnsignedInt32), TypeTag::Named(name)) => {
+                self.new.enumeration(name).is_some()
+            }
+            (TypeTag::List(old_element), TypeTag::List(new_element)) => {
+                self.is_wire_compatible(old_element, new_element)
+            }
+            (TypeTag::Map(old_key, old_value), TypeTag::Map(new_key, new_value)) => {
+                self.is_wire_compatible(old_key, new_key)
+                    && self.is_wire_compatible(old_value, new_value)
+            }
+            _ => old_type == new_type,
+        }
+    }
+}
+
+impl<'a> CompatChecker<'a> {
+    //! Source File
+
+    /// Checks the declarations in the source files.
+    pub fn check_source_file(&mut self) {
+        let (old, new): (&'a SourceFile, &'a SourceFile) = (self.old, self.new);
+        for old_dec in old.declarations() {
+            let name: &str = Self::declaration_name(old_dec);
+            match Self::declaration(new, name) {
+                Some(new_dec) if discriminant(old_dec) != discriminant(new_dec) => {
+                    self.report(name, ChangeKind::DeclarationKindChanged)
+                }
+                Some(new_dec) => self.check_declaration(old_dec, new_dec),
+                None => match self.renames.get(name) {
+                    Some(new_name) => self.report(
+                        name,
+                        ChangeKind::MessageRenamed {
+                            new_name: new_name.to_string(),
+                        },
+                    ),
+                    None => self.report(name, ChangeKind::DeclarationRemoved),
+                },
+            }
+        }
+        for new_dec in new.declarations() {
+            let name: &str = Self::declaration_name(new_dec);
+            let renamed: bool = self.renames.values().any(|new_name| *new_name == name);
+            if Self::declaration(old, name).is_none() && !renamed {
+                self.report(name, ChangeKind::DeclarationAdded);
+            }
+        }
+    }
+
+    fn check_declaration(&mut self, old_dec: &SourceDec, new_dec: &SourceDec) {
+        match (old_dec, new_dec) {
+            (SourceDec::MessageDec(old), SourceDec::MessageDec(new)) => {
+                self.check_message(old, new)
+            }
+            (SourceDec::EnumDec(old), SourceDec::EnumDec(new)) => self.check_enum(old, new),
+            (SourceDec::VariantDec(old), SourceDec::VariantDec(new)) => {
+                self.check_variant(old, new)
+            }
+            (SourceDec::ServiceDec(old), SourceDec::ServiceDec(new)) => {
+                self.check_service(old, new)
+            }
+            _ => unreachable!("declaration kinds are checked by the caller"),
+        }
+    }
+}
+
+impl<'a> CompatChecker<'a> {
+    //! Declarations
+
+    /// Checks the message fields.
+    ///
+    /// Required fields are matched by position & optional fields by field number.
+    pub fn check_message(&mut self, old: &Message, new: &Message) {
+        let old_required: Vec<&MessageField> = old.required_fields().collect();
+        let new_required: Vec<&MessageField> = new.required_fields().collect();
+        for i in 0..old_required.len().max(new_required.len()) {
+            match (old_required.get(i), new_required.get(i)) {
+                (Some(old_field), Some(new_field)) => {
+                    self.check_field(old.name(), (*old_field).into(), (*new_field).into(), false)
+                }
+                (Some(old_field), None) => self.report(
+                    format!("{}.{}", old.name(), old_field.name()),
+                    ChangeKind::RequiredFieldRemoved,
+                ),
+                (None, Some(new_field)) => self.report(
+                    format!("{}.{}", new.name(), new_field.name()),
+                    ChangeKind::RequiredFieldAdded,
+                ),
+                (None, None) => {}
+            }
+        }
+
+        let old_fields: Vec<WireField> = old.optional_fields().map(WireField::from).collect();
+        let new_fields: Vec<WireField> = new.optional_fields().map(WireField::from).collect();
+        self.check_fields(Some(old), old.name(), &old_fields, &new_fields);
+    }
+
+    /// Checks the enum cases. Cases are matched by name.
+    pub fn check_enum(&mut self, old: &Enum, new: &Enum) {
+        for old_case in old.cases() {
+            let location: String = format!("{}.{}", old.name(), old_case.name());
+            match new
+                .cases()
+                .iter()
+                .find(|case| case.name() == old_case.name())
+            {
+                Some(new_case) if new_case.number() != old_case.number() => self.report(
+                    location,
+                    ChangeKind::CaseNumberChanged {
+                        old_number: old_case.number(),
+                        new_number: new_case.number(),
+                    },
+                ),
+                Some(_) => {}
+                None => self.report(location, ChangeKind::CaseRemoved),
+            }
+        }
+        for new_case in new.cases() {
+            if !old
+                .cases()
+                .iter()
+                .any(|case| case.name() == new_case.name())
+            {
+                self.report(
+                    format!("{}.{}", new.name(), new_case.name()),
+                    ChangeKind::CaseAdded,
+                );
+            }
+        }
+    }
+
+    /// Checks the variant cases. Cases are matched by field number like optional fields.
+    pub fn check_variant(&mut self, old: &Variant, new: &Variant) {
+        let old_fields: Vec<WireField> = Self::variant_wire_fields(old);
+        let new_fields: Vec<WireField> = Self::variant_wire_fields(new);
+        self.check_fields(None, old.name(), &old_fields, &new_fields);
+    }
+
+    fn variant_wire_fields(variant: &Variant) -> Vec<WireField<'_>> {
+        variant
+            .cases()
+            .iter()
+            .map(|case| WireField {
+                name: case.name(),
+                field_number: case.number(),
+                type_tag: case.type_tag(),
+                fixed: false,
+                packed: false,
+            })
+            .collect()
+    }
+
+    /// Checks the service rpcs. Rpcs are matched by name.
+    pub fn check_service(&mut self, old: &Service, new: &Service) {
+        for (old_method_id, old_rpc) in old.method_ids() {
+            let location: String = format!("{}.{}", old.name(), old_rpc.name());
+            match new
+                .method_ids()
+                .find(|(_, rpc)| rpc.name() == old_rpc.name())
+            {
+                Some((new_method_id, new_rpc)) => {
+                    if new_method_id != old_method_id {
+                        self.report(
+                            location.as_str(),
+                            ChangeKind::RpcMethodIdChanged {
+                                old_method_id,
+                                new_method_id,
+                            },
+                        );
+                    }
+                    if !self.is_same_message(old_rpc.request(), new_rpc.request())
+                        || !self.is_same_message(old_rpc.response(), new_rpc.response())
+                    {
+                        self.report(location, ChangeKind::RpcMessagesChanged);
+                    }
+                }
+                None => self.report(location, ChangeKind::RpcRemoved),
+            }
+        }
+        for new_rpc in new.rpcs() {
+            if !old.rpcs().iter().any(|rpc| rpc.name() == new_rpc.name()) {
+                self.report(
+                    format!("{}.{}", new.name(), new_rpc.name()),
+                    ChangeKind::RpcAdded,
+                );
+            }
+        }
+    }
+}
+
+impl<'a> CompatChecker<'a> {
+    //! Fields
+
+    /// Checks the tagged fields. Fields are matched by field number.
+    ///
+    /// New fields reusing a field number reserved in the old message are breaking.
+    fn check_fields(
+        &mut self,
+        old: Option<&Message>,
+        declaration: &str,
+        old_fields: &[WireField],
+        new_fields: &[WireField],
+    ) {
+        for old_field in old_fields {
+            match new_fields
+                .iter()
+                .find(|field| field.field_number == old_field.field_number)
+            {
+                Some(new_field) => self.check_field(declaration, *old_field, *new_field, true),
+                None => self.report(
+                    format!("{}.{}", declaration, old_field.name),
+                    ChangeKind::FieldRemoved,
+                ),
+            }
+        }
+        for new_field in new_fields {
+            let location: String = format!("{}.{}", declaration, new_field.name);
+            if old_fields
+                .iter()
+                .any(|field| field.field_number == new_field.field_number)
+            {
+                continue;
+            }
+            if old.is_some_and(|old| old.is_reserved_number(new_field.field_number)) {
+                self.report(
+                    location,
+                    ChangeKind::FieldNumberReused {
+                        field_number: new_field.field_number,
+                    },
+                );
+            } else {
+                self.report(location, ChangeKind::FieldAdded);
+            }
+        }
+    }
+
+    /// Checks the matching old & new fields.
+    ///
+    /// A tagged field renamed to an incompatible type is treated as a reused field number.
+    fn check_field(
+        &mut self,
+        declaration: &str,
+        old_field: WireField,
+        new_field: WireField,
+        tagged: bool,
+    ) {
+        let location: String = format!("{}.{}", declaration, old_field.name);
+        let compatible: bool = self.is_wire_compatible(old_field.type_tag, new_field.type_tag);
+        if old_field.name != new_field.name {
+            if tagged && !compatible {
+                self.report(
+                    location,
+                    ChangeKind::FieldNumberReused {
+                        field_number: new_field.field_number,
+                    },
+                );
+                return;
+            }
+            self.report(
+                location.as_str(),
+                ChangeKind::FieldRenamed {
+                    new_name: new_field.name.to_string(),
+                },
+            );
+        }
+        if !compatible {
+            self.report(
+                location.as_str(),
+                ChangeKind::FieldTypeChanged {
+                    old_type: old_field.type_tag.clone(),
+                    new_type: new_field.type_tag.clone(),
+                },
+            );
+        } else if old_field.type_tag != new_field.type_tag {
+            self.report(
+                location.as_str(),
+                ChangeKind::FieldTypeEquivalent {
+                    old_type: old_field.type_tag.clone(),
+                    new_type: new_field.type_tag.clone(),
+                },
+            );
+        }
+        if old_field.fixed != new_field.fixed {
+            self.report(location.as_str(), ChangeKind::FieldFixedChanged);
+        }
+        if old_field.packed != new_field.packed {
+            self.report(location, ChangeKind::FieldPackedChanged);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{Display, Formatter};
+
+use crate::compat::Change;
+
+/// The changes between two versions of a schema.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct CompatReport {
+    changes: Vec<Change>,
+}
+
+impl From<Vec<Change>> for CompatReport {
+    fn from(changes: Vec<Change>) -> Self {
+        Self { changes }
+    }
+}
+
+impl CompatReport {
+    //! Changes
+
+    /// Gets all the changes.
+    pub fn changes(&self) -> &[Change] {
+        self.changes.as_slice()
+    }
+
+    /// Gets the breaking changes.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+
+    /// Gets the safe changes.
+    pub fn safe_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| !change.is_breaking())
+    }
+
+    /// Checks if the schema versions are compatible. (there are no breaking changes)
+    pub fn is_compatible(&self) -> bool {
+        self.breaking_changes().next().is_none()
+    }
+}
+
+impl Display for CompatReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, change) in self.changes.iter().enumerate() {
+            if i != 0 {
+                write!(f, "\n")?;
+            }
+            write!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+pub use change::*;
+pub use compat_checker::*;
+pub use compat_report::*;
+
+mod change;
+mod compat_checker;
+mod compat_report;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,165 @@
+use crate::compat::{check_compat, Change, ChangeKind, CompatReport};
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::Named;
+use crate::tree::{Enum, Message, MessageField, Service, SourceFile, TypeTag, Variant};
+
+#[test]
+fn declarations() {
+    let point: Message = Message::from("Point")
+        .with_field(("x", SignedInt32))
+        .with_field(("y", SignedInt32));
+    let old: SourceFile = SourceFile::default()
+        .with_declaration(point.clone())
+        .with_declaration(Message::from("Empty"))
+        .with_declaration(Message::from("Status"))
+        .with_declaration(Message::from("Shape").with_field(
+            MessageField::from(("origin", Named("Point".to_string()))).with_field_number(1),
+        ));
+    let new: SourceFile = SourceFile::default()
+        .with_declaration(
+            Message::from("Position")
+                .with_field(("x", SignedInt32))
+                .with_field(("y", SignedInt32)),
+        )
+        .with_declaration(Enum::from("Status"))
+        .with_declaration(Message::from("Shape").with_field(
+            MessageField::from(("origin", Named("Position".to_string()))).with_field_number(1),
+        ))
+        .with_declaration(Message::from("Size").with_field(("width", UnsignedInt32)));
+    let report: CompatReport = check_compat(&old, &new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "Point",
+            ChangeKind::MessageRenamed {
+                new_name: "Position".to_string(),
+            },
+        ),
+        Change::new("Empty", ChangeKind::DeclarationRemoved),
+        Change::new("Status", ChangeKind::DeclarationKindChanged),
+        Change::new(
+            "Shape.origin",
+            ChangeKind::FieldTypeEquivalent {
+                old_type: Named("Point".to_string()),
+                new_type: Named("Position".to_string()),
+            },
+        ),
+        Change::new("Size", ChangeKind::DeclarationAdded),
+    ]
+    .into();
+    assert_eq!(report, expected);
+    assert_eq!(
+        report.to_string(),
+        "breaking `Point`: message renamed to `Position`\n\
+         breaking `Empty`: declaration removed\n\
+         breaking `Status`: declaration kind changed\n\
+         safe `Shape.origin`: field type changed from `Point` to the equivalent `Position`\n\
+         safe `Size`: declaration added"
+    );
+}
+
+#[test]
+fn enums() {
+    let old: SourceFile = SourceFile::default().with_declaration(
+        Enum::from("Status")
+            .with_case(("ACTIVE", 1))
+            .with_case(("INACTIVE", 2))
+            .with_case(("BANNED", 3)),
+    );
+    let new: SourceFile = SourceFile::default().with_declaration(
+        Enum::from("Status")
+            .with_case(("ACTIVE", 1))
+            .with_case(("INACTIVE", 4))
+            .with_case(("PENDING", 5)),
+    );
+    let report: CompatReport = check_compat(&old, &new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "Status.INACTIVE",
+            ChangeKind::CaseNumberChanged {
+                old_number: 2,
+                new_number: 4,
+            },
+        ),
+        Change::new("Status.BANNED", ChangeKind::CaseRemoved),
+        Change::new("Status.PENDING", ChangeKind::CaseAdded),
+    ]
+    .into();
+    assert_eq!(report, expected);
+    assert_eq!(report.breaking_changes().count(), 1);
+}
+
+#[test]
+fn variants() {
+    let old: SourceFile = SourceFile::default().with_declaration(
+        Variant::from("Value")
+            .with_case(("text", TypeTag::String, 1))
+            .with_case(("number", UnsignedInt64, 2)),
+    );
+    let new: SourceFile = SourceFile::default().with_declaration(
+        Variant::from("Value")
+            .with_case(("text", TypeTag::String, 1))
+            .with_case(("flag", Boolean, 2))
+            .with_case(("data", TypeTag::Bytes, 3)),
+    );
+    let report: CompatReport = check_compat(&old, &new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "Value.number",
+            ChangeKind::FieldNumberReused { field_number: 2 },
+        ),
+        Change::new("Value.data", ChangeKind::FieldAdded),
+    ]
+    .into();
+    assert_eq!(report, expected);
+}
+
+#[test]
+fn services() {
+    let messages: [Message; 3] = [
+        Message::from("GetUser"),
+        Message::from("User"),
+        Message::from("DeleteUser"),
+    ];
+    let mut old: SourceFile = SourceFile::default().with_declaration(
+        Service::from("Users")
+            .with_rpc(("Get", "GetUser", "User"))
+            .with_rpc(("Delete", "DeleteUser", "User"))
+            .with_rpc(("List", "GetUser", "User")),
+    );
+    let mut new: SourceFile = SourceFile::default().with_declaration(
+        Service::from("Users")
+            .with_rpc(("Delete", "DeleteUser", "User"))
+            .with_rpc(("Get", "GetUser", "GetUser"))
+            .with_rpc(("Create", "User", "User")),
+    );
+    for message in messages {
+        old.add_declaration(message.clone());
+        new.add_declaration(message);
+    }
+    let report: CompatReport = check_compat(&old, &new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "Users.Get",
+            ChangeKind::RpcMethodIdChanged {
+                old_method_id: 1,
+                new_method_id: 2,
+            },
+        ),
+        Change::new("Users.Get", ChangeKind::RpcMessagesChanged),
+        Change::new(
+            "Users.Delete",
+            ChangeKind::RpcMethodIdChanged {
+                old_method_id: 2,
+                new_method_id: 1,
+            },
+        ),
+        Change::new("Users.List", ChangeKind::RpcRemoved),
+        Change::new("Users.Create", ChangeKind::RpcAdded),
+    ]
+    .into();
+    assert_eq!(report, expected);
+}
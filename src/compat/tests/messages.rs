@@ -0,0 +1,169 @@
+use crate::compat::{check_compat, Change, ChangeKind, CompatReport};
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::Named;
+use crate::tree::{Enum, Message, MessageField, Reserved, SourceFile, TypeTag};
+
+fn user() -> Message {
+    Message::from("User")
+        .with_field(("id", UnsignedInt64))
+        .with_field(MessageField::from(("name", TypeTag::String)).with_field_number(1))
+        .with_field(MessageField::from(("age", UnsignedInt32)).with_field_number(2))
+}
+
+fn check(old: Message, new: Message) -> CompatReport {
+    let status: Enum = Enum::from("Status").with_case(("ACTIVE", 1));
+    let old: SourceFile = SourceFile::default()
+        .with_declaration(status.clone())
+        .with_declaration(old);
+    let new: SourceFile = SourceFile::default()
+        .with_declaration(status)
+        .with_declaration(new);
+    check_compat(&old, &new)
+}
+
+#[test]
+fn unchanged() {
+    let report: CompatReport = check(user(), user());
+    assert_eq!(report, CompatReport::default());
+    assert!(report.is_compatible());
+}
+
+#[test]
+fn safe_changes() {
+    let new: Message = Message::from("User")
+        .with_field(("id", UnsignedInt64))
+        .with_field(MessageField::from(("full_name", TypeTag::String)).with_field_number(1))
+        .with_field(
+            MessageField::from(("status", Named("Status".to_string()))).with_field_number(2),
+        )
+        .with_field(MessageField::from(("email", TypeTag::String)).with_field_number(3));
+    let report: CompatReport = check(user(), new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "User.name",
+            ChangeKind::FieldRenamed {
+                new_name: "full_name".to_string(),
+            },
+        ),
+        Change::new(
+            "User.age",
+            ChangeKind::FieldRenamed {
+                new_name: "status".to_string(),
+            },
+        ),
+        Change::new(
+            "User.age",
+            ChangeKind::FieldTypeEquivalent {
+                old_type: UnsignedInt32.into(),
+                new_type: Named("Status".to_string()),
+            },
+        ),
+        Change::new("User.email", ChangeKind::FieldAdded),
+    ]
+    .into();
+    assert_eq!(report, expected);
+    assert!(report.is_compatible());
+}
+
+#[test]
+fn breaking_changes() {
+    let new: Message = Message::from("User")
+        .with_field(("id", TypeTag::String))
+        .with_field(("created", UnsignedInt64))
+        .with_field(MessageField::from(("name", TypeTag::Bytes)).with_field_number(1))
+        .with_field(MessageField::from(("score", Float64)).with_field_number(2));
+    let report: CompatReport = check(user(), new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "User.id",
+            ChangeKind::FieldTypeChanged {
+                old_type: UnsignedInt64.into(),
+                new_type: TypeTag::String,
+            },
+        ),
+        Change::new("User.created", ChangeKind::RequiredFieldAdded),
+        Change::new(
+            "User.name",
+            ChangeKind::FieldTypeChanged {
+                old_type: TypeTag::String,
+                new_type: TypeTag::Bytes,
+            },
+        ),
+        Change::new(
+            "User.age",
+            ChangeKind::FieldNumberReused { field_number: 2 },
+        ),
+    ]
+    .into();
+    assert_eq!(report, expected);
+    assert!(!report.is_compatible());
+    assert_eq!(report.breaking_changes().count(), 4);
+}
+
+#[test]
+fn removed_fields() {
+    let new: Message = Message::from("User")
+        .with_field(MessageField::from(("name", TypeTag::String)).with_field_number(1))
+        .with_reserved(Reserved::Numbers(vec![(2, 2)]));
+    let report: CompatReport = check(user(), new);
+
+    let expected: CompatReport = vec![
+        Change::new("User.id", ChangeKind::RequiredFieldRemoved),
+        Change::new("User.age", ChangeKind::FieldRemoved),
+    ]
+    .into();
+    assert_eq!(report, expected);
+    assert!(!report.is_compatible());
+}
+
+#[test]
+fn reserved_number_reused() {
+    let old: Message = user().with_reserved(Reserved::Numbers(vec![(3, 5)]));
+    let new: Message = user()
+        .with_field(MessageField::from(("email", TypeTag::String)).with_field_number(4))
+        .with_field(MessageField::from(("phone", TypeTag::String)).with_field_number(6));
+    let report: CompatReport = check(old, new);
+
+    let expected: CompatReport = vec![
+        Change::new(
+            "User.email",
+            ChangeKind::FieldNumberReused { field_number: 4 },
+        ),
+        Change::new("User.phone", ChangeKind::FieldAdded),
+    ]
+    .into();
+    assert_eq!(report, expected);
+}
+
+#[test]
+fn encoding_options() {
+    let old: Message = Message::from("Stats")
+        .with_field(MessageField::from(("count", UnsignedInt32)).with_field_number(1))
+        .with_field(
+            MessageField::from(("samples", TypeTag::List(Box::new(UnsignedInt32.into()))))
+                .with_field_number(2),
+        );
+    let new: Message = Message::from("Stats")
+        .with_field(
+            MessageField::from(("count", UnsignedInt32))
+                .with_field_number(1)
+                .with_fixed(),
+        )
+        .with_field(
+            MessageField::from(("samples", TypeTag::List(Box::new(UnsignedInt32.into()))))
+                .with_field_number(2)
+                .with_packed(),
+        );
+    let report: CompatReport = check(old, new);
+
+    let expected: CompatReport = vec![
+        Change::new("Stats.count", ChangeKind::FieldFixedChanged),
+        Change::new("Stats.samples", ChangeKind::FieldPackedChanged),
+    ]
+    .into();
+    assert_eq!(report, expected);
+    assert!(!report.is_compatible());
+    assert_eq!(report.safe_changes().count(), 1);
+}
@@ -1,3 +1,225 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::{Message, MessageField, TypeTag, WithTypeTag};
+use crate::validate::ValidateError;
+
 /// An error generating code.
+///
+/// Errors are located by their path in the schema. (`Message` or `Message.field`)
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
-pub enum GenError {}
+pub enum GenError {
+    /// The type of the field is not supported by the generator.
+    UnsupportedType {
+        message: String,
+        field: String,
+        type_tag: TypeTag,
+    },
+
+    /// The field does not have a field number where one is needed.
+    MissingFieldNumber { message: String, field: String },
+
+    /// The field is declared fixed-width but its type has no fixed-width encoding.
+    UnsupportedFixed { message: String, field: String },
+
+    /// The field is declared packed but its type has no packed encoding.
+    UnsupportedPacked { message: String, field: String },
+
+    /// The name is not a valid identifier.
+    InvalidIdentifier { location: String, name: String },
+
+    /// The named type does not resolve to a declaration.
+    UnresolvedType { location: String, name: String },
+
+    /// The message contains itself, directly or through other messages.
+    RecursiveMessage { message: String },
+
+    /// The rpc name is declared more than once in the service.
+    DuplicateRpcName { service: String, rpc: String },
+
+    /// The source file failed schema validation.
+    InvalidSchema(ValidateError),
+
+    /// The module path is generated from more than one source file. (`path` is one of the files)
+    DuplicateModule { module: String, path: String },
+
+    /// The path is not the path of a source file in the source set.
+    UnknownSourceFile { path: String },
+
+    /// The name converts to the same rust name as another name in its scope. (`Message.field`)
+    NameCollision { name: String, rust_name: String },
+
+    /// The service requires a codec trait the messages are not generated with.
+    MissingCodecTrait {
+        service: String,
+        codec_trait: String,
+    },
+}
+
+impl GenError {
+    //! Construction
+
+    /// Creates an unsupported type error for the message field.
+    pub fn unsupported_type(message: &Message, field: &MessageField) -> Self {
+        Self::UnsupportedType {
+            message: message.name().to_string(),
+            field: field.name().to_string(),
+            type_tag: field.type_tag().clone(),
+        }
+    }
+
+    /// Creates a missing field number error for the message field.
+    pub fn missing_field_number(message: &Message, field: &MessageField) -> Self {
+        Self::MissingFieldNumber {
+            message: message.name().to_string(),
+            field: field.name().to_string(),
+        }
+    }
+}
+
+impl GenError {
+    //! Location
+
+    /// Sets the location of the error if it has not been located yet.
+    ///
+    /// The generators locate errors from the innermost scope outwards, so the most specific
+    /// location is kept.
+    pub fn at<S>(self, location: S) -> Self
+    where
+        S: Into<String>,
+    {
+        match self {
+            Self::InvalidIdentifier { location: at, name } if at.is_empty() => {
+                Self::InvalidIdentifier {
+                    location: location.into(),
+                    name,
+                }
+            }
+            Self::UnresolvedType { location: at, name } if at.is_empty() => Self::UnresolvedType {
+                location: location.into(),
+                name,
+            },
+            error => error,
+        }
+    }
+
+    /// Writes the location suffix. (nothing for unlocated errors)
+    fn write_location(f: &mut Formatter<'_>, location: &str) -> std::fmt::Result {
+        if location.is_empty() {
+            Ok(())
+        } else {
+            write!(f, " in `{}`", location)
+        }
+    }
+}
+
+impl From<ValidateError> for GenError {
+    fn from(error: ValidateError) -> Self {
+        Self::InvalidSchema(error)
+    }
+}
+
+impl Display for GenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedType {
+                message,
+                field,
+                type_tag,
+            } => write!(
+                f,
+                "unsupported type `{}` for field `{}.{}`",
+                type_tag, message, field
+            ),
+            Self::MissingFieldNumber { message, field } => {
+                write!(f, "missing field number for field `{}.{}`", message, field)
+            }
+            Self::UnsupportedFixed { message, field } => write!(
+                f,
+                "field `{}.{}` cannot be fixed-width for its type",
+                message, field
+            ),
+            Self::UnsupportedPacked { message, field } => write!(
+                f,
+                "field `{}.{}` cannot be packed for its type",
+                message, field
+            ),
+            Self::InvalidIdentifier { location, name } => {
+                write!(f, "invalid identifier `{}`", name)?;
+                Self::write_location(f, location)
+            }
+            Self::UnresolvedType { location, name } => {
+                write!(f, "unresolved type `{}`", name)?;
+                Self::write_location(f, location)
+            }
+            Self::RecursiveMessage { message } => {
+                write!(f, "message `{}` contains itself", message)
+            }
+            Self::DuplicateRpcName { service, rpc } => {
+                write!(f, "duplicate rpc name `{}.{}`", service, rpc)
+            }
+            Self::NameCollision { name, rust_name } => write!(
+                f,
+                "name `{}` collides with another name as `{}`",
+                name, rust_name
+            ),
+            Self::DuplicateModule { module, path } => write!(
+                f,
+                "module `{}` of file `{}` is generated from more than one file",
+                module, path
+            ),
+            Self::UnknownSourceFile { path } => {
+                write!(f, "file `{}` is not in the source set", path)
+            }
+            Self::MissingCodecTrait {
+                service,
+                codec_trait,
+            } => write!(
+                f,
+                "service `{}` requires the messages to implement `{}`",
+                service, codec_trait
+            ),
+            Self::InvalidSchema(error) => write!(f, "invalid schema:\n{}", error),
+        }
+    }
+}
+
+impl Error for GenError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::GenError;
+    use crate::tree::PrimitiveType::UnsignedInt8;
+    use crate::tree::{Message, MessageField};
+
+    #[test]
+    fn display() {
+        let message: Message = "MyMessage".into();
+        let field: MessageField = ("one", UnsignedInt8).into();
+
+        let error: GenError = GenError::unsupported_type(&message, &field);
+        assert_eq!(
+            error.to_string(),
+            "unsupported type `u8` for field `MyMessage.one`"
+        );
+
+        let error: GenError = GenError::missing_field_number(&message, &field);
+        assert_eq!(
+            error.to_string(),
+            "missing field number for field `MyMessage.one`"
+        );
+
+        let error: GenError = GenError::UnresolvedType {
+            location: String::default(),
+            name: "Other".to_string(),
+        };
+        assert_eq!(error.to_string(), "unresolved type `Other`");
+        let error: GenError = error.at("MyMessage.two").at("MyMessage");
+        assert_eq!(
+            error.to_string(),
+            "unresolved type `Other` in `MyMessage.two`"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/// An encoding or decoding trait implemented by the generated message structs.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum CodecTrait {
+    /// `EncodedLen`
+    EncodedLen,
+
+    /// `EncodeToSlice` (requires `EncodedLen`)
+    EncodeToSlice,
+
+    /// `EncodeToWrite` (requires `EncodedLen`, nested messages are written length-prefixed)
+    EncodeToWrite,
+
+    /// `DecodeFromReadLengthPrefixed` (requires `DecodeFromRead`)
+    DecodeFromReadLengthPrefixed,
+
+    /// `DecodeFromRead` (requires `DecodeFromReadLengthPrefixed`, nested messages are read
+    /// length-prefixed)
+    DecodeFromRead,
+}
+
+impl CodecTrait {
+    //! Constants
+
+    /// All the codec traits.
+    pub const ALL: [Self; 5] = [
+        Self::EncodedLen,
+        Self::EncodeToSlice,
+        Self::EncodeToWrite,
+        Self::DecodeFromReadLengthPrefixed,
+        Self::DecodeFromRead,
+    ];
+}
+
+impl CodecTrait {
+    //! Properties
+
+    /// Gets the rust trait name.
+    pub const fn trait_name(&self) -> &'static str {
+        match self {
+            Self::EncodedLen => "EncodedLen",
+            Self::EncodeToSlice => "EncodeToSlice",
+            Self::EncodeToWrite => "EncodeToWrite",
+            Self::DecodeFromReadLengthPrefixed => "DecodeFromReadLengthPrefixed",
+            Self::DecodeFromRead => "DecodeFromRead",
+        }
+    }
+
+    /// Gets the codec trait required by the codec trait. (`None` if it has no requirement)
+    pub const fn requires(&self) -> Option<Self> {
+        match self {
+            Self::EncodedLen => None,
+            Self::EncodeToSlice => Some(Self::EncodedLen),
+            Self::EncodeToWrite => Some(Self::EncodedLen),
+            Self::DecodeFromReadLengthPrefixed => Some(Self::DecodeFromRead),
+            Self::DecodeFromRead => Some(Self::DecodeFromReadLengthPrefixed),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashSet;
+
+use code_gen::rust::Access::Public;
+use code_gen::rust::{
+    Enum as RustEnum, EnumVariant, Function, ImplBlock, Match, MatchCase, Receiver, Reference,
+    Signature, Source, TypeTag as RustType, WithAccess, WithAttributes,
+    WithComments as WithRustComments, WithDerives, WithFnGenerics, WithFunctions, WithReceiver,
+    WithResult, WithVarParams, WithVariants,
+};
+use code_gen::{WithName, WithStatements};
+
+use crate::gen::rust::{CodecTrait, Naming, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::Enum;
+use crate::validate::validate_enum;
+
+/// The name of the variant holding unrecognized case numbers.
+pub const UNRECOGNIZED_CASE_NAME: &str = "Unrecognized";
+
+/// Responsible for generating code for enum types.
+#[derive(Clone, Debug, Default)]
+pub struct GenEnum {
+    naming: Naming,
+    options: RustGenOptions,
+}
+
+impl GenEnum {
+    //! Options
+
+    /// Sets the naming.
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Sets the options. (the derives, attributes & codec traits apply to the enums)
+    pub fn with_options(mut self, options: RustGenOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl GenEnum {
+    //! Gen
+
+    /// Generates the source code for the enum. The enum is validated first.
+    pub fn gen(&self, enumeration: &Enum) -> Result<Source, GenError> {
+        self.gen_source(enumeration)
+            .map_err(|e| e.at(enumeration.name()))
+    }
+
+    /// Generates the source code for the enum without locating the errors.
+    fn gen_source(&self, enumeration: &Enum) -> Result<Source, GenError> {
+        validate_enum(enumeration)?;
+        self.check_cases(enumeration)?;
+
+        let mut source: Source = Source::default();
+        source.add_statement(self.gen_enum(enumeration)?);
+        source.add_statement(self.gen_impl_default(enumeration)?);
+        source.add_statement(self.gen_impl_numbers(enumeration)?);
+        source.add_statement(self.gen_impl_from(enumeration)?);
+        source.add_statement(self.gen_impl_try_from(enumeration)?);
+        if self.options.implements(CodecTrait::DecodeFromRead) {
+            source.add_statement(self.gen_impl_decode_from_read(enumeration)?);
+        }
+        Ok(source)
+    }
+
+    /// Checks the enum case names do not collide. (duplicate names & numbers are reported by the
+    /// validator)
+    fn check_cases(&self, enumeration: &Enum) -> Result<(), GenError> {
+        let mut rust_names: HashSet<String> = HashSet::default();
+        rust_names.insert(UNRECOGNIZED_CASE_NAME.to_string());
+        for case in enumeration.cases() {
+            Naming::check_collision(
+                &mut rust_names,
+                enumeration.name(),
+                case.name(),
+                self.naming
+                    .case_name(enumeration.name(), case.name())
+                    .map_err(|e| e.at(format!("{}.{}", enumeration.name(), case.name())))?,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GenEnum {
+    //! Declaration
+
+    /// Generates the enum declaration.
+    fn gen_enum(&self, enumeration: &Enum) -> Result<RustEnum, GenError> {
+        let mut e: RustEnum = self.naming.type_name(enumeration.name())?.into();
+        e.set_access(Public);
+
+        for line in enumeration.to_string().lines() {
+            e.add_comment(line);
+        }
+        for derive in self.derives() {
+            e.add_derive(derive);
+        }
+        for attribute in self.options.attributes(enumeration.name()) {
+            e.add_attribute(attribute.as_str());
+        }
+
+        for case in enumeration.cases() {
+            e.add_variant(self.naming.case_name(enumeration.name(), case.name())?);
+        }
+        let mut unrecognized: EnumVariant =
+            EnumVariant::from(UNRECOGNIZED_CASE_NAME).with_field("u32");
+        unrecognized.add_comment("A case number not declared by this version of the enum.");
+        e.add_variant(unrecognized);
+
+        Ok(e)
+    }
+
+    /// Gets the derives for the enum declaration.
+    ///
+    /// Enums are always `Copy` & `Clone` since the generated field getters return them by value.
+    /// The derives set in the options are added, except `Default` which is implemented for the
+    /// first declared case.
+    fn derives(&self) -> Vec<&str> {
+        match self.options.derives() {
+            Some(derives) => {
+                let mut result: Vec<&str> = vec!["Copy", "Clone"];
+                for derive in derives {
+                    if derive != "Default" && !result.contains(&derive.as_str()) {
+                        result.push(derive.as_str());
+                    }
+                }
+                result
+            }
+            None => vec![
+                "Copy",
+                "Clone",
+                "Ord",
+                "PartialOrd",
+                "Eq",
+                "PartialEq",
+                "Hash",
+                "Debug",
+            ],
+        }
+    }
+}
+
+impl GenEnum {
+    //! Default
+
+    /// Generates the impl block for the `Default` trait. (the first declared case)
+    fn gen_impl_default(&self, enumeration: &Enum) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(enumeration.name())?.into();
+        block.set_for_trait("Default");
+
+        let default: String = match enumeration.cases().first() {
+            Some(case) => format!(
+                "Self::{}",
+                self.naming.case_name(enumeration.name(), case.name())?
+            ),
+            None => format!("Self::{}(0)", UNRECOGNIZED_CASE_NAME),
+        };
+        let signature: Signature = Signature::from("default").with_result("Self");
+        let mut function: Function = Function::from(signature);
+        function.add_literal(default);
+        block.add_function(function);
+
+        Ok(block)
+    }
+}
+
+impl GenEnum {
+    //! Numbers
+
+    /// Generates the impl block converting between cases & case numbers.
+    fn gen_impl_numbers(&self, enumeration: &Enum) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(enumeration.name())?.into();
+        block.add_comment("Numbers");
+        block.add_function(self.gen_fn_from_number(enumeration)?);
+        block.add_function(self.gen_fn_number(enumeration)?);
+        Ok(block)
+    }
+
+    /// Generates the `from_number` function. (unrecognized numbers are preserved)
+    fn gen_fn_from_number(&self, enumeration: &Enum) -> Result<Function, GenError> {
+        let signature: Signature = Signature::from("from_number")
+            .with_param(("number", "u32"))
+            .with_result("Self");
+        let mut function: Function = Function::from(signature);
+        function.set_access(Public);
+        function.add_comment("Gets the case with the case number.");
+
+        let mut match_statement: Match = Match::from("number");
+        for case in enumeration.cases() {
+            let case_name: String = self.naming.case_name(enumeration.name(), case.name())?;
+            match_statement.add_match_case(
+                MatchCase::from(format!("{}", case.number()))
+                    .with_literal(format!("Self::{}", case_name)),
+            );
+        }
+        match_statement.add_match_case(
+            MatchCase::from("number")
+                .with_literal(format!("Self::{}(number)", UNRECOGNIZED_CASE_NAME)),
+        );
+        function.add_statement(match_statement);
+
+        Ok(function)
+    }
+
+    /// Generates the `number` function.
+    fn gen_fn_number(&self, enumeration: &Enum) -> Result<Function, GenError> {
+        let signature: Signature = Signature::from("number")
+            .with_receiver(Receiver::Borrowed)
+            .with_result("u32");
+        let mut function: Function = Function::from(signature);
+        function.set_access(Public);
+        function.add_comment("Gets the case number.");
+
+        let mut match_statement: Match = Match::from("self");
+        for case in enumeration.cases() {
+            let case_name: String = self.naming.case_name(enumeration.name(), case.name())?;
+            match_statement.add_match_case(
+                MatchCase::from(format!("Self::{}", case_name))
+                    .with_literal(format!("{}", case.number())),
+            );
+        }
+        match_statement.add_match_case(
+            MatchCase::from(format!("Self::{}(number)", UNRECOGNIZED_CASE_NAME))
+                .with_literal("*number"),
+        );
+        function.add_statement(match_statement);
+
+        Ok(function)
+    }
+}
+
+impl GenEnum {
+    //! Conversions
+
+    /// Generates the impl block for the `From<E> for u32` conversion.
+    fn gen_impl_from(&self, enumeration: &Enum) -> Result<ImplBlock, GenError> {
+        let type_name: String = self.naming.type_name(enumeration.name())?;
+        let mut block: ImplBlock = "u32".into();
+        block.set_for_trait(RustType::from("From").with_generic(type_name.as_str()));
+
+        let signature: Signature = Signature::from("from")
+            .with_param(("value", type_name.as_str()))
+            .with_result("Self");
+        let mut function: Function = Function::from(signature);
+        function.add_literal("value.number()");
+        block.add_function(function);
+
+        Ok(block)
+    }
+
+    /// Generates the impl block for the `TryFrom<u32>` conversion.
+    ///
+    /// The conversion fails with the case number when it is not declared.
+    fn gen_impl_try_from(&self, enumeration: &Enum) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(enumeration.name())?.into();
+        block.set_for_trait(RustType::from("TryFrom").with_generic("u32"));
+        block.add_semi("type Error = u32");
+
+        let result_type: RustType = RustType::from("Result")
+            .with_generic("Self")
+            .with_generic("Self::Error");
+        let signature: Signature = Signature::from("try_from")
+            .with_param(("number", "u32"))
+            .with_result(result_type);
+        let mut function: Function = Function::from(signature);
+        let match_statement: Match = Match::from("Self::from_number(number)")
+            .with_match_case(
+                MatchCase::from(format!("Self::{}(number)", UNRECOGNIZED_CASE_NAME))
+                    .with_literal("Err(number)"),
+            )
+            .with_match_case(MatchCase::from("value").with_literal("Ok(value)"));
+        function.add_statement(match_statement);
+        block.add_function(function);
+
+        Ok(block)
+    }
+}
+
+impl GenEnum {
+    //! DecodeFromRead
+
+    /// Generates the impl block for implementing the `DecodeFromRead` trait.
+    ///
+    /// Enums are decoded from a case number & never fail on unrecognized numbers.
+    fn gen_impl_decode_from_read(&self, enumeration: &Enum) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(enumeration.name())?.into();
+        block.set_for_trait("DecodeFromRead");
+
+        let result_type: RustType = RustType::from("Result")
+            .with_generic("Self")
+            .with_generic("io::Error");
+        let signature: Signature = Signature::from("decode_from_read")
+            .with_generic(("R", "io::Read"))
+            .with_param(("r", RustType::from("R").to_reference(Reference::MUT)))
+            .with_result(result_type);
+        let mut function: Function = Function::from(signature);
+        function.add_literal("Ok(Self::from_number(read::read_u32_var(r)?))");
+        block.add_function(function);
+
+        Ok(block)
+    }
+}
@@ -0,0 +1,6 @@
+pub use gen_enum::*;
+
+mod gen_enum;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenEnum;
+use crate::gen::GenError;
+use crate::tree::{Enum, EnumCase, WithComments};
+
+#[test]
+fn enums() -> Result<(), GenError> {
+    let mut status: Enum = "Status".into();
+    status.add_comment("The status of an account.");
+    status.add_case(EnumCase::from(("ACTIVE", 1)).with_comment("The account is active."));
+    status.add_case(("DISABLED", 2));
+
+    let source: Source = GenEnum::default().gen(&status)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("enums.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
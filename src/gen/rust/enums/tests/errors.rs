@@ -0,0 +1,64 @@
+use crate::gen::rust::GenEnum;
+use crate::gen::GenError;
+use crate::tree::Enum;
+use crate::validate::Diagnostic;
+
+#[test]
+fn duplicate_case_name() {
+    let e: Enum = Enum::from("Dup")
+        .with_case(("ONE", 1))
+        .with_case(("ONE", 2));
+    let result: Result<_, GenError> = GenEnum::default().gen(&e);
+    let expected: GenError =
+        GenError::InvalidSchema(vec![Diagnostic::new("Dup.ONE", "duplicate name `ONE`")].into());
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn duplicate_case_number() {
+    let e: Enum = Enum::from("Dup")
+        .with_case(("ONE", 1))
+        .with_case(("TWO", 1));
+    let result: Result<_, GenError> = GenEnum::default().gen(&e);
+    let expected: GenError =
+        GenError::InvalidSchema(vec![Diagnostic::new("Dup.TWO", "duplicate case number 1")].into());
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unrecognized_case_name() {
+    let e: Enum = Enum::from("Dup").with_case(("Unrecognized", 1));
+    let result: Result<_, GenError> = GenEnum::default().gen(&e);
+    let expected: GenError = GenError::NameCollision {
+        name: "Dup.Unrecognized".to_string(),
+        rust_name: "Unrecognized".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn colliding_case_name() {
+    let e: Enum = Enum::from("Status")
+        .with_case(("IN_PROGRESS", 1))
+        .with_case(("InProgress", 2));
+    let result: Result<_, GenError> = GenEnum::default().gen(&e);
+    let expected: GenError = GenError::NameCollision {
+        name: "Status.InProgress".to_string(),
+        rust_name: "InProgress".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn case_number_out_of_range() {
+    let e: Enum = Enum::from("Status").with_case(("ZERO", 0));
+    let result: Result<_, GenError> = GenEnum::default().gen(&e);
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new(
+            "Status.ZERO",
+            "case number 0 is out of range (1..=536870911)",
+        )]
+        .into(),
+    );
+    assert_eq!(result.err(), Some(expected));
+}
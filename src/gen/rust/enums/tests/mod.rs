@@ -0,0 +1,3 @@
+mod enums;
+mod errors;
+mod options;
@@ -0,0 +1,34 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{CodecTrait, GenEnum, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::Enum;
+
+#[test]
+fn options() -> Result<(), GenError> {
+    let status: Enum = Enum::from("Status")
+        .with_case(("ACTIVE", 1))
+        .with_case(("DISABLED", 2));
+
+    let options: RustGenOptions = RustGenOptions::default()
+        .with_derives(["Clone", "Debug", "Default"])
+        .with_attribute("Status", "non_exhaustive")
+        .with_codec_traits([CodecTrait::EncodedLen, CodecTrait::EncodeToWrite]);
+    let source: Source = GenEnum::default().with_options(options).gen(&status)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("options.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+/// The rust type backing map fields.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub enum MapBacking {
+    /// Maps are backed by a `BTreeMap`. (the message struct can derive `Ord` & `Hash`)
+    #[default]
+    BTreeMap,
+
+    /// Maps are backed by a `HashMap`. (the message struct cannot derive `PartialOrd` or `Hash`)
+    HashMap,
+}
+
+impl MapBacking {
+    //! Properties
+
+    /// Gets the rust type name.
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::BTreeMap => "BTreeMap",
+            Self::HashMap => "HashMap",
+        }
+    }
+
+    /// Checks if the backing type implements `PartialOrd`, `Ord` & `Hash`.
+    pub const fn is_ordered(&self) -> bool {
+        matches!(self, Self::BTreeMap)
+    }
+}
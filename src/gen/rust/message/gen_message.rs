@@ -1,43 +1,296 @@
+use std::collections::HashSet;
+
 use code_gen::rust::Source;
-use code_gen::WithStatements;
+use code_gen::{WithName, WithStatements};
 
 use crate::gen::rust::{
-    GenMessageDecode, GenMessageEncode, GenMessageField, GenMessageStruct, Naming, Typing,
+    CodecTrait, GenEnum, GenMessageDecode, GenMessageEncode, GenMessageField, GenMessageStruct,
+    GenService, GenVariant, Naming, RustGenOptions, Typing,
 };
 use crate::gen::GenError;
-use crate::tree::Message;
+use crate::tree::{
+    Message, MessageField, PrimitiveType, Service, SourceDec, SourceFile, TypeTag, Variant,
+    WithTypeTag,
+};
+use crate::validate::validate_message;
+
+/// The name of the member holding the unknown fields when they are preserved.
+pub const UNKNOWN_FIELDS_NAME: &str = "unknown_fields";
 
 /// Responsible for generating code for message types.
 #[derive(Clone, Debug, Default)]
 pub struct GenMessage {
     naming: Naming,
     typing: Typing,
+    options: RustGenOptions,
+}
+
+impl GenMessage {
+    //! Options
+
+    /// Sets the options. (the typing uses the map backing of the options)
+    pub fn with_options(mut self, options: RustGenOptions) -> Self {
+        self.typing = self.typing.with_map_backing(options.map_backing());
+        self.options = options;
+        self
+    }
+
+    /// Sets the naming. (used for converting declared names to rust names)
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.typing = self.typing.with_naming(naming.clone());
+        self.naming = naming;
+        self
+    }
+
+    /// Gets the naming.
+    pub fn naming(&self) -> &Naming {
+        &self.naming
+    }
+
+    /// Gets the typing.
+    pub fn typing(&self) -> &Typing {
+        &self.typing
+    }
+
+    /// Gets the options.
+    pub fn options(&self) -> &RustGenOptions {
+        &self.options
+    }
 }
 
 impl GenMessage {
     //! Gen
 
+    /// Generates the source code for the declarations in the source file.
+    ///
+    /// Named field types are resolved against the declarations in the source file & the imported
+    /// source files. (declarations in the source file take precedence, then the imports in import
+    /// order) The source file is not validated. (`GenSourceFile` validates & generates whole
+    /// source files)
+    pub fn gen_declarations(
+        &self,
+        source_file: &SourceFile,
+        imports: &[&SourceFile],
+    ) -> Result<Source, GenError> {
+        // later source files replace the declarations of earlier ones, so the first import wins
+        let mut typing: Typing = self.typing.clone();
+        for imported in imports.iter().rev() {
+            typing = typing.with_source_file(imported);
+        }
+        let gen: GenMessage = Self {
+            typing: typing.with_source_file(source_file),
+            ..self.clone()
+        };
+
+        let mut rust_names: HashSet<String> = HashSet::default();
+        for declaration in source_file.declarations() {
+            let rust_name: String = self
+                .naming
+                .type_name(declaration.name())
+                .map_err(|e| e.at(declaration.name()))?;
+            Naming::check_collision(&mut rust_names, "", declaration.name(), rust_name)?;
+        }
+
+        let mut source: Source = Source::default();
+        for declaration in source_file.declarations() {
+            match declaration {
+                SourceDec::MessageDec(message) => source.add_statement(gen.gen(message)?),
+                SourceDec::EnumDec(enumeration) => source.add_statement(
+                    GenEnum::default()
+                        .with_naming(self.naming.clone())
+                        .with_options(self.options.clone())
+                        .gen(enumeration)?,
+                ),
+                SourceDec::VariantDec(variant) => source.add_statement(gen.gen_variant(variant)?),
+                SourceDec::ServiceDec(service) => source.add_statement(gen.gen_service(service)?),
+            }
+        }
+        Ok(source)
+    }
+
+    /// Generates the source code for the variant.
+    pub fn gen_variant(&self, variant: &Variant) -> Result<Source, GenError> {
+        let mut path: Vec<String> = vec![variant.name().to_string()];
+        for case in variant.cases() {
+            self.check_named_recursion(case.type_tag(), &mut path)
+                .map_err(|e| e.at(format!("{}.{}", variant.name(), case.name())))?;
+        }
+        GenVariant::new(&self.naming, &self.typing, &self.options).gen(variant)
+    }
+
+    /// Generates the source code for the service.
+    pub fn gen_service(&self, service: &Service) -> Result<Source, GenError> {
+        GenService::new(&self.naming, &self.typing, &self.options).gen(service)
+    }
+
     /// Generates the source code for the message.
+    ///
+    /// The message is validated on its own. (named types are resolved by the typing)
     pub fn gen(&self, message: &Message) -> Result<Source, GenError> {
+        self.gen_source(message).map_err(|e| e.at(message.name()))
+    }
+
+    /// Generates the source code for the message without locating the errors.
+    fn gen_source(&self, message: &Message) -> Result<Source, GenError> {
+        validate_message(message)?;
+        self.check_fields(message)?;
+        self.check_recursion(message, &mut Vec::default())?;
+
         let mut source: Source = Source::default();
 
-        let gen: GenMessageStruct = GenMessageStruct::new(&self.naming, &self.typing);
+        let gen: GenMessageStruct =
+            GenMessageStruct::new(&self.naming, &self.typing, &self.options);
         source.add_statement(gen.gen_struct(message)?);
 
-        let gen: GenMessageField = GenMessageField::new(&self.naming, &self.typing);
+        let gen: GenMessageField = GenMessageField::new(&self.naming, &self.typing, &self.options);
         for field in message.fields() {
             source.add_statement(gen.gen_field(message, field)?);
         }
+        if self.options.unknown_fields().is_preserve() {
+            source.add_statement(gen.gen_unknown_fields(message)?);
+        }
 
-        let gen: GenMessageEncode = GenMessageEncode::new(&self.naming, &self.typing);
-        source.add_statement(gen.gen_impl_encoded_len(message)?);
-        source.add_statement(gen.gen_impl_encode_to_slice(message)?);
-        source.add_statement(gen.gen_impl_encode_to_write(message)?);
+        let gen: GenMessageEncode =
+            GenMessageEncode::new(&self.naming, &self.typing, &self.options);
+        if self.options.implements(CodecTrait::EncodedLen) {
+            source.add_statement(gen.gen_impl_encoded_len(message)?);
+        }
+        if self.options.implements(CodecTrait::EncodeToSlice) {
+            source.add_statement(gen.gen_impl_encode_to_slice(message)?);
+        }
+        if self.options.implements(CodecTrait::EncodeToWrite) {
+            source.add_statement(gen.gen_impl_encode_to_write(message)?);
+        }
 
-        let gen: GenMessageDecode = GenMessageDecode::new(&self.naming, &self.typing);
-        source.add_statement(gen.gen_impl_decode_from_read_length_prefixed(message)?);
-        source.add_statement(gen.gen_impl_decode_from_read(message)?);
+        let gen: GenMessageDecode =
+            GenMessageDecode::new(&self.naming, &self.typing, &self.options);
+        if self
+            .options
+            .implements(CodecTrait::DecodeFromReadLengthPrefixed)
+        {
+            source.add_statement(gen.gen_impl_decode_from_read_length_prefixed(message)?);
+        }
+        if self.options.implements(CodecTrait::DecodeFromRead) {
+            source.add_statement(gen.gen_impl_decode_from_read(message)?);
+        }
 
         Ok(source)
     }
+
+    /// Checks the message fields for colliding names, unsupported options and unsupported list
+    /// fields.
+    ///
+    /// The struct field & every generated function name of a field must not collide with the names
+    /// generated for the other fields. (a field `x` has a setter `set_x`, so there cannot also be a
+    /// field `set_x`) Duplicate names & field numbers are reported by the validator.
+    fn check_fields(&self, message: &Message) -> Result<(), GenError> {
+        let gen: GenMessageField = GenMessageField::new(&self.naming, &self.typing, &self.options);
+        let mut rust_names: HashSet<String> = HashSet::default();
+        if self.options.unknown_fields().is_preserve() {
+            rust_names.insert(UNKNOWN_FIELDS_NAME.to_string());
+        }
+        for field in message.fields() {
+            if field.is_fixed() && !self.typing.has_fixed_encoding(field.type_tag()) {
+                return Err(GenError::UnsupportedFixed {
+                    message: message.name().to_string(),
+                    field: field.name().to_string(),
+                });
+            }
+            if field.is_packed() && !self.typing.has_packed_encoding(field.type_tag()) {
+                return Err(GenError::UnsupportedPacked {
+                    message: message.name().to_string(),
+                    field: field.name().to_string(),
+                });
+            }
+            if let TypeTag::List(element) = field.type_tag() {
+                if let TypeTag::List(_) = **element {
+                    return Err(GenError::unsupported_type(message, field));
+                }
+                if field.is_required() {
+                    return Err(GenError::missing_field_number(message, field));
+                }
+            }
+            if let TypeTag::Map(key, value) = field.type_tag() {
+                self.check_map_field(message, field, key, value)?;
+            }
+            let location: String = format!("{}.{}", message.name(), field.name());
+            let member_names: Vec<String> = gen
+                .member_names(message, field)
+                .map_err(|e| e.at(location.as_str()))?;
+            for rust_name in member_names {
+                Naming::check_collision(&mut rust_names, message.name(), field.name(), rust_name)?;
+            }
+            self.typing
+                .field_type(field.type_tag())
+                .map_err(|e| e.at(location))?;
+        }
+        Ok(())
+    }
+
+    /// Checks the map field has a supported key & value type and a field number.
+    ///
+    /// Keys must be strings or non-floating point primitives. Values cannot be lists or maps.
+    fn check_map_field(
+        &self,
+        message: &Message,
+        field: &MessageField,
+        key: &TypeTag,
+        value: &TypeTag,
+    ) -> Result<(), GenError> {
+        let supported_key: bool = match key {
+            TypeTag::Primitive(PrimitiveType::Float32 | PrimitiveType::Float64) => false,
+            TypeTag::Primitive(_) | TypeTag::String => true,
+            _ => false,
+        };
+        if !supported_key || Typing::is_collection(value) {
+            return Err(GenError::unsupported_type(message, field));
+        }
+        if field.is_required() {
+            return Err(GenError::missing_field_number(message, field));
+        }
+        Ok(())
+    }
+
+    /// Checks the message does not contain itself through its named field types.
+    fn check_recursion(&self, message: &Message, path: &mut Vec<String>) -> Result<(), GenError> {
+        path.push(message.name().to_string());
+        for field in message.fields() {
+            self.check_named_recursion(field.type_tag(), path)
+                .map_err(|e| e.at(format!("{}.{}", message.name(), field.name())))?;
+        }
+        path.pop();
+        Ok(())
+    }
+
+    /// Checks the named type does not contain a declaration in the path.
+    ///
+    /// Messages are checked through their fields & variants through their cases.
+    fn check_named_recursion(
+        &self,
+        declared_type: &TypeTag,
+        path: &mut Vec<String>,
+    ) -> Result<(), GenError> {
+        let name: &str = match declared_type {
+            TypeTag::Named(name) => name.as_str(),
+            _ => return Ok(()),
+        };
+        if self.typing.enumeration(name).is_some() {
+            return Ok(());
+        }
+        if path.iter().any(|declared| declared == name) {
+            return Err(GenError::RecursiveMessage {
+                message: name.to_string(),
+            });
+        }
+        if let Some(variant) = self.typing.variant(name) {
+            path.push(name.to_string());
+            for case in variant.cases() {
+                self.check_named_recursion(case.type_tag(), path)?;
+            }
+            path.pop();
+            Ok(())
+        } else {
+            self.check_recursion(self.typing.message(name)?, path)
+        }
+    }
 }
@@ -2,29 +2,31 @@ use code_gen::rust::{
     Function, ImplBlock, Match, MatchCase, Reference, Signature, TypeTag as RustType, WhileLet,
     WithFnGenerics, WithFunctions, WithResult, WithVarParams,
 };
-use code_gen::{EmptyLine, Literal, Semi, WithName, WithStatements};
+use code_gen::{Semi, WithName, WithStatements};
 
-use crate::gen::rust::{Naming, Typing};
+use crate::gen::rust::{Naming, RustExp, RustGenOptions, Typing, UNKNOWN_FIELDS_NAME};
 use crate::gen::GenError;
 use crate::tree::PrimitiveType::*;
 use crate::tree::TypeTag::Primitive;
-use crate::tree::{Message, WithTypeTag};
+use crate::tree::{Message, MessageField, TypeTag, WithTypeTag};
 
 /// Responsible for generating struct impl blocks for message decoding.
 #[derive(Copy, Clone, Debug)]
 pub struct GenMessageDecode<'a> {
     naming: &'a Naming,
-    _typing: &'a Typing,
+    typing: &'a Typing,
+    options: &'a RustGenOptions,
 }
 
 impl<'a> GenMessageDecode<'a> {
     //! Construction
 
     /// Creates a new gen message decode.
-    pub const fn new(naming: &'a Naming, typing: &'a Typing) -> Self {
+    pub const fn new(naming: &'a Naming, typing: &'a Typing, options: &'a RustGenOptions) -> Self {
         Self {
             naming,
-            _typing: typing,
+            typing,
+            options,
         }
     }
 }
@@ -33,6 +35,8 @@ impl<'a> GenMessageDecode<'a> {
     //! DecodeFromReadLengthPrefixed
 
     /// Generates the impl block for implementing the `DecodeFromReadLengthPrefixed` trait.
+    ///
+    /// The trait reads the length prefix & decodes the limited input with `DecodeFromRead`.
     pub fn gen_impl_decode_from_read_length_prefixed(
         &self,
         message: &Message,
@@ -47,6 +51,8 @@ impl<'a> GenMessageDecode<'a> {
     //! DecodeFromRead
 
     /// Generates the impl block for implementing the `DecodeFromRead` trait.
+    ///
+    /// The message is decoded from the remaining input.
     pub fn gen_impl_decode_from_read(&self, message: &Message) -> Result<ImplBlock, GenError> {
         let mut block: ImplBlock = self.naming.type_name(message.name())?.into();
         block.set_for_trait("DecodeFromRead");
@@ -73,30 +79,29 @@ impl<'a> GenMessageDecode<'a> {
         message: &Message,
         function: &mut Function,
     ) -> Result<(), GenError> {
-        function.add_semi("let len: usize = VarIntSize::decode_from_read_prefix(r)?.value");
-        function.add_semi("let mut r: ReadLimit<R> = ReadLimit { read: r, limit: len }");
-        function.add_statement(EmptyLine::default());
-        function.add_semi("let result: Self = Self::default()");
-        function.add_semi("let mut field_number: u32 = 0");
-
-        let while_let_exp: Literal =
-            Literal::from("FieldHeader::decode_from_read_prefix_optional(&mut r)?");
-        let mut while_let: WhileLet = WhileLet::new("Some", "header", while_let_exp);
+        let mutable: bool =
+            !message.fields().is_empty() || self.options.unknown_fields().is_preserve();
+        function.add_statement(Semi::from(Self::gen_result_binding(mutable)));
+        self.gen_decode_required_statements(message, function)?;
+
+        let mut while_let: WhileLet = WhileLet::new("Some", "header", Self::gen_header_exp());
         self.gen_while_let_decode_statements(message, &mut while_let)?;
         function.add_statement(while_let);
 
-        function.add_literal("Ok(result)");
+        function.add_statement(RustExp::call("Ok", [RustExp::from("result")]));
         Ok(())
     }
 
     /// Generates the statements in the while-let decoding loop.
+    ///
+    /// Field headers carry absolute field numbers. (each field is encoded with its own header, not
+    /// relative to the previous field)
     fn gen_while_let_decode_statements(
         &self,
         message: &Message,
         while_let: &mut WhileLet,
     ) -> Result<(), GenError> {
-        while_let.add_semi("field_number += header.field_number()");
-        let mut match_statement: Match = Match::from(Literal::from("field_number"));
+        let mut match_statement: Match = Match::from(Self::gen_field_number_exp());
         self.gen_decode_match_cases(message, &mut match_statement)?;
         while_let.add_statement(match_statement);
         Ok(())
@@ -108,33 +113,197 @@ impl<'a> GenMessageDecode<'a> {
         message: &Message,
         match_statement: &mut Match,
     ) -> Result<(), GenError> {
-        for field in message.fields() {
+        for field in message.optional_fields() {
             if let Some(field_number) = field.field_number() {
                 let mut match_case: MatchCase = MatchCase::from(format!("{}", field_number));
-                match field.type_tag() {
-                    Primitive(primitive) => {
-                        let read_fn_name: &str = match primitive {
-                            UnsignedInt8 => "u8",
-                            UnsignedInt16 => "u16_var",
-                            UnsignedInt32 => "u32_var",
-                            UnsignedInt64 => "u64_var",
-                        };
-                        let field_name = self.naming.field_name(field.name())?;
-                        let result_set: String = format!(
-                            "result.set_{}(Some(read::read_{}(&mut r)?))",
-                            field_name, read_fn_name
-                        );
-                        match_case.add_statement(Semi::from(result_set));
+                let member: RustExp = RustExp::from("result")
+                    .field(self.naming.field_name(message.name(), field.name())?)
+                    .to_mut_ref();
+                let result_set: RustExp = match field.type_tag() {
+                    TypeTag::List(element) if self.typing.has_packed_encoding(field.type_tag()) => {
+                        let args: [RustExp; 4] = [
+                            Self::gen_reader_exp(),
+                            RustExp::from("header").method("wire_type", []),
+                            self.gen_read_fn(element, field.is_fixed())?,
+                            member,
+                        ];
+                        RustExp::call("read::read_packable", args).to_try()
                     }
-                }
+                    TypeTag::List(_) => RustExp::from("result").method(
+                        self.naming
+                            .field_fn_name(message.name(), field.name(), "push_{}")?,
+                        [self.gen_read_exp(field)?.to_try()],
+                    ),
+                    TypeTag::Map(key, value) => {
+                        let args: [RustExp; 4] = [
+                            Self::gen_reader_exp(),
+                            self.gen_read_fn(key, false)?,
+                            self.gen_read_fn(value, false)?,
+                            member,
+                        ];
+                        RustExp::call("read::read_map_entry", args).to_try()
+                    }
+                    _ => RustExp::from("result").method(
+                        self.naming
+                            .field_fn_name(message.name(), field.name(), "set_{}")?,
+                        [self.gen_read_exp(field)?.to_try().to_some()],
+                    ),
+                };
+                match_case.add_statement(Semi::from(result_set));
                 match_statement.add_match_case(match_case);
-            } else {
-                unimplemented!("required fields not yet supported")
             }
         }
-        let match_case: MatchCase =
-            MatchCase::from("_").with_semi("unimplemented!(\"unknown fields not yet supported\")");
-        match_statement.add_match_case(match_case);
+        if let Some(match_case) = self.gen_reserved_match_case(message) {
+            match_statement.add_match_case(match_case);
+        }
+        let exp: RustExp = if self.options.unknown_fields().is_preserve() {
+            Self::gen_copy_field_exp(RustExp::from("result").field(UNKNOWN_FIELDS_NAME))
+        } else {
+            Self::gen_skip_value_exp()
+        };
+        match_statement.add_match_case(MatchCase::from("_").with_statement(Semi::from(exp)));
         Ok(())
     }
+
+    /// Generates the match case skipping the reserved field numbers. (they are never unknown)
+    fn gen_reserved_match_case(&self, message: &Message) -> Option<MatchCase> {
+        let patterns: Vec<String> = message
+            .reserved_numbers()
+            .map(|(start, end)| {
+                if start == end {
+                    start.to_string()
+                } else {
+                    format!("{}..={}", start, end)
+                }
+            })
+            .collect();
+        if patterns.is_empty() {
+            None
+        } else {
+            Some(
+                MatchCase::from(patterns.join(" | "))
+                    .with_statement(Semi::from(Self::gen_skip_value_exp())),
+            )
+        }
+    }
+
+    /// Generates the statements decoding the required fields.
+    ///
+    /// Required fields are decoded positionally, in declaration order, before the optional fields.
+    /// A required field missing from the input is a decode error. (`read::missing_required_field`)
+    /// The generator only reports required fields it cannot decode positionally. (lists & maps)
+    fn gen_decode_required_statements(
+        &self,
+        message: &Message,
+        function: &mut Function,
+    ) -> Result<(), GenError> {
+        for field in message.required_fields() {
+            let missing: RustExp = RustExp::call(
+                "read::missing_required_field",
+                [RustExp::from("e"), RustExp::str(field.name())],
+            );
+            let value: RustExp = self
+                .gen_read_exp(field)?
+                .method("map_err", [RustExp::closure("e", missing)])
+                .to_try();
+            let result_set: RustExp = RustExp::from("result").method(
+                self.naming
+                    .field_fn_name(message.name(), field.name(), "set_{}")?,
+                [value],
+            );
+            function.add_statement(Semi::from(result_set));
+        }
+        Ok(())
+    }
+
+    /// Gets the expression passing the reader. (the `&mut R` parameter is implicitly reborrowed)
+    fn gen_reader_exp() -> RustExp {
+        RustExp::from("r")
+    }
+
+    /// Gets the binding of the default `result` value.
+    pub fn gen_result_binding(mutable: bool) -> RustExp {
+        RustExp::let_binding(
+            mutable,
+            "result",
+            "Self",
+            RustExp::call("Self::default", []),
+        )
+    }
+
+    /// Gets the expression decoding the next field header. (`None` at the end of the input)
+    pub fn gen_header_exp() -> RustExp {
+        RustExp::call(
+            "FieldHeader::decode_from_read_prefix_optional",
+            [Self::gen_reader_exp()],
+        )
+        .to_try()
+    }
+
+    /// Gets the expression of the field number of the decoded field header.
+    pub fn gen_field_number_exp() -> RustExp {
+        RustExp::from("header").method("field_number", [])
+    }
+
+    /// Gets the expression skipping the value of the decoded field header.
+    fn gen_skip_value_exp() -> RustExp {
+        let wire_type: RustExp = RustExp::from("header").method("wire_type", []);
+        RustExp::call("read::skip_value", [Self::gen_reader_exp(), wire_type]).to_try()
+    }
+
+    /// Gets the expression copying the field of the decoded field header to the target bytes.
+    pub fn gen_copy_field_exp(target: RustExp) -> RustExp {
+        let args: [RustExp; 3] = [
+            Self::gen_reader_exp(),
+            RustExp::from("header").to_ref(),
+            target.to_mut_ref(),
+        ];
+        RustExp::call("read::copy_field", args).to_try()
+    }
+
+    /// Generates the expression reading the field value. (lists read a single element)
+    fn gen_read_exp(&self, field: &MessageField) -> Result<RustExp, GenError> {
+        let read_fn: RustExp = self.gen_read_fn(field.type_tag(), field.is_fixed())?;
+        Ok(RustExp::call(read_fn, [Self::gen_reader_exp()]))
+    }
+
+    /// Generates the path of the function reading a value of the declared type.
+    ///
+    /// Lists use the function reading a single element & maps use the function reading an entry.
+    pub fn gen_read_fn(&self, declared_type: &TypeTag, fixed: bool) -> Result<RustExp, GenError> {
+        let encoding: &str = if fixed { "fixed" } else { "var" };
+        match declared_type {
+            Primitive(primitive) => {
+                let read_fn_name: String = match primitive {
+                    UnsignedInt8 => "u8".to_string(),
+                    UnsignedInt16 => format!("u16_{}", encoding),
+                    UnsignedInt32 => format!("u32_{}", encoding),
+                    UnsignedInt64 => format!("u64_{}", encoding),
+                    SignedInt8 => "i8".to_string(),
+                    SignedInt16 => format!("i16_{}", encoding),
+                    SignedInt32 => format!("i32_{}", encoding),
+                    SignedInt64 => format!("i64_{}", encoding),
+                    Float32 => "f32".to_string(),
+                    Float64 => "f64".to_string(),
+                    Boolean => "bool".to_string(),
+                };
+                Ok(format!("read::read_{}", read_fn_name).into())
+            }
+            TypeTag::String => Ok("read::read_string".into()),
+            TypeTag::Bytes => Ok("read::read_bytes".into()),
+            TypeTag::Named(_) if self.typing.is_enum(declared_type) => Ok(format!(
+                "{}::decode_from_read",
+                self.typing.type_name(declared_type)?
+            )
+            .into()),
+            // variants are decoded length-prefixed like messages
+            TypeTag::Named(_) => Ok(format!(
+                "{}::decode_from_read_length_prefixed",
+                self.typing.type_name(declared_type)?
+            )
+            .into()),
+            TypeTag::List(element) => self.gen_read_fn(element, fixed),
+            TypeTag::Map(_, _) => Ok("read::read_map_entry".into()),
+        }
+    }
 }
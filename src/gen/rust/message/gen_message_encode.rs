@@ -3,27 +3,29 @@ use code_gen::rust::{
     TypeTag as RustType, WithFnGenerics, WithFunctions, WithReceiver, WithResult, WithUnsafeFlag,
     WithVarParams,
 };
-use code_gen::{Literal, Semi, WithName, WithStatements};
+use code_gen::{Semi, WithName, WithStatements};
 
-use crate::gen::rust::{Naming, Typing};
+use crate::gen::rust::{Naming, RustExp, RustGenOptions, Typing, UNKNOWN_FIELDS_NAME};
 use crate::gen::GenError;
 use crate::tree::{Message, MessageField, PrimitiveType, TypeTag, WithTypeTag};
 
-/// Responsible for generating struct impl blocks for message fields.
+/// Responsible for generating struct impl blocks for message encoding.
 #[derive(Copy, Clone, Debug)]
 pub struct GenMessageEncode<'a> {
     naming: &'a Naming,
-    _typing: &'a Typing,
+    typing: &'a Typing,
+    options: &'a RustGenOptions,
 }
 
 impl<'a> GenMessageEncode<'a> {
     //! Construction
 
-    /// Creates a new gen message field.
-    pub const fn new(naming: &'a Naming, typing: &'a Typing) -> Self {
+    /// Creates a new gen message encode.
+    pub const fn new(naming: &'a Naming, typing: &'a Typing, options: &'a RustGenOptions) -> Self {
         Self {
             naming,
-            _typing: typing,
+            typing,
+            options,
         }
     }
 }
@@ -40,89 +42,167 @@ impl<'a> GenMessageEncode<'a> {
             .with_receiver(Receiver::Borrowed)
             .with_result(RustPrimitive::UnsignedIntSize);
         let mut function: Function = Function::from(signature);
-        function.add_statement(Semi::from("let mut encoded_len: usize = 0"));
-
-        for field in message.fields() {
-            function.add_statement(self.gen_encoded_len_statement(field)?);
-        }
-
-        function.add_expression_statement(Literal::from("encoded_len"));
+        self.gen_encode_statements(&mut function, message, |exp| exp.method("encoded_len", []))?;
+        function.add_statement(RustExp::from("encoded_len"));
         block.add_function(function);
 
         Ok(block)
     }
 
-    fn gen_encoded_len_statement(&self, field: &MessageField) -> Result<Semi<Literal>, GenError> {
-        if let Some(field_number) = field.field_number() {
-            let field_exp: String =
-                self.gen_field_exp(field.name(), field.type_tag(), field_number)?;
-            // todo -- remove literal
-            Ok(Semi::from(Literal::from(format!(
-                "encoded_len += {}.encoded_len()",
-                field_exp
-            ))))
+    /// Generates the statements adding the encoding call for each field to `encoded_len`.
+    ///
+    /// The encoding call is applied to the encoding expression of each field in encoding order &
+    /// then to the preserved unknown fields.
+    fn gen_encode_statements<F>(
+        &self,
+        function: &mut Function,
+        message: &Message,
+        encode: F,
+    ) -> Result<(), GenError>
+    where
+        F: Fn(RustExp) -> RustExp,
+    {
+        let mutable: bool = !self.encodes_nothing(message);
+        function.add_statement(Semi::from(RustExp::let_binding(
+            mutable,
+            "encoded_len",
+            "usize",
+            RustExp::from("0"),
+        )));
+        for field in message.encoding_order() {
+            let exp: RustExp = encode(self.gen_field_exp(message, field)?);
+            function.add_statement(Semi::from(RustExp::add_assign("encoded_len", exp)));
+        }
+        if self.options.unknown_fields().is_preserve() {
+            let exp: RustExp = encode(Self::gen_unknown_fields_exp());
+            function.add_statement(Semi::from(RustExp::add_assign("encoded_len", exp)));
+        }
+        Ok(())
+    }
+
+    /// Checks if the message always encodes to nothing. (it has no fields to encode)
+    fn encodes_nothing(&self, message: &Message) -> bool {
+        message.fields().is_empty() && !self.options.unknown_fields().is_preserve()
+    }
+
+    /// Gets the name of the encoding target parameter. (unused targets are prefixed with `_`)
+    fn target_param(&self, message: &Message, name: &str) -> String {
+        if self.encodes_nothing(message) {
+            format!("_{}", name)
         } else {
-            unimplemented!("required fields not yet supported")
+            name.to_string()
         }
     }
 
-    fn gen_field_exp(
-        &self,
-        declared_name: &str,
-        type_tag: &TypeTag,
-        field_number: u32,
-    ) -> Result<String, GenError> {
-        match type_tag {
-            TypeTag::Primitive(primitive) => match primitive {
-                PrimitiveType::UnsignedInt8 => {
-                    self.field_exp_int(declared_name, false, 8, None, field_number)
-                }
-                PrimitiveType::UnsignedInt16 => {
-                    // todo -- supported fixed fields
-                    self.field_exp_int(declared_name, false, 16, Some(false), field_number)
-                }
-                PrimitiveType::UnsignedInt32 => {
-                    // todo -- supported fixed fields
-                    self.field_exp_int(declared_name, false, 32, Some(false), field_number)
-                }
-                PrimitiveType::UnsignedInt64 => {
-                    // todo -- supported fixed fields
-                    self.field_exp_int(declared_name, false, 64, Some(false), field_number)
-                }
-            },
+    /// Gets the encoding expression for the preserved unknown fields.
+    fn gen_unknown_fields_exp() -> RustExp {
+        let bytes: RustExp = RustExp::from("self").field(UNKNOWN_FIELDS_NAME).to_ref();
+        RustExp::call("RawBytes::new", [bytes])
+    }
+
+    /// Gets the encoding expression for the field.
+    ///
+    /// Optional fields are encoded as tagged fields & required fields are encoded as plain values.
+    /// Message values are encoded length-prefixed. List fields are encoded as a tagged field per
+    /// element, or as a single tagged run of elements when packed. Map fields are encoded as a
+    /// tagged field per entry, each entry holding the key as field 1 & the value as field 2.
+    fn gen_field_exp(&self, message: &Message, field: &MessageField) -> Result<RustExp, GenError> {
+        let member: RustExp =
+            RustExp::from("self").field(self.naming.field_name(message.name(), field.name())?);
+
+        let (kind, value): (&str, RustExp) = match (field.type_tag(), field.field_number()) {
+            (TypeTag::Map(key, value), _) => {
+                let entry: RustExp = RustExp::tuple([
+                    self.gen_tagged_exp(key, 1, "key"),
+                    self.gen_tagged_exp(value, 2, "value"),
+                ]);
+                let entries: RustExp = member
+                    .method("iter", [])
+                    .method("map", [RustExp::closure("(key, value)", entry)]);
+                ("Field", entries)
+            }
+            (TypeTag::List(_), _) if field.is_packed() => ("Packed", member.method("as_slice", [])),
+            (TypeTag::List(_), _) => ("List", member.method("as_slice", [])),
+            (tag, Some(_)) if self.typing.is_copy(tag)? => ("Field", member),
+            (TypeTag::Named(_), Some(_)) => ("Field", member.method("as_ref", [])),
+            (_, Some(_)) => ("Field", member.method("as_deref", [])),
+            (tag, None) if self.typing.is_copy(tag)? => ("Value", member),
+            (TypeTag::String, None) => ("Value", member.method("as_str", [])),
+            (TypeTag::Bytes, None) => ("Value", member.method("as_slice", [])),
+            (_, None) => ("Value", member.to_ref()),
+        };
+
+        let mut args: Vec<RustExp> = Vec::default();
+        if let Some(field_number) = field.field_number() {
+            args.push(field_number.into());
         }
+        if self.typing.has_fixed_encoding(field.type_tag()) {
+            args.push(field.is_fixed().into());
+        }
+        args.push(value);
+
+        Ok(RustExp::call(
+            format!("{}{}::new", self.type_prefix(field.type_tag()), kind),
+            args,
+        ))
     }
 
-    /// Gets the field constructor expression string.
-    fn field_exp_int(
-        &self,
-        declared_name: &str,
-        signed: bool,
-        bits: u32,
-        fixed: Option<bool>,
-        field_number: u32,
-    ) -> Result<String, GenError> {
-        let signed: &str = if signed { "Signed" } else { "Unsigned" };
-        let name: String = self.naming.field_name(declared_name)?;
-        let result: String = if let Some(fixed) = fixed {
-            format!(
-                "{}Int{}Field::new({}, {}, self.{})",
-                signed, bits, field_number, fixed, name
-            )
-        } else {
-            format!(
-                "{}Int{}Field::new({}, self.{})",
-                signed, bits, field_number, name
-            )
+    /// Gets the tagged field encoding expression for the value borrowed by the variable.
+    ///
+    /// Used for the key & value of map entries and for the value of variant cases.
+    pub fn gen_tagged_exp(&self, declared_type: &TypeTag, field_number: u32, var: &str) -> RustExp {
+        let var: RustExp = RustExp::from(var);
+        let value: RustExp = match declared_type {
+            TypeTag::Primitive(_) => var.to_deref(),
+            TypeTag::Named(_) if self.typing.is_enum(declared_type) => var.to_deref(),
+            TypeTag::String => var.method("as_str", []),
+            TypeTag::Bytes => var.method("as_slice", []),
+            _ => var,
         };
-        Ok(result)
+
+        let mut args: Vec<RustExp> = vec![field_number.into()];
+        if self.typing.has_fixed_encoding(declared_type) {
+            args.push(false.into());
+        }
+        args.push(value.to_some());
+
+        RustExp::call(
+            format!("{}Field::new", self.type_prefix(declared_type)),
+            args,
+        )
+    }
+
+    /// Gets the encoding type prefix for the declared type. (lists use the element prefix)
+    fn type_prefix(&self, declared_type: &TypeTag) -> &'static str {
+        match declared_type {
+            TypeTag::Primitive(primitive) => match primitive {
+                PrimitiveType::UnsignedInt8 => "UnsignedInt8",
+                PrimitiveType::UnsignedInt16 => "UnsignedInt16",
+                PrimitiveType::UnsignedInt32 => "UnsignedInt32",
+                PrimitiveType::UnsignedInt64 => "UnsignedInt64",
+                PrimitiveType::SignedInt8 => "SignedInt8",
+                PrimitiveType::SignedInt16 => "SignedInt16",
+                PrimitiveType::SignedInt32 => "SignedInt32",
+                PrimitiveType::SignedInt64 => "SignedInt64",
+                PrimitiveType::Float32 => "Float32",
+                PrimitiveType::Float64 => "Float64",
+                PrimitiveType::Boolean => "Boolean",
+            },
+            TypeTag::String => "String",
+            TypeTag::Bytes => "Bytes",
+            TypeTag::Named(_) if self.typing.is_enum(declared_type) => "Enum",
+            // variants are encoded length-prefixed like messages
+            TypeTag::Named(_) => "Message",
+            TypeTag::List(element) => self.type_prefix(element),
+            TypeTag::Map(_, _) => "Map",
+        }
     }
 }
 
 impl<'a> GenMessageEncode<'a> {
     //! EncodeToSlice
 
-    /// Generates the impl block for implementing `EncodedLen`.
+    /// Generates the impl block for implementing `EncodeToSlice`.
     pub fn gen_impl_encode_to_slice(&self, message: &Message) -> Result<ImplBlock, GenError> {
         let mut block: ImplBlock = self.naming.type_name(message.name())?.into();
         block.set_for_trait("EncodeToSlice");
@@ -130,7 +210,7 @@ impl<'a> GenMessageEncode<'a> {
         let signature: Signature = Signature::from("encode_to_slice_unchecked")
             .with_receiver(Receiver::Borrowed)
             .with_param((
-                "target",
+                self.target_param(message, "target"),
                 RustPrimitive::UnsignedInt8
                     .to_type_tag()
                     .to_slice()
@@ -138,34 +218,17 @@ impl<'a> GenMessageEncode<'a> {
             ))
             .with_result(RustPrimitive::UnsignedIntSize);
         let mut function: Function = Function::from(signature).with_unsafe();
-
-        function.add_statement(Semi::from("let mut encoded_len: usize = 0"));
-        for field in message.fields() {
-            function.add_statement(self.gen_encode_to_slice_statement(field)?);
-        }
-        function.add_expression_statement(Literal::from("encoded_len"));
-
+        self.gen_encode_statements(&mut function, message, |exp| {
+            let target: RustExp = RustExp::from("target")
+                .index(RustExp::range_from("encoded_len"))
+                .to_mut_ref();
+            exp.method("encode_to_slice_unchecked", [target])
+        })?;
+        function.add_statement(RustExp::from("encoded_len"));
         block.add_function(function);
 
         Ok(block)
     }
-
-    fn gen_encode_to_slice_statement(
-        &self,
-        field: &MessageField,
-    ) -> Result<Semi<Literal>, GenError> {
-        if let Some(field_number) = field.field_number() {
-            let field_exp: String =
-                self.gen_field_exp(field.name(), field.type_tag(), field_number)?;
-            // todo -- remove literal
-            Ok(Semi::from(Literal::from(format!(
-                "encoded_len += {}.encode_to_slice_unchecked(target)",
-                field_exp
-            ))))
-        } else {
-            unimplemented!("required fields not yet supported")
-        }
-    }
 }
 
 impl<'a> GenMessageEncode<'a> {
@@ -183,37 +246,17 @@ impl<'a> GenMessageEncode<'a> {
             .with_receiver(Receiver::Borrowed)
             .with_generic(("W", "io::Write"))
             .with_param((
-                "w",
+                self.target_param(message, "w"),
                 RustType::Named("W".to_string()).to_reference(Reference::MUT),
             ))
             .with_result(result_type);
         let mut function: Function = Function::from(signature);
-
-        function.add_statement(Semi::from("let mut encoded_len: usize = 0"));
-        for field in message.fields() {
-            function.add_statement(self.gen_encode_to_write_statement(field)?);
-        }
-        function.add_expression_statement(Literal::from("Ok(encoded_len)"));
-
+        self.gen_encode_statements(&mut function, message, |exp| {
+            exp.method("encode_to_write", [RustExp::from("w")]).to_try()
+        })?;
+        function.add_statement(RustExp::call("Ok", [RustExp::from("encoded_len")]));
         block.add_function(function);
 
         Ok(block)
     }
-
-    fn gen_encode_to_write_statement(
-        &self,
-        field: &MessageField,
-    ) -> Result<Semi<Literal>, GenError> {
-        if let Some(field_number) = field.field_number() {
-            let field_exp: String =
-                self.gen_field_exp(field.name(), field.type_tag(), field_number)?;
-            // todo -- remove literal
-            Ok(Semi::from(Literal::from(format!(
-                "encoded_len += {}.encode_to_write(w)?",
-                field_exp
-            ))))
-        } else {
-            unimplemented!("required fields not yet supported")
-        }
-    }
 }
@@ -1,26 +1,32 @@
+use code_gen::rust::Access::Public;
 use code_gen::rust::{
-    gen_builder_copy, gen_getter_copy, gen_setter_copy, Function, ImplBlock, TypeTag as RustType,
-    WithComments, WithFunctions,
+    gen_getter_copy, Function, ImplBlock, Receiver, Signature, TypeTag as RustType, WithAccess,
+    WithComments, WithFnGenerics, WithFunctions, WithReceiver, WithResult, WithVarParams,
 };
-use code_gen::WithName;
+use code_gen::{WithName, WithStatements};
 
-use crate::gen::rust::{Naming, Typing};
+use crate::gen::rust::{Naming, RustGenOptions, Typing, UNKNOWN_FIELDS_NAME};
 use crate::gen::GenError;
-use crate::tree::{Message, MessageField, WithTypeTag};
+use crate::tree::{Message, MessageField, TypeTag, WithTypeTag};
 
 /// Responsible for generating struct impl blocks for message fields.
 #[derive(Copy, Clone, Debug)]
 pub struct GenMessageField<'a> {
     naming: &'a Naming,
     typing: &'a Typing,
+    options: &'a RustGenOptions,
 }
 
 impl<'a> GenMessageField<'a> {
     //! Construction
 
     /// Creates a new gen message field.
-    pub const fn new(naming: &'a Naming, typing: &'a Typing) -> Self {
-        Self { naming, typing }
+    pub const fn new(naming: &'a Naming, typing: &'a Typing, options: &'a RustGenOptions) -> Self {
+        Self {
+            naming,
+            typing,
+            options,
+        }
     }
 }
 
@@ -36,68 +42,491 @@ impl<'a> GenMessageField<'a> {
         let mut block: ImplBlock = self.naming.type_name(message.name())?.into();
         block.add_comment(format!("Field: {}", field));
 
-        self.gen_getters(&mut block, field)?;
-        self.gen_setters(&mut block, field)?;
-        self.gen_builders(&mut block, field)?;
+        self.gen_getters(&mut block, message, field)?;
+        self.gen_setters(&mut block, message, field)?;
+        if self.options.builders() {
+            self.gen_builders(&mut block, message, field)?;
+        }
 
         Ok(block)
     }
 }
 
+impl<'a> GenMessageField<'a> {
+    //! Member Names
+
+    /// Gets the name of the struct field & the names of the functions generated for the field.
+    pub fn member_names(
+        &self,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<Vec<String>, GenError> {
+        let mut patterns: Vec<&str> = vec!["{}", "set_{}"];
+        if self.options.builders() {
+            patterns.push("with_{}");
+        }
+        match field.type_tag() {
+            TypeTag::List(_) => patterns.extend(["{}_iter", "push_{}", "clear_{}"]),
+            TypeTag::Map(_, _) => patterns.extend(["get_{}", "insert_{}", "remove_{}"]),
+            _ => {}
+        }
+        patterns
+            .into_iter()
+            .map(|pattern| {
+                self.naming
+                    .field_fn_name(message.name(), field.name(), pattern)
+            })
+            .collect()
+    }
+}
+
 impl<'a> GenMessageField<'a> {
     //! Get
 
     /// Generates the getter functions for the field.
-    fn gen_getters(&self, b: &mut ImplBlock, field: &MessageField) -> Result<(), GenError> {
-        if self.typing.is_copy(field.type_tag())? {
-            let name: String = self.naming.field_name(field.name())?;
-            let tag: RustType = self.typing.field_type(field.type_tag())?.to_option();
-            let function: Function = gen_getter_copy(name, tag)
-                .with_comment(format!("Gets the field: `{}`.", field.name()));
-            b.add_function(function);
+    fn gen_getters(
+        &self,
+        b: &mut ImplBlock,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<(), GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let function: Function = if self.typing.is_copy(field.type_tag())? {
+            let tag: RustType = self.typing.message_field_type(field)?;
+            gen_getter_copy(name, tag)
         } else {
-            unreachable!()
+            self.gen_getter_borrowed(name, message, field)?
+        };
+        b.add_function(function.with_comment(format!("Gets the field: `{}`.", field.name())));
+        if let TypeTag::List(element) = field.type_tag() {
+            b.add_function(self.gen_list_iter(message, field, element)?);
+        }
+        if let TypeTag::Map(key, value) = field.type_tag() {
+            b.add_function(self.gen_map_get(message, field, key, value)?);
         }
         Ok(())
     }
+
+    /// Generates the getter function borrowing the non-`Copy` field.
+    fn gen_getter_borrowed(
+        &self,
+        name: String,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<Function, GenError> {
+        let tag: RustType = self
+            .typing
+            .borrowed_field_type(field.type_tag())?
+            .ok_or_else(|| GenError::unsupported_type(message, field))?;
+        let (tag, exp): (RustType, String) =
+            if field.is_required() || Typing::is_collection(field.type_tag()) {
+                let exp: String = match field.type_tag() {
+                    TypeTag::String => format!("self.{}.as_str()", name),
+                    TypeTag::Bytes | TypeTag::List(_) => format!("self.{}.as_slice()", name),
+                    _ => format!("&self.{}", name),
+                };
+                (tag, exp)
+            } else {
+                let exp: String = match field.type_tag() {
+                    TypeTag::Named(_) => format!("self.{}.as_ref()", name),
+                    _ => format!("self.{}.as_deref()", name),
+                };
+                (tag.to_option(), exp)
+            };
+
+        let signature: Signature = Signature::from(name.as_str())
+            .with_receiver(Receiver::Borrowed)
+            .with_result(tag);
+        let mut function: Function = Function::from(signature).with_access(Public);
+        function.add_literal(exp);
+        Ok(function)
+    }
 }
 
 impl<'a> GenMessageField<'a> {
     //! Set
 
     /// Generates the setter functions for the field.
-    fn gen_setters(&self, b: &mut ImplBlock, field: &MessageField) -> Result<(), GenError> {
-        if self.typing.is_copy(field.type_tag())? {
-            let name: String = self.naming.field_name(field.name())?;
-            let tag: RustType = self.typing.field_type(field.type_tag())?.to_option();
-            let function: Function = gen_setter_copy(name, tag).with_comment(format!(
+    fn gen_setters(
+        &self,
+        b: &mut ImplBlock,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<(), GenError> {
+        let fn_name: String = self
+            .naming
+            .field_fn_name(message.name(), field.name(), "set_{}")?;
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let tag: RustType = self.typing.message_field_type(field)?;
+        let is_copy: bool = self.typing.is_copy(field.type_tag())?;
+        if !is_copy && self.typing.borrowed_field_type(field.type_tag())?.is_none() {
+            return Err(GenError::unsupported_type(message, field));
+        }
+        let function: Function = if !self.options.setters_return_old() {
+            self.gen_setter_assign(fn_name, name, tag)
+                .with_comment(format!("Sets the field: `{}`.", field.name()))
+        } else {
+            let function: Function = if is_copy {
+                self.gen_setter_copy(fn_name, name, tag, field)?
+            } else {
+                self.gen_setter_owned(fn_name, name, tag)
+            };
+            function.with_comment(format!(
                 "Sets the field: `{}`. Returns the previous value.",
                 field.name()
-            ));
-            b.add_function(function);
-        } else {
-            unreachable!()
+            ))
+        };
+        b.add_function(function);
+        if let TypeTag::List(element) = field.type_tag() {
+            b.add_function(self.gen_list_push(message, field, element)?);
+            b.add_function(self.gen_list_clear(message, field)?);
+        }
+        if let TypeTag::Map(key, value) = field.type_tag() {
+            b.add_function(self.gen_map_insert(message, field, key, value)?);
+            b.add_function(self.gen_map_remove(message, field, key, value)?);
         }
         Ok(())
     }
+
+    /// Generates the setter function for the `Copy` field value.
+    fn gen_setter_copy(
+        &self,
+        fn_name: String,
+        name: String,
+        tag: RustType,
+        field: &MessageField,
+    ) -> Result<Function, GenError> {
+        let type_name: String = self.typing.type_name(field.type_tag())?;
+        let type_name: String = if field.is_required() {
+            type_name
+        } else {
+            format!("Option<{}>", type_name)
+        };
+
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::BorrowedMut)
+            .with_param((name.clone(), tag.clone()))
+            .with_result(tag);
+        let mut function: Function = Function::from(signature).with_access(Public);
+        function.add_semi(format!("let old_value: {} = self.{}", type_name, name));
+        function.add_semi(format!("self.{} = {}", name, name));
+        function.add_literal("old_value");
+        Ok(function)
+    }
+
+    /// Generates the setter function taking ownership of the non-`Copy` field value.
+    fn gen_setter_owned(&self, fn_name: String, name: String, tag: RustType) -> Function {
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::BorrowedMut)
+            .with_param((name.clone(), tag.clone()))
+            .with_result(tag);
+        let mut function: Function = Function::from(signature).with_access(Public);
+        function.add_literal(format!("std::mem::replace(&mut self.{}, {})", name, name));
+        function
+    }
+
+    /// Generates the setter function assigning the field value without returning the previous value.
+    fn gen_setter_assign(&self, fn_name: String, name: String, tag: RustType) -> Function {
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::BorrowedMut)
+            .with_param((name.clone(), tag));
+        let mut function: Function = Function::from(signature).with_access(Public);
+        function.add_semi(format!("self.{} = {}", name, name));
+        function
+    }
 }
 
 impl<'a> GenMessageField<'a> {
     //! Build
 
     /// Generates the builder functions for the field.
-    fn gen_builders(&self, b: &mut ImplBlock, field: &MessageField) -> Result<(), GenError> {
-        if self.typing.is_copy(field.type_tag())? {
-            let name: String = self.naming.field_name(field.name())?;
-            let tag: RustType = self.typing.field_type(field.type_tag())?.to_option();
-            let function: Function = gen_builder_copy(name, tag).with_comment(format!(
-                "Builds the field: `{}`. Returns the struct itself.",
+    fn gen_builders(
+        &self,
+        b: &mut ImplBlock,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<(), GenError> {
+        let fn_name: String = self
+            .naming
+            .field_fn_name(message.name(), field.name(), "with_{}")?;
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let function: Function = if self.typing.is_copy(field.type_tag())? {
+            let tag: RustType = self.typing.message_field_type(field)?;
+            self.gen_builder_copy(fn_name, name, tag)
+        } else {
+            self.gen_builder_into(fn_name, name, message, field)?
+        };
+        b.add_function(function.with_comment(format!(
+            "Builds the field: `{}`. Returns the struct itself.",
+            field.name()
+        )));
+        Ok(())
+    }
+
+    /// Generates the builder function for the `Copy` field value.
+    fn gen_builder_copy(&self, fn_name: String, name: String, tag: RustType) -> Function {
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::OwnedMut)
+            .with_param((name.clone(), tag))
+            .with_result(RustType::from("Self"));
+        let mut function: Function = Function::from(signature).with_access(Public);
+        function.add_semi(format!("self.{} = {}", name, name));
+        function.add_literal("self");
+        function
+    }
+
+    /// Generates the builder function accepting any value convertible into the non-`Copy` field.
+    fn gen_builder_into(
+        &self,
+        fn_name: String,
+        name: String,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<Function, GenError> {
+        if self.typing.borrowed_field_type(field.type_tag())?.is_none() {
+            return Err(GenError::unsupported_type(message, field));
+        }
+
+        let bound: String = format!("Into<{}>", self.typing.type_name(field.type_tag())?);
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::OwnedMut)
+            .with_generic(("V", bound.as_str()))
+            .with_param((name.clone(), RustType::from("V")))
+            .with_result(RustType::from("Self"));
+        let mut function: Function = Function::from(signature).with_access(Public);
+        if field.is_required() || Typing::is_collection(field.type_tag()) {
+            function.add_semi(format!("self.{} = {}.into()", name, name));
+        } else {
+            function.add_semi(format!("self.{} = Some({}.into())", name, name));
+        }
+        function.add_literal("self");
+        Ok(function)
+    }
+}
+
+impl<'a> GenMessageField<'a> {
+    //! List
+
+    /// Generates the function iterating the elements of the list field.
+    ///
+    /// `Copy` elements are iterated by value & other elements are iterated by reference.
+    fn gen_list_iter(
+        &self,
+        message: &Message,
+        field: &MessageField,
+        element: &TypeTag,
+    ) -> Result<Function, GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let (item, adapter): (String, &str) = self.gen_element_access(element)?;
+        let exp: String = format!("iter(){}", adapter);
+
+        let fn_name: String = self
+            .naming
+            .field_fn_name(message.name(), field.name(), "{}_iter")?;
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::Borrowed)
+            .with_result(RustType::from(format!(
+                "impl Iterator<Item = {}> + '_",
+                item
+            )));
+        let mut function: Function =
+            Function::from(signature)
+                .with_access(Public)
+                .with_comment(format!(
+                    "Iterates the elements of the field: `{}`.",
+                    field.name()
+                ));
+        function.add_literal(format!("self.{}.{}", name, exp));
+        Ok(function)
+    }
+
+    /// Generates the function pushing an element onto the list field.
+    fn gen_list_push(
+        &self,
+        message: &Message,
+        field: &MessageField,
+        element: &TypeTag,
+    ) -> Result<Function, GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let bound: String = format!("Into<{}>", self.typing.type_name(element)?);
+
+        let fn_name: String = self
+            .naming
+            .field_fn_name(message.name(), field.name(), "push_{}")?;
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::BorrowedMut)
+            .with_generic(("V", bound.as_str()))
+            .with_param(("element", RustType::from("V")));
+        let mut function: Function =
+            Function::from(signature)
+                .with_access(Public)
+                .with_comment(format!(
+                    "Pushes an element onto the field: `{}`.",
+                    field.name()
+                ));
+        function.add_semi(format!("self.{}.push(element.into())", name));
+        Ok(function)
+    }
+
+    /// Generates the function clearing the list field.
+    fn gen_list_clear(
+        &self,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<Function, GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+
+        let fn_name: String =
+            self.naming
+                .field_fn_name(message.name(), field.name(), "clear_{}")?;
+        let signature: Signature = Signature::from(fn_name).with_receiver(Receiver::BorrowedMut);
+        let mut function: Function = Function::from(signature)
+            .with_access(Public)
+            .with_comment(format!("Clears the field: `{}`.", field.name()));
+        function.add_semi(format!("self.{}.clear()", name));
+        Ok(function)
+    }
+}
+
+impl<'a> GenMessageField<'a> {
+    //! Map
+
+    /// Generates the function getting the value for a key in the map field.
+    fn gen_map_get(
+        &self,
+        message: &Message,
+        field: &MessageField,
+        key: &TypeTag,
+        value: &TypeTag,
+    ) -> Result<Function, GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let (key_type, key_exp): (String, &str) = self.gen_key_param(key)?;
+        let (value_type, adapter): (String, &str) = self.gen_element_access(value)?;
+
+        let fn_name: String = self
+            .naming
+            .field_fn_name(message.name(), field.name(), "get_{}")?;
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::Borrowed)
+            .with_param(("key", RustType::from(key_type)))
+            .with_result(RustType::from(value_type).to_option());
+        let mut function: Function =
+            Function::from(signature)
+                .with_access(Public)
+                .with_comment(format!(
+                    "Gets the value for the key in the field: `{}`.",
+                    field.name()
+                ));
+        function.add_literal(format!("self.{}.get({}){}", name, key_exp, adapter));
+        Ok(function)
+    }
+
+    /// Generates the function inserting an entry into the map field.
+    fn gen_map_insert(
+        &self,
+        message: &Message,
+        field: &MessageField,
+        key: &TypeTag,
+        value: &TypeTag,
+    ) -> Result<Function, GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let key_bound: String = format!("Into<{}>", self.typing.type_name(key)?);
+        let value_bound: String = format!("Into<{}>", self.typing.type_name(value)?);
+
+        let fn_name: String =
+            self.naming
+                .field_fn_name(message.name(), field.name(), "insert_{}")?;
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::BorrowedMut)
+            .with_generic(("K", key_bound.as_str()))
+            .with_generic(("V", value_bound.as_str()))
+            .with_param(("key", RustType::from("K")))
+            .with_param(("value", RustType::from("V")))
+            .with_result(self.typing.field_type(value)?.to_option());
+        let mut function: Function =
+            Function::from(signature)
+                .with_access(Public)
+                .with_comment(format!(
+                    "Inserts the entry into the field: `{}`. Returns the previous value.",
+                    field.name()
+                ));
+        function.add_literal(format!("self.{}.insert(key.into(), value.into())", name));
+        Ok(function)
+    }
+
+    /// Generates the function removing the entry for a key from the map field.
+    fn gen_map_remove(
+        &self,
+        message: &Message,
+        field: &MessageField,
+        key: &TypeTag,
+        value: &TypeTag,
+    ) -> Result<Function, GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let (key_type, key_exp): (String, &str) = self.gen_key_param(key)?;
+
+        let fn_name: String =
+            self.naming
+                .field_fn_name(message.name(), field.name(), "remove_{}")?;
+        let signature: Signature = Signature::from(fn_name)
+            .with_receiver(Receiver::BorrowedMut)
+            .with_param(("key", RustType::from(key_type)))
+            .with_result(self.typing.field_type(value)?.to_option());
+        let mut function: Function =
+            Function::from(signature)
+                .with_access(Public)
+                .with_comment(format!(
+                "Removes the entry for the key from the field: `{}`. Returns the removed value.",
                 field.name()
             ));
-            b.add_function(function);
-        } else {
-            unreachable!()
+        function.add_literal(format!("self.{}.remove({})", name, key_exp));
+        Ok(function)
+    }
+
+    /// Gets the parameter type & lookup expression for a map key. (strings are looked up by `&str`)
+    fn gen_key_param(&self, key: &TypeTag) -> Result<(String, &'static str), GenError> {
+        match key {
+            TypeTag::String => Ok(("&str".to_string(), "key")),
+            _ => Ok((self.typing.type_name(key)?, "&key")),
         }
-        Ok(())
+    }
+}
+
+impl<'a> GenMessageField<'a> {
+    //! Elements
+
+    /// Gets the accessed type & reference adapter for list elements & map values.
+    ///
+    /// `Copy` elements are accessed by value & other elements are accessed by reference.
+    fn gen_element_access(&self, element: &TypeTag) -> Result<(String, &'static str), GenError> {
+        match element {
+            TypeTag::String => Ok(("&str".to_string(), ".map(String::as_str)")),
+            TypeTag::Bytes => Ok(("&[u8]".to_string(), ".map(Vec::as_slice)")),
+            _ if self.typing.is_copy(element)? => {
+                Ok((self.typing.type_name(element)?, ".copied()"))
+            }
+            _ => Ok((format!("&{}", self.typing.type_name(element)?), "")),
+        }
+    }
+}
+
+impl<'a> GenMessageField<'a> {
+    //! Unknown Fields
+
+    /// Generates the impl block for the preserved unknown fields.
+    pub fn gen_unknown_fields(&self, message: &Message) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(message.name())?.into();
+        block.add_comment("Unknown Fields");
+
+        let signature: Signature = Signature::from(UNKNOWN_FIELDS_NAME)
+            .with_receiver(Receiver::Borrowed)
+            .with_result(RustType::from("&[u8]"));
+        let mut function: Function = Function::from(signature)
+            .with_access(Public)
+            .with_comment("Gets the encoded unknown fields.");
+        function.add_literal("self.unknown_fields.as_slice()");
+        block.add_function(function);
+
+        Ok(block)
     }
 }
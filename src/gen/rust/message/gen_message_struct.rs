@@ -1,27 +1,32 @@
 use code_gen::rust::Access::Public;
 use code_gen::rust::{
-    Struct, StructField, TypeTag as RustType, WithAccess, WithComments as WithRustComments,
-    WithDerives, WithStructFields,
+    Struct, StructField, TypeTag as RustType, WithAccess, WithAttributes,
+    WithComments as WithRustComments, WithDerives, WithStructFields,
 };
 use code_gen::WithName;
 
-use crate::gen::rust::{Naming, Typing};
+use crate::gen::rust::{Naming, RustGenOptions, Typing, UNKNOWN_FIELDS_NAME};
 use crate::gen::GenError;
-use crate::tree::{Message, MessageField, WithComments, WithTypeTag};
+use crate::tree::{Message, MessageField, WithComments};
 
 /// Responsible for generating struct declarations for message types.
 #[derive(Copy, Clone, Debug)]
 pub struct GenMessageStruct<'a> {
     naming: &'a Naming,
     typing: &'a Typing,
+    options: &'a RustGenOptions,
 }
 
 impl<'a> GenMessageStruct<'a> {
     //! Construction
 
     /// Creates a new gen message struct.
-    pub const fn new(naming: &'a Naming, typing: &'a Typing) -> Self {
-        Self { naming, typing }
+    pub const fn new(naming: &'a Naming, typing: &'a Typing, options: &'a RustGenOptions) -> Self {
+        Self {
+            naming,
+            typing,
+            options,
+        }
     }
 }
 
@@ -35,9 +40,15 @@ impl<'a> GenMessageStruct<'a> {
 
         self.gen_comments(&mut s, message)?;
         self.gen_derives(&mut s, message)?;
+        for attribute in self.options.attributes(message.name()) {
+            s.add_attribute(attribute.as_str());
+        }
 
         for field in message.fields() {
-            self.gen_field(&mut s, field)?;
+            self.gen_field(&mut s, message, field)?;
+        }
+        if self.options.unknown_fields().is_preserve() {
+            s.add_field((UNKNOWN_FIELDS_NAME.to_string(), RustType::from("Vec<u8>")));
         }
 
         Ok(s)
@@ -49,35 +60,61 @@ impl<'a> GenMessageStruct<'a> {
             s.add_comment(format!("// {}", comment.as_str()));
         }
 
-        if message.fields().is_empty() {
+        if message.fields().is_empty() && message.reserved().next().is_none() {
             s.add_comment(format!("message {} {{}}", message.name()));
         } else {
             s.add_comment(format!("message {} {{", message.name()));
-            for field in message.fields() {
+            for (index, field) in message.fields().iter().enumerate() {
+                Self::gen_reserved_comments(s, message, index);
                 s.add_comment("");
                 for comment in field.comments() {
                     s.add_comment(format!("    // {}", comment));
                 }
                 s.add_comment(format!("    {}", field));
             }
+            Self::gen_reserved_comments(s, message, message.fields().len());
             s.add_comment("}");
         }
 
         Ok(())
     }
 
+    /// Generates the comments for the reserved statements before the field at the index.
+    fn gen_reserved_comments(s: &mut Struct, message: &Message, index: usize) {
+        for reserved in message.reserved_before(index) {
+            s.add_comment("");
+            s.add_comment(format!("    {}", reserved));
+        }
+    }
+
     /// Generates the derives for the struct.
+    ///
+    /// The derives are inferred from the field types unless they are set in the options.
     fn gen_derives(&self, s: &mut Struct, message: &Message) -> Result<(), GenError> {
-        if self.typing.all_copy(message)? {
+        if let Some(derives) = self.options.derives() {
+            for derive in derives {
+                s.add_derive(derive.as_str());
+            }
+            return Ok(());
+        }
+
+        if self.typing.all_copy(message)? && !self.options.unknown_fields().is_preserve() {
             s.add_derive("Copy");
         }
 
         s.add_derive("Clone");
-        s.add_derive("Ord");
-        s.add_derive("PartialOrd");
-        s.add_derive("Eq");
-        s.add_derive("PartialEq");
-        s.add_derive("Hash");
+        if self.typing.all_eq(message)? {
+            s.add_derive("Ord");
+            s.add_derive("PartialOrd");
+            s.add_derive("Eq");
+            s.add_derive("PartialEq");
+            s.add_derive("Hash");
+        } else if self.typing.all_partial_ord(message)? {
+            s.add_derive("PartialOrd");
+            s.add_derive("PartialEq");
+        } else {
+            s.add_derive("PartialEq");
+        }
         s.add_derive("Debug");
         s.add_derive("Default");
 
@@ -85,10 +122,18 @@ impl<'a> GenMessageStruct<'a> {
     }
 
     /// Generates the code for the field.
-    fn gen_field(&self, s: &mut Struct, field: &MessageField) -> Result<(), GenError> {
-        let name: String = self.naming.field_name(field.name())?;
-        let tag: RustType = self.typing.field_type(field.type_tag())?.to_option();
-        let field: StructField = (name, tag).into();
+    fn gen_field(
+        &self,
+        s: &mut Struct,
+        message: &Message,
+        field: &MessageField,
+    ) -> Result<(), GenError> {
+        let name: String = self.naming.field_name(message.name(), field.name())?;
+        let tag: RustType = self.typing.message_field_type(field)?;
+        let mut field: StructField = (name, tag).into();
+        if self.options.public_fields() {
+            field.set_access(Public);
+        }
         s.add_field(field);
         Ok(())
     }
@@ -0,0 +1,30 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::{Message, WithComments};
+
+#[test]
+fn empty() -> Result<(), GenError> {
+    let mut message: Message = "Empty".into();
+
+    message.add_comment("A message without fields.");
+
+    let source: Source = GenMessage::default().gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("empty.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::TypeTag::{List, Named};
+use crate::tree::{Enum, Message, MessageField, SourceFile};
+
+#[test]
+fn enums() -> Result<(), GenError> {
+    let status: Enum = Enum::from("Status")
+        .with_case(("ACTIVE", 1))
+        .with_case(("DISABLED", 2));
+
+    let status_type = || Named("Status".to_string());
+    let mut account: Message = "Account".into();
+    account.add_field(MessageField::from(("status", status_type())));
+    account.add_field(MessageField::from(("previous", status_type())).with_field_number(1));
+    account.add_field(
+        MessageField::from(("history", List(Box::new(status_type())))).with_field_number(2),
+    );
+    account.add_field(
+        MessageField::from(("packed_history", List(Box::new(status_type()))))
+            .with_field_number(3)
+            .with_packed(),
+    );
+
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(status)
+        .with_declaration(account);
+    let source: Source = GenMessage::default().gen_declarations(&source_file, &[])?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("enums.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use crate::gen::rust::{GenMessage, RustGenOptions, UnknownFields};
+use crate::gen::GenError;
+use crate::tree::Message;
+use crate::tree::MessageField;
+use crate::tree::PrimitiveType::*;
+use crate::tree::SourceFile;
+use crate::tree::TypeTag::{List, Named};
+use crate::validate::Diagnostic;
+
+#[test]
+fn duplicate_field_name() {
+    let message: Message = Message::from("Dup")
+        .with_field(MessageField::from(("one", UnsignedInt8)).with_field_number(1))
+        .with_field(MessageField::from(("one", UnsignedInt16)).with_field_number(2));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError =
+        GenError::InvalidSchema(vec![Diagnostic::new("Dup.one", "duplicate name `one`")].into());
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn duplicate_field_number() {
+    let message: Message = Message::from("Dup")
+        .with_field(MessageField::from(("one", UnsignedInt8)).with_field_number(1))
+        .with_field(MessageField::from(("two", UnsignedInt16)).with_field_number(1));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new("Dup.two", "duplicate field number 1")].into(),
+    );
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn colliding_field_name() {
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("userId", UnsignedInt64)).with_field_number(1))
+        .with_field(MessageField::from(("user_id", UnsignedInt64)).with_field_number(2));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::NameCollision {
+        name: "User.user_id".to_string(),
+        rust_name: "user_id".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn colliding_function_name() {
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("x", UnsignedInt8)).with_field_number(1))
+        .with_field(MessageField::from(("set_x", UnsignedInt8)).with_field_number(2));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::NameCollision {
+        name: "User.set_x".to_string(),
+        rust_name: "set_x".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("tags_iter", UnsignedInt8)).with_field_number(1))
+        .with_field(
+            MessageField::from(("tags", List(Box::new(UnsignedInt8.into())))).with_field_number(2),
+        );
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::NameCollision {
+        name: "User.tags".to_string(),
+        rust_name: "tags_iter".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unknown_fields_collision() {
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("unknown_fields", UnsignedInt8)).with_field_number(1));
+    let result: Result<_, GenError> = GenMessage::default()
+        .with_options(RustGenOptions::default().with_unknown_fields(UnknownFields::Preserve))
+        .gen(&message);
+    let expected: GenError = GenError::NameCollision {
+        name: "User.unknown_fields".to_string(),
+        rust_name: "unknown_fields".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn invalid_identifier() {
+    let message: Message = Message::from("Invalid")
+        .with_field(MessageField::from(("one-two", UnsignedInt8)).with_field_number(1));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new(
+            "Invalid.one-two",
+            "invalid identifier `one-two`",
+        )]
+        .into(),
+    );
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unsupported_fixed() {
+    let message: Message = Message::from("Invalid").with_field(
+        MessageField::from(("one", UnsignedInt8))
+            .with_field_number(1)
+            .with_fixed(),
+    );
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::UnsupportedFixed {
+        message: "Invalid".to_string(),
+        field: "one".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unresolved_type() {
+    let message: Message = Message::from("Outer")
+        .with_field(MessageField::from(("inner", Named("Inner".to_string()))).with_field_number(1));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::UnresolvedType {
+        location: "Outer.inner".to_string(),
+        name: "Inner".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn recursive_message() {
+    let source_file: SourceFile =
+        SourceFile::default()
+            .with_declaration(Message::from("One").with_field(
+                MessageField::from(("two", Named("Two".to_string()))).with_field_number(1),
+            ))
+            .with_declaration(Message::from("Two").with_field(
+                MessageField::from(("one", Named("One".to_string()))).with_field_number(1),
+            ));
+    let result: Result<_, GenError> = GenMessage::default().gen_declarations(&source_file, &[]);
+    let expected: GenError = GenError::RecursiveMessage {
+        message: "One".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unsupported_packed() {
+    let message: Message = Message::from("Invalid").with_field(
+        MessageField::from(("one", UnsignedInt8))
+            .with_field_number(1)
+            .with_packed(),
+    );
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::UnsupportedPacked {
+        message: "Invalid".to_string(),
+        field: "one".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn list_missing_field_number() {
+    let message: Message = Message::from("Invalid").with_field(MessageField::from((
+        "one",
+        List(Box::new(UnsignedInt8.into())),
+    )));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::MissingFieldNumber {
+        message: "Invalid".to_string(),
+        field: "one".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn field_number_out_of_range() {
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("id", UnsignedInt8)).with_field_number(0));
+    let result: Result<_, GenError> = GenMessage::default().gen(&message);
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new(
+            "User.id",
+            "field number 0 is out of range (1..=536870911)",
+        )]
+        .into(),
+    );
+    assert_eq!(result.err(), Some(expected));
+}
@@ -0,0 +1,49 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, WithComments};
+
+#[test]
+fn fixed_ints() -> Result<(), GenError> {
+    let mut message: Message = "FixedInts".into();
+
+    message.add_comment("A message with fixed-width integer fields.");
+
+    message.add_field(
+        MessageField::from(("id", UnsignedInt64))
+            .with_fixed()
+            .with_comment("A required fixed-width field."),
+    );
+    message.add_field(
+        MessageField::from(("hash", UnsignedInt32))
+            .with_field_number(1)
+            .with_fixed()
+            .with_comment("An optional fixed-width field."),
+    );
+    message.add_field(
+        MessageField::from(("offset", SignedInt16))
+            .with_field_number(2)
+            .with_fixed()
+            .with_comment("A signed fixed-width field."),
+    );
+
+    let source: Source = GenMessage::default().gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("fixed_ints.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, WithComments};
+
+#[test]
+fn keywords() -> Result<(), GenError> {
+    let mut message: Message = "Keywords".into();
+
+    message.add_comment("A message with fields named with rust keywords.");
+
+    message.add_field(MessageField::from(("type", UnsignedInt8)));
+    message.add_field(
+        MessageField::from(("self", UnsignedInt32))
+            .with_field_number(1)
+            .with_comment("The optional field."),
+    );
+
+    let source: Source = GenMessage::default().gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("keywords.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
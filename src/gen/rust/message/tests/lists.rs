@@ -0,0 +1,57 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{List, String as Text};
+use crate::tree::{Message, MessageField, TypeTag, WithComments};
+
+#[test]
+fn lists() -> Result<(), GenError> {
+    let mut message: Message = "Lists".into();
+
+    message.add_comment("A message with list fields.");
+
+    let list = |element: TypeTag| List(Box::new(element));
+    message.add_field(
+        MessageField::from(("tags", list(UnsignedInt32.into())))
+            .with_field_number(1)
+            .with_packed()
+            .with_comment("A packed list."),
+    );
+    message.add_field(
+        MessageField::from(("hashes", list(UnsignedInt64.into())))
+            .with_field_number(2)
+            .with_fixed()
+            .with_packed()
+            .with_comment("A fixed-width packed list."),
+    );
+    message.add_field(
+        MessageField::from(("flags", list(Boolean.into())))
+            .with_field_number(3)
+            .with_comment("An unpacked list."),
+    );
+    message.add_field(
+        MessageField::from(("names", list(Text)))
+            .with_field_number(4)
+            .with_comment("A list of strings."),
+    );
+
+    let source: Source = GenMessage::default().gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("lists.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
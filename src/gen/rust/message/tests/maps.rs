@@ -0,0 +1,63 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{GenMessage, MapBacking, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{Map, String as Text};
+use crate::tree::{Message, MessageField, TypeTag, WithComments};
+
+fn maps_message() -> Message {
+    let mut message: Message = "Maps".into();
+
+    message.add_comment("A message with map fields.");
+
+    let map = |key: TypeTag, value: TypeTag| Map(Box::new(key), Box::new(value));
+    message.add_field(
+        MessageField::from(("scores", map(Text, UnsignedInt32.into())))
+            .with_field_number(1)
+            .with_comment("A map keyed by strings."),
+    );
+    message.add_field(
+        MessageField::from(("names", map(UnsignedInt64.into(), Text)))
+            .with_field_number(2)
+            .with_comment("A map keyed by integers."),
+    );
+    message
+}
+
+fn render(source: Source) -> String {
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+#[test]
+fn maps() -> Result<(), GenError> {
+    let source: Source = GenMessage::default().gen(&maps_message())?;
+    let result: String = render(source);
+
+    let expected: &str = include_str!("maps.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
+
+#[test]
+fn hash_maps() -> Result<(), GenError> {
+    let source: Source = GenMessage::default()
+        .with_options(RustGenOptions::default().with_map_backing(MapBacking::HashMap))
+        .gen(&maps_message())?;
+    let result: String = render(source);
+
+    assert!(result.contains("#[derive(Clone, PartialEq, Debug, Default)]"));
+    assert!(result.contains("    scores: HashMap<String, u32>,"));
+
+    Ok(())
+}
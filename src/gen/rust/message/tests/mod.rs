@@ -1 +1,16 @@
+mod booleans;
+mod enums;
+mod errors;
+mod fixed_ints;
+mod floats;
+mod keywords;
+mod lists;
+mod maps;
+mod nested;
+mod options;
+mod required_fields;
+mod reserved;
+mod signed_ints;
+mod strings;
+mod unknown_fields;
 mod unsigned_ints;
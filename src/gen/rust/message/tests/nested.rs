@@ -0,0 +1,76 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{List, Named};
+use crate::tree::{Message, MessageField, SourceFile, WithComments};
+
+#[test]
+fn nested() -> Result<(), GenError> {
+    let mut point: Message = "Point".into();
+    point.add_comment("A point.");
+    point.add_field(MessageField::from(("x", Float64)).with_field_number(1));
+    point.add_field(MessageField::from(("y", Float64)).with_field_number(2));
+
+    let mut line: Message = "Line".into();
+    line.add_comment("A line between two points.");
+    line.add_field(
+        MessageField::from(("start", Named("Point".to_string())))
+            .with_comment("The required start point."),
+    );
+    line.add_field(
+        MessageField::from(("end", Named("Point".to_string())))
+            .with_field_number(1)
+            .with_comment("The optional end point."),
+    );
+
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(point)
+        .with_declaration(line);
+    let source: Source = GenMessage::default().gen_declarations(&source_file, &[])?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("nested.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
+
+#[test]
+fn recursive_list() -> Result<(), GenError> {
+    let node: Message = Message::from("Node")
+        .with_field(MessageField::from(("value", UnsignedInt8)).with_field_number(1))
+        .with_field(
+            MessageField::from(("children", List(Box::new(Named("Node".to_string())))))
+                .with_field_number(2),
+        );
+
+    let source_file: SourceFile = SourceFile::default().with_declaration(node);
+    let source: Source = GenMessage::default().gen_declarations(&source_file, &[])?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("recursive_list.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{CodecTrait, GenMessage, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::String as Text;
+use crate::tree::{Message, MessageField, WithComments};
+
+#[test]
+fn options() -> Result<(), GenError> {
+    let mut message: Message = "Options".into();
+
+    message.add_comment("A message generated with options.");
+
+    message.add_field(
+        MessageField::from(("one", UnsignedInt8))
+            .with_field_number(1)
+            .with_comment("The first field."),
+    );
+    message.add_field(MessageField::from(("two", Text)).with_field_number(2));
+
+    let options: RustGenOptions = RustGenOptions::default()
+        .with_derives(["Clone", "Debug", "Default"])
+        .with_public_fields(true)
+        .with_setters_return_old(false)
+        .with_builders(false)
+        .with_attribute("Options", "non_exhaustive")
+        .with_codec_traits([CodecTrait::EncodedLen, CodecTrait::EncodeToWrite]);
+    let source: Source = GenMessage::default().with_options(options).gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("options.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
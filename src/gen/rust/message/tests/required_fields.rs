@@ -0,0 +1,41 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, WithComments};
+
+#[test]
+fn required_fields() -> Result<(), GenError> {
+    let mut message: Message = "RequiredFields".into();
+
+    message.add_comment("A message with required fields.");
+
+    message.add_field(MessageField::from(("one", UnsignedInt8)).with_comment("A required field."));
+    message.add_field(
+        MessageField::from(("two", UnsignedInt16))
+            .with_field_number(1)
+            .with_comment("An optional field."),
+    );
+    message.add_field(
+        MessageField::from(("three", UnsignedInt32)).with_comment("Another required field."),
+    );
+
+    let source: Source = GenMessage::default().gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("required_fields.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{GenMessage, RustGenOptions, UnknownFields};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, Reserved};
+
+#[test]
+fn reserved() -> Result<(), GenError> {
+    let message: Message = Message::from("Reserved")
+        .with_field(MessageField::from(("one", UnsignedInt8)).with_field_number(1))
+        .with_reserved(Reserved::Numbers(vec![(3, 3), (7, 9)]))
+        .with_reserved(Reserved::Names(vec!["old_name".to_string()]));
+
+    let source: Source = GenMessage::default()
+        .with_options(RustGenOptions::default().with_unknown_fields(UnknownFields::Preserve))
+        .gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("reserved.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
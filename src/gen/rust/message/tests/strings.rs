@@ -0,0 +1,44 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::TypeTag::{Bytes, String as Text};
+use crate::tree::{Message, MessageField, WithComments};
+
+#[test]
+fn strings() -> Result<(), GenError> {
+    let mut message: Message = "Strings".into();
+
+    message.add_comment("A message with string & byte-array fields.");
+
+    message.add_field(
+        MessageField::from(("one", Text))
+            .with_field_number(1)
+            .with_comment("The first field."),
+    );
+    message.add_field(
+        MessageField::from(("two", Bytes))
+            .with_field_number(2)
+            .with_comment("The second field."),
+    );
+    message.add_field(MessageField::from(("three", Text)).with_comment("A required string."));
+    message.add_field(MessageField::from(("four", Bytes)).with_comment("A required byte-array."));
+
+    let source: Source = GenMessage::default().gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("strings.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{GenMessage, RustGenOptions, UnknownFields};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, WithComments};
+
+#[test]
+fn unknown_fields() -> Result<(), GenError> {
+    let mut message: Message = "UnknownFields".into();
+
+    message.add_comment("A message preserving unknown fields.");
+
+    message.add_field(
+        MessageField::from(("one", UnsignedInt8))
+            .with_field_number(1)
+            .with_comment("The first field."),
+    );
+
+    let source: Source = GenMessage::default()
+        .with_options(RustGenOptions::default().with_unknown_fields(UnknownFields::Preserve))
+        .gen(&message)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("unknown_fields.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -1,7 +1,25 @@
+pub use codec_trait::*;
+pub use enums::*;
+pub use map_backing::*;
 pub use message::*;
 pub use naming::*;
+pub use rust_exp::*;
+pub use rust_gen_options::*;
+pub use service::*;
+pub use source_file::*;
 pub use typing::*;
+pub use unknown_fields::*;
+pub use variant::*;
 
+mod codec_trait;
+mod enums;
+mod map_backing;
 mod message;
 mod naming;
+mod rust_exp;
+mod rust_gen_options;
+mod service;
+mod source_file;
 mod typing;
+mod unknown_fields;
+mod variant;
@@ -1,20 +1,229 @@
+use std::collections::{BTreeMap, HashSet};
+
 use crate::gen::GenError;
+use crate::tree::{is_identifier, SourceFile};
+
+/// The rust keywords. (strict & reserved, escaped as raw identifiers)
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The rust keywords that cannot be raw identifiers. (escaped with a trailing `_`)
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
 
 /// Responsible for naming things.
+///
+/// Declared names are converted to `snake_case` for fields, functions & modules and to
+/// `UpperCamelCase` for types & enum cases. Rust keywords are escaped as raw identifiers.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
 pub struct Naming {
-    _nothing: (),
+    overrides: BTreeMap<String, String>,
+}
+
+impl Naming {
+    //! Options
+
+    /// Overrides the rust name for the declared name. (used instead of the converted name)
+    ///
+    /// Declarations are named by their declared name & their fields, cases & rpcs by their path in
+    /// the declaration. (`User` & `User.type`)
+    pub fn with_override<D, R>(mut self, declared_name: D, rust_name: R) -> Self
+    where
+        D: Into<String>,
+        R: Into<String>,
+    {
+        self.add_override(declared_name, rust_name);
+        self
+    }
+
+    /// Overrides the rust name for the declared name. (used instead of the converted name)
+    pub fn add_override<D, R>(&mut self, declared_name: D, rust_name: R)
+    where
+        D: Into<String>,
+        R: Into<String>,
+    {
+        self.overrides
+            .insert(declared_name.into(), rust_name.into());
+    }
+}
+
+impl Naming {
+    //! Identifiers
+
+    /// Gets the identifier if it is valid.
+    fn identifier(name: String) -> Result<String, GenError> {
+        if is_identifier(name.as_str()) {
+            Ok(name)
+        } else {
+            Err(GenError::InvalidIdentifier {
+                location: String::default(),
+                name,
+            })
+        }
+    }
+
+    /// Checks if the name is a rust keyword.
+    pub fn is_keyword(name: &str) -> bool {
+        KEYWORDS.contains(&name)
+    }
+
+    /// Escapes the name if it is a rust keyword.
+    ///
+    /// Keywords are escaped as raw identifiers (`r#type`), except for the keywords that cannot be
+    /// raw identifiers which get a trailing underscore. (`self_`)
+    pub fn escape_keyword(name: String) -> String {
+        if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+            format!("{}_", name)
+        } else if Self::is_keyword(name.as_str()) {
+            format!("r#{}", name)
+        } else {
+            name
+        }
+    }
+
+    /// Gets the overridden or converted name for the declared name in the scope. (not escaped)
+    ///
+    /// The scope is the declaration name, or empty for declarations & modules.
+    fn convert<S>(
+        &self,
+        scope: &str,
+        declared_name: S,
+        convert: fn(&str) -> String,
+    ) -> Result<String, GenError>
+    where
+        S: Into<String>,
+    {
+        let declared_name: String = Self::identifier(declared_name.into())?;
+        let path: String = if scope.is_empty() {
+            declared_name.clone()
+        } else {
+            format!("{}.{}", scope, declared_name)
+        };
+        match self.overrides.get(path.as_str()) {
+            Some(rust_name) => Self::identifier(rust_name.clone()),
+            None => Ok(convert(declared_name.as_str())),
+        }
+    }
+}
+
+impl Naming {
+    //! Collisions
+
+    /// Checks the rust name has not already been generated in the scope.
+    ///
+    /// The scope is the declaration name, or empty for the declarations of a source file.
+    pub fn check_collision(
+        rust_names: &mut HashSet<String>,
+        scope: &str,
+        declared_name: &str,
+        rust_name: String,
+    ) -> Result<(), GenError> {
+        if rust_names.contains(rust_name.as_str()) {
+            let name: String = if scope.is_empty() {
+                declared_name.to_string()
+            } else {
+                format!("{}.{}", scope, declared_name)
+            };
+            Err(GenError::NameCollision { name, rust_name })
+        } else {
+            rust_names.insert(rust_name);
+            Ok(())
+        }
+    }
+}
+
+impl Naming {
+    //! Case Conversion
+
+    /// Splits the name into words at underscores & lowercase to uppercase boundaries.
+    ///
+    /// Runs of uppercase letters are kept together as acronyms. (`HTTPServer` is `HTTP` & `Server`)
+    pub fn words(name: &str) -> Vec<&str> {
+        let bytes: &[u8] = name.as_bytes();
+        let mut words: Vec<&str> = Vec::default();
+        let mut start: usize = 0;
+        for i in 0..bytes.len() {
+            if bytes[i] == b'_' {
+                if start < i {
+                    words.push(&name[start..i]);
+                }
+                start = i + 1;
+            } else if bytes[i].is_ascii_uppercase() && start < i {
+                let prev: u8 = bytes[i - 1];
+                let next_lower: bool = bytes.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+                if prev.is_ascii_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_ascii_uppercase() && next_lower)
+                {
+                    words.push(&name[start..i]);
+                    start = i;
+                }
+            }
+        }
+        if start < bytes.len() {
+            words.push(&name[start..]);
+        }
+        words
+    }
+
+    /// Converts the name to `snake_case`. (leading underscores are kept)
+    pub fn to_snake_case(name: &str) -> String {
+        let prefix: &str = &name[..name.len() - name.trim_start_matches('_').len()];
+        let words: Vec<String> = Self::words(name)
+            .iter()
+            .map(|word| word.to_ascii_lowercase())
+            .collect();
+        format!("{}{}", prefix, words.join("_"))
+    }
+
+    /// Converts the name to `UpperCamelCase`. (leading underscores are kept)
+    pub fn to_upper_camel_case(name: &str) -> String {
+        let prefix: &str = &name[..name.len() - name.trim_start_matches('_').len()];
+        let mut result: String = prefix.to_string();
+        for word in Self::words(name) {
+            let (first, rest): (&str, &str) = word.split_at(1);
+            result.push_str(first.to_ascii_uppercase().as_str());
+            result.push_str(rest.to_ascii_lowercase().as_str());
+        }
+        result
+    }
 }
 
 impl Naming {
     //! Field Names
 
-    /// Gets the field name for the declared field name.
-    pub fn field_name<S>(&self, declared_name: S) -> Result<String, GenError>
+    /// Gets the field name for the declared field name in the message.
+    pub fn field_name<S>(&self, message: &str, declared_name: S) -> Result<String, GenError>
     where
         S: Into<String>,
     {
-        Ok(declared_name.into())
+        Ok(Self::escape_keyword(self.convert(
+            message,
+            declared_name,
+            Self::to_snake_case,
+        )?))
+    }
+
+    /// Gets the name of a field function from the pattern.
+    ///
+    /// The `{}` in the pattern is replaced with the unescaped field name. (`set_{}` is `set_type`)
+    pub fn field_fn_name<S>(
+        &self,
+        message: &str,
+        declared_name: S,
+        pattern: &str,
+    ) -> Result<String, GenError>
+    where
+        S: Into<String>,
+    {
+        let field_name: String = self.convert(message, declared_name, Self::to_snake_case)?;
+        Ok(Self::escape_keyword(
+            pattern.replace("{}", field_name.as_str()),
+        ))
     }
 }
 
@@ -26,6 +235,189 @@ impl Naming {
     where
         S: Into<String>,
     {
-        Ok(declared_name.into())
+        Ok(Self::escape_keyword(self.convert(
+            "",
+            declared_name,
+            Self::to_upper_camel_case,
+        )?))
+    }
+}
+
+impl Naming {
+    //! Case Names
+
+    /// Gets the enum variant name for the declared case name in the enum or variant.
+    pub fn case_name<S>(&self, declaration: &str, declared_name: S) -> Result<String, GenError>
+    where
+        S: Into<String>,
+    {
+        Ok(Self::escape_keyword(self.convert(
+            declaration,
+            declared_name,
+            Self::to_upper_camel_case,
+        )?))
+    }
+}
+
+impl Naming {
+    //! Function Names
+
+    /// Gets the function name for the declared rpc name in the service.
+    pub fn fn_name<S>(&self, service: &str, declared_name: S) -> Result<String, GenError>
+    where
+        S: Into<String>,
+    {
+        Ok(Self::escape_keyword(self.convert(
+            service,
+            declared_name,
+            Self::to_snake_case,
+        )?))
+    }
+}
+
+impl Naming {
+    //! Module Names
+
+    /// Gets the module name for the declared package segment or file name.
+    pub fn mod_name<S>(&self, declared_name: S) -> Result<String, GenError>
+    where
+        S: Into<String>,
+    {
+        Ok(Self::escape_keyword(self.convert(
+            "",
+            declared_name,
+            Self::to_snake_case,
+        )?))
+    }
+
+    /// Gets the module path for the source file with the path.
+    ///
+    /// The package segments are followed by the file name without its directory & extension.
+    pub fn mod_path(&self, source_file: &SourceFile, path: &str) -> Result<Vec<String>, GenError> {
+        let mut mod_path: Vec<String> = Vec::default();
+        if let Some(package) = source_file.package() {
+            for segment in package.segments() {
+                mod_path.push(self.mod_name(segment)?);
+            }
+        }
+        let file_name: &str = path.rsplit('/').next().unwrap_or(path);
+        let stem: &str = file_name
+            .split_once('.')
+            .map(|(stem, _)| stem)
+            .unwrap_or(file_name);
+        mod_path.push(self.mod_name(stem)?);
+        Ok(mod_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::rust::Naming;
+    use crate::gen::GenError;
+    use crate::tree::SourceFile;
+
+    #[test]
+    fn field_name() {
+        let naming: Naming = Naming::default();
+        assert_eq!(naming.field_name("User", "one"), Ok("one".to_string()));
+        assert_eq!(
+            naming.field_name("User", "_one_2"),
+            Ok("_one_2".to_string())
+        );
+        assert_eq!(
+            naming.field_name("User", "2one"),
+            Err(GenError::InvalidIdentifier {
+                location: String::default(),
+                name: "2one".to_string()
+            })
+        );
+        assert!(naming.field_name("User", "").is_err());
+        assert!(naming.field_name("User", "_").is_err());
+        assert!(naming.field_name("User", "one-two").is_err());
+
+        assert_eq!(
+            naming.field_name("User", "userId"),
+            Ok("user_id".to_string())
+        );
+        assert_eq!(naming.field_name("User", "type"), Ok("r#type".to_string()));
+        assert_eq!(naming.field_name("User", "self"), Ok("self_".to_string()));
+        assert_eq!(
+            naming.field_fn_name("User", "type", "set_{}"),
+            Ok("set_type".to_string())
+        );
+    }
+
+    #[test]
+    fn case_conversion() {
+        assert_eq!(
+            Naming::words("HTTPServer_v2Name"),
+            vec!["HTTP", "Server", "v2", "Name"]
+        );
+        assert_eq!(Naming::to_snake_case("getUserById"), "get_user_by_id");
+        assert_eq!(Naming::to_snake_case("_Private"), "_private");
+        assert_eq!(Naming::to_upper_camel_case("ACTIVE"), "Active");
+        assert_eq!(Naming::to_upper_camel_case("http_status"), "HttpStatus");
+
+        let naming: Naming = Naming::default();
+        assert_eq!(
+            naming.type_name("user_account"),
+            Ok("UserAccount".to_string())
+        );
+        assert_eq!(
+            naming.case_name("Status", "NOT_FOUND"),
+            Ok("NotFound".to_string())
+        );
+        assert_eq!(
+            naming.fn_name("Users", "GetUser"),
+            Ok("get_user".to_string())
+        );
+        assert_eq!(naming.type_name("Self"), Ok("Self_".to_string()));
+    }
+
+    #[test]
+    fn overrides() {
+        let naming: Naming = Naming::default()
+            .with_override("User.type", "kind")
+            .with_override("URLs", "Urls")
+            .with_override("Kind", "Category");
+        assert_eq!(naming.field_name("User", "type"), Ok("kind".to_string()));
+        assert_eq!(
+            naming.field_fn_name("User", "type", "with_{}"),
+            Ok("with_kind".to_string())
+        );
+        assert_eq!(naming.field_name("Group", "type"), Ok("r#type".to_string()));
+        assert_eq!(naming.type_name("URLs"), Ok("Urls".to_string()));
+        assert_eq!(naming.type_name("Kind"), Ok("Category".to_string()));
+        assert_eq!(naming.field_name("User", "Kind"), Ok("kind".to_string()));
+
+        let naming: Naming = naming.with_override("User.match", "match");
+        assert_eq!(
+            naming.field_name("User", "match"),
+            Ok("r#match".to_string())
+        );
+
+        let naming: Naming = naming.with_override("User.one", "not-valid");
+        assert!(naming.field_name("User", "one").is_err());
+    }
+
+    #[test]
+    fn mod_path() {
+        let naming: Naming = Naming::default();
+        let source_file: SourceFile = SourceFile::default().with_package("acme.billing");
+        assert_eq!(
+            naming.mod_path(&source_file, "schema/invoice.pp"),
+            Ok(vec![
+                "acme".to_string(),
+                "billing".to_string(),
+                "invoice".to_string()
+            ])
+        );
+        assert_eq!(
+            naming.mod_path(&SourceFile::default(), "common.pp"),
+            Ok(vec!["common".to_string()])
+        );
+        assert!(naming
+            .mod_path(&SourceFile::default(), "my-file.pp")
+            .is_err());
     }
 }
@@ -0,0 +1,382 @@
+use code_gen::{CodeBuffer, Expression, Statement};
+
+/// A rust expression in the generated code.
+///
+/// Expressions are built as trees so the names in them come from `Naming` & the generated code
+/// can be analyzed before it is written.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum RustExp {
+    /// A path, variable or literal. (`self`, `read::read_u8`, `1`)
+    Path(String),
+
+    /// A string literal. (`"name"`)
+    Str(String),
+
+    /// A field access. (`self.one`)
+    Field(Box<RustExp>, String),
+
+    /// A function call. (`Some(value)`)
+    Call(Box<RustExp>, Vec<RustExp>),
+
+    /// A method call. (`self.one.as_deref()`)
+    MethodCall(Box<RustExp>, String, Vec<RustExp>),
+
+    /// An index. (`target[encoded_len..]`)
+    Index(Box<RustExp>, Box<RustExp>),
+
+    /// A range from the start. (`encoded_len..`)
+    RangeFrom(Box<RustExp>),
+
+    /// A reference. (`&self.one`, `&mut r`)
+    Ref(bool, Box<RustExp>),
+
+    /// A dereference. (`*value`)
+    Deref(Box<RustExp>),
+
+    /// A try expression. (`read::read_u8(&mut r)?`)
+    Try(Box<RustExp>),
+
+    /// A tuple. (`(key, value)`)
+    Tuple(Vec<RustExp>),
+
+    /// A closure with the parameter pattern. (`|(key, value)| key`)
+    Closure(String, Box<RustExp>),
+
+    /// An assignment. (`result = value`)
+    Assign(Box<RustExp>, Box<RustExp>),
+
+    /// An add-assignment. (`encoded_len += value`)
+    AddAssign(Box<RustExp>, Box<RustExp>),
+
+    /// A typed let binding, mutable if the flag is set. (`let mut result: Self = value`)
+    Let(bool, String, String, Box<RustExp>),
+}
+
+impl From<&str> for RustExp {
+    fn from(path: &str) -> Self {
+        Self::Path(path.to_string())
+    }
+}
+
+impl From<String> for RustExp {
+    fn from(path: String) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<u32> for RustExp {
+    fn from(value: u32) -> Self {
+        Self::Path(value.to_string())
+    }
+}
+
+impl From<bool> for RustExp {
+    fn from(value: bool) -> Self {
+        Self::Path(value.to_string())
+    }
+}
+
+impl RustExp {
+    //! Construction
+
+    /// Creates a string literal.
+    pub fn str<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Str(value.into())
+    }
+
+    /// Creates a call of the function with the arguments.
+    pub fn call<F, I>(function: F, args: I) -> Self
+    where
+        F: Into<RustExp>,
+        I: IntoIterator<Item = RustExp>,
+    {
+        Self::Call(Box::new(function.into()), args.into_iter().collect())
+    }
+
+    /// Creates a tuple of the elements.
+    pub fn tuple<I>(elements: I) -> Self
+    where
+        I: IntoIterator<Item = RustExp>,
+    {
+        Self::Tuple(elements.into_iter().collect())
+    }
+
+    /// Creates a range from the start.
+    pub fn range_from<S>(start: S) -> Self
+    where
+        S: Into<RustExp>,
+    {
+        Self::RangeFrom(Box::new(start.into()))
+    }
+
+    /// Creates a closure with the parameter pattern & body.
+    pub fn closure<S>(params: S, body: RustExp) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Closure(params.into(), Box::new(body))
+    }
+
+    /// Creates an assignment of the value to the target.
+    pub fn assign<T>(target: T, value: RustExp) -> Self
+    where
+        T: Into<RustExp>,
+    {
+        Self::Assign(Box::new(target.into()), Box::new(value))
+    }
+
+    /// Creates an add-assignment of the value to the target.
+    pub fn add_assign<T>(target: T, value: RustExp) -> Self
+    where
+        T: Into<RustExp>,
+    {
+        Self::AddAssign(Box::new(target.into()), Box::new(value))
+    }
+
+    /// Creates a let binding of the value to the variable with the type.
+    pub fn let_binding<N, T>(mutable: bool, name: N, type_name: T, value: RustExp) -> Self
+    where
+        N: Into<String>,
+        T: Into<String>,
+    {
+        Self::Let(mutable, name.into(), type_name.into(), Box::new(value))
+    }
+}
+
+impl RustExp {
+    //! Conversions
+
+    /// Accesses the field of the expression.
+    pub fn field<S>(self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Field(Box::new(self), name.into())
+    }
+
+    /// Calls the method on the expression with the arguments.
+    pub fn method<S, I>(self, name: S, args: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = RustExp>,
+    {
+        Self::MethodCall(Box::new(self), name.into(), args.into_iter().collect())
+    }
+
+    /// Indexes the expression.
+    pub fn index(self, index: RustExp) -> Self {
+        Self::Index(Box::new(self), Box::new(index))
+    }
+
+    /// Converts the expression to a reference.
+    pub fn to_ref(self) -> Self {
+        Self::Ref(false, Box::new(self))
+    }
+
+    /// Converts the expression to a mutable reference.
+    pub fn to_mut_ref(self) -> Self {
+        Self::Ref(true, Box::new(self))
+    }
+
+    /// Converts the expression to a dereference.
+    pub fn to_deref(self) -> Self {
+        Self::Deref(Box::new(self))
+    }
+
+    /// Converts the expression to a try expression.
+    pub fn to_try(self) -> Self {
+        Self::Try(Box::new(self))
+    }
+
+    /// Wraps the expression in `Some`.
+    pub fn to_some(self) -> Self {
+        Self::call("Some", [self])
+    }
+}
+
+impl RustExp {
+    //! Write
+
+    /// Checks if the expression needs parentheses as the receiver of a postfix expression.
+    fn is_prefix_or_binary(&self) -> bool {
+        matches!(
+            self,
+            Self::RangeFrom(_)
+                | Self::Ref(_, _)
+                | Self::Deref(_)
+                | Self::Closure(_, _)
+                | Self::Assign(_, _)
+                | Self::AddAssign(_, _)
+                | Self::Let(_, _, _, _)
+        )
+    }
+
+    /// Writes the expression as the receiver of a postfix expression.
+    fn write_receiver(&self, b: &mut CodeBuffer) {
+        if self.is_prefix_or_binary() {
+            b.write("(");
+            Expression::write(self, b);
+            b.write(")");
+        } else {
+            Expression::write(self, b);
+        }
+    }
+
+    /// Writes the comma-separated expressions.
+    fn write_list(b: &mut CodeBuffer, exps: &[RustExp]) {
+        for (i, exp) in exps.iter().enumerate() {
+            if i > 0 {
+                b.write(", ");
+            }
+            Expression::write(exp, b);
+        }
+    }
+}
+
+impl Expression for RustExp {
+    fn write(&self, b: &mut CodeBuffer) {
+        match self {
+            Self::Path(path) => b.write(path),
+            Self::Str(value) => b.write(format!("{:?}", value).as_str()),
+            Self::Field(exp, name) => {
+                exp.write_receiver(b);
+                b.write(".");
+                b.write(name);
+            }
+            Self::Call(function, args) => {
+                function.write_receiver(b);
+                b.write("(");
+                Self::write_list(b, args);
+                b.write(")");
+            }
+            Self::MethodCall(exp, name, args) => {
+                exp.write_receiver(b);
+                b.write(".");
+                b.write(name);
+                b.write("(");
+                Self::write_list(b, args);
+                b.write(")");
+            }
+            Self::Index(exp, index) => {
+                exp.write_receiver(b);
+                b.write("[");
+                Expression::write(index.as_ref(), b);
+                b.write("]");
+            }
+            Self::RangeFrom(start) => {
+                Expression::write(start.as_ref(), b);
+                b.write("..");
+            }
+            Self::Ref(mutable, exp) => {
+                b.write(if *mutable { "&mut " } else { "&" });
+                Expression::write(exp.as_ref(), b);
+            }
+            Self::Deref(exp) => {
+                b.write("*");
+                Expression::write(exp.as_ref(), b);
+            }
+            Self::Try(exp) => {
+                exp.write_receiver(b);
+                b.write("?");
+            }
+            Self::Tuple(elements) => {
+                b.write("(");
+                Self::write_list(b, elements);
+                b.write(")");
+            }
+            Self::Closure(params, body) => {
+                b.write("|");
+                b.write(params);
+                b.write("| ");
+                Expression::write(body.as_ref(), b);
+            }
+            Self::Assign(target, value) => {
+                Expression::write(target.as_ref(), b);
+                b.write(" = ");
+                Expression::write(value.as_ref(), b);
+            }
+            Self::AddAssign(target, value) => {
+                Expression::write(target.as_ref(), b);
+                b.write(" += ");
+                Expression::write(value.as_ref(), b);
+            }
+            Self::Let(mutable, name, type_name, value) => {
+                b.write(if *mutable { "let mut " } else { "let " });
+                b.write(name);
+                b.write(": ");
+                b.write(type_name);
+                b.write(" = ");
+                Expression::write(value.as_ref(), b);
+            }
+        }
+    }
+}
+
+impl Statement for RustExp {
+    fn write(&self, b: &mut CodeBuffer, level: usize) {
+        b.indent(level);
+        Expression::write(self, b);
+        b.end_line();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use code_gen::{CodeBuffer, Expression};
+
+    use crate::gen::rust::RustExp;
+
+    fn render(exp: &RustExp) -> String {
+        let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 256);
+        Expression::write(exp, &mut b);
+        b.export()
+    }
+
+    #[test]
+    fn write() {
+        let cases: &[(RustExp, &str)] = &[
+            (RustExp::from("self").field("r#type"), "self.r#type"),
+            (
+                RustExp::from("self").field("one").method("as_deref", []),
+                "self.one.as_deref()",
+            ),
+            (
+                RustExp::call("read::read_u8", [RustExp::from("r")]).to_try(),
+                "read::read_u8(r)?",
+            ),
+            (RustExp::from("value").to_deref().to_some(), "Some(*value)"),
+            (
+                RustExp::from("value").to_deref().method("clone", []),
+                "(*value).clone()",
+            ),
+            (
+                RustExp::closure(
+                    "(key, value)",
+                    RustExp::tuple([RustExp::from("key"), RustExp::from("value")]),
+                ),
+                "|(key, value)| (key, value)",
+            ),
+            (
+                RustExp::add_assign("encoded_len", RustExp::from("x").method("encoded_len", [])),
+                "encoded_len += x.encoded_len()",
+            ),
+            (
+                RustExp::from("target")
+                    .index(RustExp::range_from("encoded_len"))
+                    .to_mut_ref(),
+                "&mut target[encoded_len..]",
+            ),
+            (
+                RustExp::let_binding(true, "result", "Self", RustExp::call("Self::default", [])),
+                "let mut result: Self = Self::default()",
+            ),
+            (RustExp::str("a \"b\""), "\"a \\\"b\\\"\""),
+        ];
+        for (exp, expected) in cases {
+            assert_eq!(render(exp), *expected);
+        }
+    }
+}
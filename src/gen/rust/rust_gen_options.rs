@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::gen::rust::{CodecTrait, MapBacking, UnknownFields};
+
+/// The options for generating rust code for message, enum & variant types.
+#[derive(Clone, Debug)]
+pub struct RustGenOptions {
+    derives: Option<Vec<String>>,
+    public_fields: bool,
+    setters_return_old: bool,
+    builders: bool,
+    attributes: BTreeMap<String, Vec<String>>,
+    codec_traits: BTreeSet<CodecTrait>,
+    unknown_fields: UnknownFields,
+    map_backing: MapBacking,
+}
+
+impl Default for RustGenOptions {
+    fn default() -> Self {
+        Self {
+            derives: None,
+            public_fields: false,
+            setters_return_old: true,
+            builders: true,
+            attributes: BTreeMap::default(),
+            codec_traits: CodecTrait::ALL.into_iter().collect(),
+            unknown_fields: UnknownFields::default(),
+            map_backing: MapBacking::default(),
+        }
+    }
+}
+
+impl RustGenOptions {
+    //! Builders
+
+    /// Sets the derives for the generated types, replacing the derives inferred from the field
+    /// types.
+    ///
+    /// `Default` is added when the message structs implement `DecodeFromRead`. (decoding starts
+    /// from the default message) Enums & variants implement `Default` themselves & enums always
+    /// derive `Copy` & `Clone`.
+    pub fn with_derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.derives = Some(derives.into_iter().map(|derive| derive.into()).collect());
+        self.add_requirements();
+        self
+    }
+
+    /// Sets whether the message struct fields are public.
+    pub fn with_public_fields(mut self, public_fields: bool) -> Self {
+        self.public_fields = public_fields;
+        self
+    }
+
+    /// Sets whether the field setters return the previous value.
+    pub fn with_setters_return_old(mut self, setters_return_old: bool) -> Self {
+        self.setters_return_old = setters_return_old;
+        self
+    }
+
+    /// Sets whether the field builders are generated.
+    pub fn with_builders(mut self, builders: bool) -> Self {
+        self.builders = builders;
+        self
+    }
+
+    /// Adds an attribute for the type generated for the declaration with the name.
+    /// (`serde(default)`)
+    pub fn with_attribute<N, A>(mut self, message: N, attribute: A) -> Self
+    where
+        N: Into<String>,
+        A: Into<String>,
+    {
+        self.attributes
+            .entry(message.into())
+            .or_default()
+            .push(attribute.into());
+        self
+    }
+
+    /// Sets the codec traits implemented for the generated types. (enums only implement
+    /// `DecodeFromRead`, their encoding is provided by the runtime)
+    ///
+    /// The codec traits required by the set codec traits are added. (see `CodecTrait::requires`)
+    pub fn with_codec_traits<I>(mut self, codec_traits: I) -> Self
+    where
+        I: IntoIterator<Item = CodecTrait>,
+    {
+        self.codec_traits = codec_traits.into_iter().collect();
+        self.add_requirements();
+        self
+    }
+
+    /// Sets the handling of unknown fields when decoding.
+    pub fn with_unknown_fields(mut self, unknown_fields: UnknownFields) -> Self {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+
+    /// Sets the rust type backing map fields.
+    pub fn with_map_backing(mut self, map_backing: MapBacking) -> Self {
+        self.map_backing = map_backing;
+        self
+    }
+}
+
+impl RustGenOptions {
+    //! Requirements
+
+    /// Adds the codec traits & derives required by the codec traits.
+    fn add_requirements(&mut self) {
+        let required: Vec<CodecTrait> = self
+            .codec_traits
+            .iter()
+            .filter_map(CodecTrait::requires)
+            .collect();
+        self.codec_traits.extend(required);
+
+        if self.implements(CodecTrait::DecodeFromRead) {
+            if let Some(derives) = &mut self.derives {
+                if !derives.iter().any(|derive| derive == "Default") {
+                    derives.push("Default".to_string());
+                }
+            }
+        }
+    }
+}
+
+impl RustGenOptions {
+    //! Properties
+
+    /// Gets the derives for the generated types. (`None` if the derives are inferred)
+    pub fn derives(&self) -> Option<&[String]> {
+        self.derives.as_deref()
+    }
+
+    /// Checks if the message struct fields are public.
+    pub fn public_fields(&self) -> bool {
+        self.public_fields
+    }
+
+    /// Checks if the field setters return the previous value.
+    pub fn setters_return_old(&self) -> bool {
+        self.setters_return_old
+    }
+
+    /// Checks if the field builders are generated.
+    pub fn builders(&self) -> bool {
+        self.builders
+    }
+
+    /// Gets the attributes for the type generated for the declaration with the name.
+    pub fn attributes(&self, message: &str) -> &[String] {
+        self.attributes
+            .get(message)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Checks if the codec trait is implemented for the generated types.
+    pub fn implements(&self, codec_trait: CodecTrait) -> bool {
+        self.codec_traits.contains(&codec_trait)
+    }
+
+    /// Gets the handling of unknown fields when decoding.
+    pub fn unknown_fields(&self) -> UnknownFields {
+        self.unknown_fields
+    }
+
+    /// Gets the rust type backing map fields.
+    pub fn map_backing(&self) -> MapBacking {
+        self.map_backing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::rust::{CodecTrait, RustGenOptions};
+
+    #[test]
+    fn requirements() {
+        let options: RustGenOptions = RustGenOptions::default()
+            .with_derives(["Clone", "Debug"])
+            .with_codec_traits([
+                CodecTrait::EncodeToSlice,
+                CodecTrait::DecodeFromReadLengthPrefixed,
+            ]);
+        assert!(options.implements(CodecTrait::EncodedLen));
+        assert!(options.implements(CodecTrait::DecodeFromRead));
+        assert!(!options.implements(CodecTrait::EncodeToWrite));
+        assert_eq!(
+            options.derives(),
+            Some(["Clone", "Debug", "Default"].map(String::from).as_slice())
+        );
+
+        let options: RustGenOptions =
+            RustGenOptions::default().with_codec_traits([CodecTrait::EncodeToWrite]);
+        assert!(options.implements(CodecTrait::EncodedLen));
+        assert!(!options.implements(CodecTrait::EncodeToSlice));
+        assert!(!options.implements(CodecTrait::DecodeFromRead));
+
+        let options: RustGenOptions =
+            RustGenOptions::default().with_codec_traits([CodecTrait::DecodeFromRead]);
+        assert!(options.implements(CodecTrait::DecodeFromReadLengthPrefixed));
+        assert!(!options.implements(CodecTrait::EncodedLen));
+
+        let options: RustGenOptions = RustGenOptions::default()
+            .with_codec_traits([CodecTrait::EncodedLen])
+            .with_derives(["Clone"]);
+        assert_eq!(
+            options.derives(),
+            Some(["Clone"].map(String::from).as_slice())
+        );
+    }
+}
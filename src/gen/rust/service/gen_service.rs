@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+
+use code_gen::rust::Access::Public;
+use code_gen::rust::{
+    Function, ImplBlock, Match, MatchCase, Receiver, Reference, Signature, Source, Struct, Trait,
+    TypeTag as RustType, WithAccess, WithComments as WithRustComments, WithFnGenerics,
+    WithFunctions, WithReceiver, WithResult, WithStructFields, WithVarParams,
+};
+use code_gen::{WithName, WithStatements};
+
+use crate::gen::rust::{CodecTrait, Naming, RustGenOptions, Typing};
+use crate::gen::GenError;
+use crate::tree::{Rpc, Service, WithComments};
+
+/// The bound on the transport generic of the dispatch function & client struct.
+const TRANSPORT_BOUND: &str = "io::Read + io::Write";
+
+/// The codec traits the request & response messages must implement.
+const CODEC_TRAITS: [CodecTrait; 3] = [
+    CodecTrait::EncodedLen,
+    CodecTrait::EncodeToWrite,
+    CodecTrait::DecodeFromReadLengthPrefixed,
+];
+
+/// Responsible for generating code for service types.
+///
+/// Each request is written as the method id followed by the length-prefixed request message, and
+/// answered with the length-prefixed response message.
+#[derive(Copy, Clone, Debug)]
+pub struct GenService<'a> {
+    naming: &'a Naming,
+    typing: &'a Typing,
+    options: &'a RustGenOptions,
+}
+
+impl<'a> GenService<'a> {
+    //! Construction
+
+    /// Creates a new gen service.
+    pub const fn new(naming: &'a Naming, typing: &'a Typing, options: &'a RustGenOptions) -> Self {
+        Self {
+            naming,
+            typing,
+            options,
+        }
+    }
+}
+
+impl<'a> GenService<'a> {
+    //! Gen
+
+    /// Generates the source code for the service.
+    pub fn gen(&self, service: &Service) -> Result<Source, GenError> {
+        self.gen_source(service).map_err(|e| e.at(service.name()))
+    }
+
+    /// Generates the source code for the service without locating the errors.
+    fn gen_source(&self, service: &Service) -> Result<Source, GenError> {
+        self.check_rpcs(service)?;
+
+        let mut source: Source = Source::default();
+        source.add_statement(self.gen_trait(service)?);
+        source.add_statement(self.gen_client_struct(service)?);
+        source.add_statement(self.gen_impl_client_construction(service)?);
+        source.add_statement(self.gen_impl_client_service(service)?);
+        Ok(source)
+    }
+
+    /// Checks the service rpcs for duplicate & colliding names and unresolved messages, and the
+    /// messages implement the codec traits of the service.
+    fn check_rpcs(&self, service: &Service) -> Result<(), GenError> {
+        if !service.rpcs().is_empty() {
+            if let Some(codec_trait) = CODEC_TRAITS
+                .into_iter()
+                .find(|codec_trait| !self.options.implements(*codec_trait))
+            {
+                return Err(GenError::MissingCodecTrait {
+                    service: service.name().to_string(),
+                    codec_trait: codec_trait.trait_name().to_string(),
+                });
+            }
+        }
+        let mut names: HashSet<&str> = HashSet::default();
+        let mut rust_names: HashSet<String> = HashSet::default();
+        rust_names.insert("serve".to_string());
+        for rpc in service.rpcs() {
+            if !names.insert(rpc.name()) {
+                return Err(GenError::DuplicateRpcName {
+                    service: service.name().to_string(),
+                    rpc: rpc.name().to_string(),
+                });
+            }
+            Naming::check_collision(
+                &mut rust_names,
+                service.name(),
+                rpc.name(),
+                self.naming
+                    .fn_name(service.name(), rpc.name())
+                    .map_err(|e| e.at(format!("{}.{}", service.name(), rpc.name())))?,
+            )?;
+            self.typing
+                .message(rpc.request())
+                .map_err(|e| e.at(format!("{}.{}", service.name(), rpc.name())))?;
+            self.typing
+                .message(rpc.response())
+                .map_err(|e| e.at(format!("{}.{}", service.name(), rpc.name())))?;
+        }
+        Ok(())
+    }
+
+    /// Gets the rust type names of the request & response messages of the rpc.
+    fn message_names(&self, rpc: &Rpc) -> Result<(String, String), GenError> {
+        Ok((
+            self.naming
+                .type_name(self.typing.message(rpc.request())?.name())?,
+            self.naming
+                .type_name(self.typing.message(rpc.response())?.name())?,
+        ))
+    }
+
+    /// Generates the signature of the rpc function.
+    fn gen_rpc_signature(&self, service: &Service, rpc: &Rpc) -> Result<Signature, GenError> {
+        let (request, response): (String, String) = self.message_names(rpc)?;
+        let result_type: RustType = RustType::from("Result")
+            .with_generic(response.as_str())
+            .with_generic("io::Error");
+        Ok(
+            Signature::from(self.naming.fn_name(service.name(), rpc.name())?)
+                .with_receiver(Receiver::BorrowedMut)
+                .with_param(("request", request.as_str()))
+                .with_result(result_type),
+        )
+    }
+}
+
+impl<'a> GenService<'a> {
+    //! Trait
+
+    /// Generates the service trait.
+    ///
+    /// The trait is implemented by servers & by the generated client.
+    fn gen_trait(&self, service: &Service) -> Result<Trait, GenError> {
+        let mut t: Trait = self.naming.type_name(service.name())?.into();
+        t.set_access(Public);
+        for line in service.to_string().lines() {
+            t.add_comment(line);
+        }
+
+        for rpc in service.rpcs() {
+            let mut function: Function = Function::from(self.gen_rpc_signature(service, rpc)?);
+            for comment in rpc.comments() {
+                function.add_comment(comment.as_str());
+            }
+            function.add_comment(format!("`{}`", rpc));
+            t.add_function(function);
+        }
+        t.add_function(self.gen_fn_serve(service)?);
+
+        Ok(t)
+    }
+
+    /// Generates the `serve` function dispatching a single request read from the transport.
+    fn gen_fn_serve(&self, service: &Service) -> Result<Function, GenError> {
+        let result_type: RustType = RustType::from("Result")
+            .with_generic("()")
+            .with_generic("io::Error");
+        let signature: Signature = Signature::from("serve")
+            .with_receiver(Receiver::BorrowedMut)
+            .with_generic(("T", TRANSPORT_BOUND))
+            .with_param((
+                "transport",
+                RustType::from("T").to_reference(Reference::MUT),
+            ))
+            .with_result(result_type);
+        let mut function: Function = Function::from(signature);
+        function.add_comment(
+            "Reads a request from the transport, dispatches it by method id & writes the response.",
+        );
+
+        function.add_semi("let method_id: u32 = read::read_u32_var(transport)?");
+        let mut match_statement: Match = Match::from("method_id");
+        for (method_id, rpc) in service.method_ids() {
+            let (request, response): (String, String) = self.message_names(rpc)?;
+            let match_case: MatchCase = MatchCase::from(method_id.to_string())
+                .with_semi(format!(
+                    "let request: {} = {}::decode_from_read_length_prefixed(transport)?",
+                    request, request
+                ))
+                .with_semi(format!(
+                    "let response: {} = self.{}(request)?",
+                    response,
+                    self.naming.fn_name(service.name(), rpc.name())?
+                ))
+                .with_semi("write::write_length_prefixed(transport, &response)?");
+            match_statement.add_match_case(match_case);
+        }
+        match_statement.add_match_case(MatchCase::from("_").with_semi(
+            "return Err(io::Error::new(io::ErrorKind::InvalidData, format!(\"unknown method id: {}\", method_id)))",
+        ));
+        function.add_statement(match_statement);
+        function.add_literal("transport.flush()");
+
+        Ok(function)
+    }
+}
+
+impl<'a> GenService<'a> {
+    //! Client
+
+    /// Gets the rust type name of the service client.
+    fn client_name(&self, service: &Service) -> Result<String, GenError> {
+        Ok(format!("{}Client", self.naming.type_name(service.name())?))
+    }
+
+    /// Gets the rust type of the service client with the transport generic.
+    fn client_type(&self, service: &Service) -> Result<RustType, GenError> {
+        Ok(RustType::from(self.client_name(service)?).with_generic("T"))
+    }
+
+    /// Generates the client struct declaration.
+    fn gen_client_struct(&self, service: &Service) -> Result<Struct, GenError> {
+        let mut s: Struct = self.client_name(service)?.into();
+        s.set_access(Public);
+        s.add_comment(format!(
+            "The client for the service: `{}`.",
+            self.naming.type_name(service.name())?
+        ));
+        s.add_generic("T");
+        s.add_field(("transport", "T"));
+        Ok(s)
+    }
+
+    /// Generates the impl block constructing the client.
+    fn gen_impl_client_construction(&self, service: &Service) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.client_type(service)?.into();
+        block.add_generic("T");
+        block.add_comment("Construction");
+
+        let signature: Signature = Signature::from("new")
+            .with_param(("transport", "T"))
+            .with_result("Self");
+        let mut function: Function = Function::from(signature);
+        function.set_access(Public);
+        function.add_comment("Creates a new client over the transport.");
+        function.add_literal("Self { transport }");
+        block.add_function(function);
+
+        let signature: Signature = Signature::from("into_transport")
+            .with_receiver(Receiver::Owned)
+            .with_result("T");
+        let mut function: Function = Function::from(signature);
+        function.set_access(Public);
+        function.add_comment("Gets the transport.");
+        function.add_literal("self.transport");
+        block.add_function(function);
+
+        Ok(block)
+    }
+
+    /// Generates the impl block implementing the service trait for the client.
+    ///
+    /// Each rpc writes the method id & request, flushes the transport and reads the response.
+    fn gen_impl_client_service(&self, service: &Service) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.client_type(service)?.into();
+        block.add_generic(("T", TRANSPORT_BOUND));
+        block.set_for_trait(self.naming.type_name(service.name())?);
+
+        for (method_id, rpc) in service.method_ids() {
+            let (_, response): (String, String) = self.message_names(rpc)?;
+            let mut function: Function = Function::from(self.gen_rpc_signature(service, rpc)?);
+            function.add_semi(format!(
+                "write::write_u32_var(&mut self.transport, {})?",
+                method_id
+            ));
+            function.add_semi("write::write_length_prefixed(&mut self.transport, &request)?");
+            function.add_semi("self.transport.flush()?");
+            function.add_literal(format!(
+                "{}::decode_from_read_length_prefixed(&mut self.transport)",
+                response
+            ));
+            block.add_function(function);
+        }
+
+        Ok(block)
+    }
+}
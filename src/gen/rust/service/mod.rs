@@ -0,0 +1,6 @@
+pub use gen_service::*;
+
+mod gen_service;
+
+#[cfg(test)]
+mod tests;
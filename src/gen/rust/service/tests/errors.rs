@@ -0,0 +1,49 @@
+use crate::gen::rust::{CodecTrait, GenMessage, GenSourceFile, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::{Message, Service, SourceFile};
+use crate::validate::Diagnostic;
+
+#[test]
+fn invalid_schema() {
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(Message::from("Request"))
+        .with_declaration(
+            Service::from("Dup")
+                .with_rpc(("Get", "Request", "Request"))
+                .with_rpc(("Get", "Request", "Request")),
+        );
+    let result: Result<_, GenError> = GenSourceFile::default().gen(&source_file);
+    let expected: GenError =
+        GenError::InvalidSchema(vec![Diagnostic::new("Dup.Get", "duplicate name `Get`")].into());
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unresolved_request() {
+    let service: Service = Service::from("Users").with_rpc(("Get", "Request", "Response"));
+    let result: Result<_, GenError> = GenMessage::default().gen_service(&service);
+    let expected: GenError = GenError::UnresolvedType {
+        location: "Users.Get".to_string(),
+        name: "Request".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn missing_codec_trait() {
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(Message::from("Request"))
+        .with_declaration(Service::from("Users").with_rpc(("Get", "Request", "Request")));
+    let options: RustGenOptions = RustGenOptions::default().with_codec_traits([
+        CodecTrait::EncodedLen,
+        CodecTrait::DecodeFromReadLengthPrefixed,
+    ]);
+    let result: Result<_, GenError> = GenMessage::default()
+        .with_options(options)
+        .gen_declarations(&source_file, &[]);
+    let expected: GenError = GenError::MissingCodecTrait {
+        service: "Users".to_string(),
+        codec_trait: "EncodeToWrite".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
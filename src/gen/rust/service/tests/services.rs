@@ -0,0 +1,43 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, Rpc, Service, SourceFile, TypeTag, WithComments};
+
+#[test]
+fn services() -> Result<(), GenError> {
+    let request: Message = Message::from("GetUserRequest")
+        .with_field(MessageField::from(("id", UnsignedInt64)).with_field_number(1));
+    let response: Message = Message::from("GetUserResponse")
+        .with_field(MessageField::from(("name", TypeTag::String)).with_field_number(1));
+
+    let mut service: Service = "Users".into();
+    service.add_comment("The users.");
+    service.add_rpc(
+        Rpc::from(("GetUser", "GetUserRequest", "GetUserResponse")).with_comment("Gets a user."),
+    );
+    service.add_rpc(("DeleteUser", "GetUserRequest", "GetUserResponse"));
+
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(request)
+        .with_declaration(response)
+        .with_declaration(service);
+    let source: Source = GenMessage::default().gen_declarations(&source_file, &[])?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("services.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
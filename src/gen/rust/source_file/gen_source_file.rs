@@ -0,0 +1,205 @@
+use code_gen::rust::Source;
+use code_gen::WithStatements;
+
+use crate::gen::rust::{CodecTrait, GenMessage, SemiLines};
+use crate::gen::GenError;
+use crate::tree::{SourceFile, SourceSet, TypeTag, WithTypeTag};
+use crate::validate::validate;
+
+/// The default path of the runtime module targeted by the generated code.
+pub const DEFAULT_RUNTIME_PATH: &str = "proto_packet_core::runtime";
+
+/// The default path of the module the generated modules are declared in.
+pub const DEFAULT_MOD_ROOT: &str = "crate";
+
+/// Responsible for generating complete rust modules for source files.
+#[derive(Clone, Debug)]
+pub struct GenSourceFile {
+    gen: GenMessage,
+    runtime_path: String,
+    mod_root: String,
+}
+
+impl Default for GenSourceFile {
+    fn default() -> Self {
+        GenMessage::default().into()
+    }
+}
+
+impl From<GenMessage> for GenSourceFile {
+    fn from(gen: GenMessage) -> Self {
+        Self {
+            gen,
+            runtime_path: DEFAULT_RUNTIME_PATH.to_string(),
+            mod_root: DEFAULT_MOD_ROOT.to_string(),
+        }
+    }
+}
+
+impl GenSourceFile {
+    //! Options
+
+    /// Sets the path of the runtime module targeted by the generated code.
+    pub fn with_runtime_path<S>(mut self, runtime_path: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.runtime_path = runtime_path.into();
+        self
+    }
+
+    /// Sets the path of the module the generated modules are declared in. (`crate::schema`)
+    pub fn with_mod_root<S>(mut self, mod_root: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.mod_root = mod_root.into();
+        self
+    }
+
+    /// Gets the message generator.
+    pub fn message_gen(&self) -> &GenMessage {
+        &self.gen
+    }
+}
+
+impl GenSourceFile {
+    //! Gen
+
+    /// Generates the rust module for the source file.
+    ///
+    /// The source file is validated first. The module starts with the `use` statements for the
+    /// standard library & runtime items the generated code references.
+    pub fn gen(&self, source_file: &SourceFile) -> Result<Source, GenError> {
+        validate(source_file)?;
+        self.gen_module(source_file, &SemiLines::default(), &[])
+    }
+
+    /// Generates the rust module for the source file with the path in the source set.
+    ///
+    /// The imported types referenced by the source file are brought into scope with `use`
+    /// statements. The source set is not validated, but an error is returned if the path is not in
+    /// the source set or a referenced type is declared by neither the file nor its imports.
+    pub fn gen_in_set(&self, source_set: &SourceSet, path: &str) -> Result<Source, GenError> {
+        let source_file: &SourceFile = match source_set.file(path) {
+            Some(source_file) => source_file,
+            None => {
+                return Err(GenError::UnknownSourceFile {
+                    path: path.to_string(),
+                })
+            }
+        };
+        let imports: Vec<&SourceFile> = source_set
+            .imported_files(path)
+            .map(|(_, imported)| imported)
+            .collect();
+        let use_imports: SemiLines = self.gen_use_imports(source_set, path, source_file)?;
+        self.gen_module(source_file, &use_imports, imports.as_slice())
+    }
+
+    fn gen_module(
+        &self,
+        source_file: &SourceFile,
+        use_imports: &SemiLines,
+        imports: &[&SourceFile],
+    ) -> Result<Source, GenError> {
+        let mut source: Source = Source::default();
+        if source_file.declarations().is_empty() {
+            return Ok(source);
+        }
+
+        source.add_statement(self.gen_use_runtime(source_file));
+        if !use_imports.is_empty() {
+            source.add_statement(use_imports.clone());
+        }
+        source.add_statement(self.gen.gen_declarations(source_file, imports)?);
+        Ok(source)
+    }
+
+    /// Generates the `use` statements for the standard library & runtime items.
+    fn gen_use_runtime(&self, source_file: &SourceFile) -> SemiLines {
+        let mut lines: SemiLines = SemiLines::default();
+        if Self::has_map_fields(source_file) {
+            lines.add_line(format!(
+                "use std::collections::{}",
+                self.gen.options().map_backing().type_name()
+            ));
+        }
+        if self.uses_io(source_file) {
+            lines.add_line("use std::io");
+        }
+        if self.uses_runtime(source_file) {
+            lines.add_line(format!("use {}::*", self.runtime_path));
+        }
+        lines
+    }
+
+    /// Checks if the generated code refers to `io` items. (the `io::Write` & `io::Read` codec
+    /// impls & the services)
+    fn uses_io(&self, source_file: &SourceFile) -> bool {
+        let encodes: bool =
+            source_file.messages().next().is_some() || source_file.variants().next().is_some();
+        let decodes: bool = encodes || source_file.enums().next().is_some();
+        source_file.services().next().is_some()
+            || (encodes && self.gen.options().implements(CodecTrait::EncodeToWrite))
+            || (decodes && self.gen.options().implements(CodecTrait::DecodeFromRead))
+    }
+
+    /// Checks if the generated code refers to runtime items. (the codec impls & the services)
+    fn uses_runtime(&self, source_file: &SourceFile) -> bool {
+        let encodes: bool =
+            source_file.messages().next().is_some() || source_file.variants().next().is_some();
+        source_file.services().next().is_some()
+            || (encodes
+                && CodecTrait::ALL
+                    .into_iter()
+                    .any(|codec_trait| self.gen.options().implements(codec_trait)))
+            || (source_file.enums().next().is_some()
+                && self.gen.options().implements(CodecTrait::DecodeFromRead))
+    }
+
+    fn has_map_fields(source_file: &SourceFile) -> bool {
+        source_file
+            .messages()
+            .flat_map(|message| message.fields())
+            .any(|field| matches!(field.type_tag(), TypeTag::Map(_, _)))
+    }
+
+    /// Generates the `use` statements for the imported types referenced by the source file.
+    ///
+    /// Types declared in the source file take precedence over imported types. Names declared by
+    /// neither are unresolved types located at the path.
+    fn gen_use_imports(
+        &self,
+        source_set: &SourceSet,
+        path: &str,
+        source_file: &SourceFile,
+    ) -> Result<SemiLines, GenError> {
+        let mut lines: SemiLines = SemiLines::default();
+        for name in source_file.referenced_names() {
+            if source_file.declaration(name).is_some() {
+                continue;
+            }
+            let imported: Option<(&str, &SourceFile)> = source_set
+                .imported_files(path)
+                .find(|(_, imported)| imported.declaration(name).is_some());
+            let (import_path, imported): (&str, &SourceFile) =
+                imported.ok_or_else(|| GenError::UnresolvedType {
+                    location: path.to_string(),
+                    name: name.to_string(),
+                })?;
+            let mod_path: Vec<String> = self
+                .gen
+                .naming()
+                .mod_path(imported, import_path)
+                .map_err(|e| e.at(import_path))?;
+            lines.add_line(format!(
+                "use {}::{}::{}",
+                self.mod_root,
+                mod_path.join("::"),
+                self.gen.naming().type_name(name)?
+            ));
+        }
+        Ok(lines)
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use code_gen::rust::Source;
+use code_gen::WithStatements;
+
+use crate::gen::rust::{GenSourceFile, RustFile, SemiLines};
+use crate::gen::GenError;
+use crate::tree::SourceSet;
+use crate::validate::validate_source_set;
+
+/// Responsible for generating rust module trees for source sets.
+#[derive(Clone, Debug, Default)]
+pub struct GenSourceSet {
+    gen: GenSourceFile,
+}
+
+impl From<GenSourceFile> for GenSourceSet {
+    fn from(gen: GenSourceFile) -> Self {
+        Self { gen }
+    }
+}
+
+impl GenSourceSet {
+    //! Gen
+
+    /// Generates the rust files for the source set.
+    ///
+    /// The source set is validated first. Each source file is generated as a module file at its
+    /// module path (`acme/billing/invoice.rs`) & each parent module is generated as a `mod.rs`
+    /// declaring its child modules. (the root module file is `mod.rs`) Files are sorted by path.
+    pub fn gen(&self, source_set: &SourceSet) -> Result<Vec<RustFile>, GenError> {
+        validate_source_set(source_set)?;
+
+        let mut modules: BTreeMap<Vec<String>, String> = BTreeMap::default();
+        let mut children: BTreeMap<Vec<String>, BTreeSet<String>> = BTreeMap::default();
+        for (path, source_file) in source_set.files() {
+            let mod_path: Vec<String> = self
+                .gen
+                .message_gen()
+                .naming()
+                .mod_path(source_file, path)
+                .map_err(|e| e.at(path))?;
+            for i in 0..mod_path.len() {
+                children
+                    .entry(mod_path[..i].to_vec())
+                    .or_default()
+                    .insert(mod_path[i].clone());
+            }
+            if modules.insert(mod_path.clone(), path.to_string()).is_some() {
+                return Err(GenError::DuplicateModule {
+                    module: mod_path.join("::"),
+                    path: path.to_string(),
+                });
+            }
+        }
+        if let Some((mod_path, path)) = modules
+            .iter()
+            .find(|(mod_path, _)| children.contains_key(*mod_path))
+        {
+            return Err(GenError::DuplicateModule {
+                module: mod_path.join("::"),
+                path: path.to_string(),
+            });
+        }
+
+        let mut files: Vec<RustFile> = Vec::default();
+        for (parent, names) in children {
+            let mut mods: SemiLines = SemiLines::default();
+            for name in names {
+                mods.add_line(format!("pub mod {}", name));
+            }
+            let mut path: Vec<String> = parent;
+            path.push("mod.rs".to_string());
+            files.push(RustFile::new(
+                path.join("/"),
+                Source::default().with_statement(mods),
+            ));
+        }
+        for (mod_path, path) in modules {
+            let source: Source = self.gen.gen_in_set(source_set, path.as_str())?;
+            files.push(RustFile::new(format!("{}.rs", mod_path.join("/")), source));
+        }
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        Ok(files)
+    }
+}
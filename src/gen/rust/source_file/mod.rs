@@ -0,0 +1,12 @@
+pub use gen_source_file::*;
+pub use gen_source_set::*;
+pub use rust_file::*;
+pub use semi_lines::*;
+
+mod gen_source_file;
+mod gen_source_set;
+mod rust_file;
+mod semi_lines;
+
+#[cfg(test)]
+mod tests;
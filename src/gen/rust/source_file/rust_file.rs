@@ -0,0 +1,37 @@
+use code_gen::rust::Source;
+
+/// A generated rust source file.
+#[derive(Clone, Debug)]
+pub struct RustFile {
+    path: String,
+    source: Source,
+}
+
+impl RustFile {
+    //! Construction
+
+    /// Creates a new rust file.
+    pub fn new<P>(path: P, source: Source) -> Self
+    where
+        P: Into<String>,
+    {
+        Self {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl RustFile {
+    //! Properties
+
+    /// Gets the path of the file relative to the root module directory. (`/` separated)
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Gets the source code.
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+}
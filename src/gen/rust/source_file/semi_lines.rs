@@ -0,0 +1,37 @@
+use code_gen::{CodeBuffer, Statement};
+
+/// Consecutive semicolon-terminated lines. (`use` & `mod` items without blank lines between)
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SemiLines {
+    lines: Vec<String>,
+}
+
+impl SemiLines {
+    //! Lines
+
+    /// Gets the lines. (without the semicolons)
+    pub fn lines(&self) -> &[String] {
+        self.lines.as_slice()
+    }
+
+    /// Checks if there are no lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Adds the line. (without the semicolon)
+    pub fn add_line<S>(&mut self, line: S)
+    where
+        S: Into<String>,
+    {
+        self.lines.push(line.into());
+    }
+}
+
+impl Statement for SemiLines {
+    fn write(&self, b: &mut CodeBuffer, level: usize) {
+        for line in &self.lines {
+            b.line(level, format!("{};", line).as_str());
+        }
+    }
+}
@@ -0,0 +1,2 @@
+mod source_files;
+mod source_sets;
@@ -0,0 +1,89 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{CodecTrait, GenMessage, GenSourceFile, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::UnsignedInt32;
+use crate::tree::{Enum, Message, MessageField, Service, SourceFile, TypeTag};
+
+#[test]
+fn source_files() -> Result<(), GenError> {
+    let counts: TypeTag = TypeTag::Map(Box::new(TypeTag::String), Box::new(UnsignedInt32.into()));
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(Enum::from("Level").with_case(("LOW", 1)))
+        .with_declaration(
+            Message::from("Stats")
+                .with_field(MessageField::from(("counts", counts)).with_field_number(1)),
+        );
+    let source: Source = GenSourceFile::default()
+        .with_runtime_path("proto_packet::runtime")
+        .gen(&source_file)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("source_files.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<(), GenError> {
+    let source: Source = GenSourceFile::default().gen(&SourceFile::default())?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    source.write(&mut b, 0);
+    assert_eq!(b.export(), "");
+
+    Ok(())
+}
+
+fn export(gen: &GenSourceFile, source_file: &SourceFile) -> Result<String, GenError> {
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    gen.gen(source_file)?.write(&mut b, 0);
+    Ok(b.export())
+}
+
+#[test]
+fn use_statements() -> Result<(), GenError> {
+    let enums: SourceFile =
+        SourceFile::default().with_declaration(Enum::from("Level").with_case(("LOW", 1)));
+    let messages: SourceFile = SourceFile::default()
+        .with_declaration(Message::from("Stats").with_field(("count", UnsignedInt32)));
+
+    let gen: GenSourceFile = GenSourceFile::default();
+    let result: String = export(&gen, &enums)?;
+    assert!(result.contains("use std::io;"));
+    assert!(result.contains("use proto_packet_core::runtime::*;"));
+
+    let options: RustGenOptions = RustGenOptions::default()
+        .with_codec_traits([CodecTrait::EncodedLen, CodecTrait::EncodeToSlice]);
+    let gen: GenSourceFile = GenMessage::default().with_options(options).into();
+    let result: String = export(&gen, &enums)?;
+    assert!(!result.contains("use std::io;"));
+    assert!(!result.contains("runtime"));
+    let result: String = export(&gen, &messages)?;
+    assert!(!result.contains("use std::io;"));
+    assert!(result.contains("use proto_packet_core::runtime::*;"));
+
+    let gen: GenSourceFile = GenMessage::default()
+        .with_options(RustGenOptions::default().with_codec_traits(Vec::<CodecTrait>::new()))
+        .into();
+    let result: String = export(&gen, &messages)?;
+    assert!(!result.contains("use std::io;"));
+    assert!(!result.contains("runtime"));
+    let services: SourceFile = messages.with_declaration(Service::from("Empty"));
+    let result: String = export(&gen, &services)?;
+    assert!(result.contains("use std::io;"));
+    assert!(result.contains("use proto_packet_core::runtime::*;"));
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{GenSourceFile, GenSourceSet, RustFile};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::{Float64, UnsignedInt64};
+use crate::tree::TypeTag::Named;
+use crate::tree::{Enum, Message, MessageField, SourceFile, SourceSet};
+use crate::validate::Diagnostic;
+
+#[test]
+fn source_sets() -> Result<(), GenError> {
+    let common: SourceFile = SourceFile::default()
+        .with_package("acme.common")
+        .with_declaration(Enum::from("Currency").with_case(("EUR", 1)))
+        .with_declaration(Message::from("Unused"));
+    let billing: SourceFile = SourceFile::default()
+        .with_package("acme.billing")
+        .with_import("common.pp")
+        .with_declaration(
+            Message::from("Invoice")
+                .with_field(("total", UnsignedInt64))
+                .with_field(
+                    MessageField::from(("currency", Named("Currency".to_string())))
+                        .with_field_number(1),
+                ),
+        );
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("common.pp", common)
+        .with_file("billing.pp", billing)
+        .with_file("empty.pp", SourceFile::default());
+    let gen: GenSourceSet = GenSourceFile::default()
+        .with_runtime_path("proto_packet::runtime")
+        .with_mod_root("crate::schema")
+        .into();
+    let files: Vec<RustFile> = gen.gen(&source_set)?;
+
+    let paths: Vec<&str> = files.iter().map(|file| file.path()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "acme/billing/billing.rs",
+            "acme/billing/mod.rs",
+            "acme/common/common.rs",
+            "acme/common/mod.rs",
+            "acme/mod.rs",
+            "empty.rs",
+            "mod.rs",
+        ]
+    );
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    for file in files
+        .iter()
+        .filter(|file| file.path() != "acme/common/common.rs")
+    {
+        b.line(0, format!("// {}", file.path()).as_str());
+        file.source().write(&mut b, 0);
+    }
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("source_sets.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
+
+#[test]
+fn duplicate_module() {
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("a/common.pp", SourceFile::default())
+        .with_file("b/common.pp", SourceFile::default());
+    let result: Option<GenError> = GenSourceSet::default().gen(&source_set).err();
+    assert_eq!(
+        result,
+        Some(GenError::DuplicateModule {
+            module: "common".to_string(),
+            path: "b/common.pp".to_string(),
+        })
+    );
+
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("acme.pp", SourceFile::default())
+        .with_file("billing.pp", SourceFile::default().with_package("acme"));
+    let result: Option<GenError> = GenSourceSet::default().gen(&source_set).err();
+    assert_eq!(
+        result,
+        Some(GenError::DuplicateModule {
+            module: "acme".to_string(),
+            path: "acme.pp".to_string(),
+        })
+    );
+}
+
+#[test]
+fn unknown_source_file() {
+    let source_set: SourceSet = SourceSet::default().with_file("common.pp", SourceFile::default());
+    let result: Option<GenError> = GenSourceFile::default()
+        .gen_in_set(&source_set, "missing.pp")
+        .err();
+    assert_eq!(
+        result,
+        Some(GenError::UnknownSourceFile {
+            path: "missing.pp".to_string(),
+        })
+    );
+}
+
+#[test]
+fn unresolved_import() {
+    let billing: SourceFile = SourceFile::default()
+        .with_import("common.pp")
+        .with_declaration(Message::from("Invoice").with_field(
+            MessageField::from(("total", Named("Money".to_string()))).with_field_number(1),
+        ));
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("common.pp", SourceFile::default())
+        .with_file("billing.pp", billing);
+    let result: Option<GenError> = GenSourceFile::default()
+        .gen_in_set(&source_set, "billing.pp")
+        .err();
+    assert_eq!(
+        result,
+        Some(GenError::UnresolvedType {
+            location: "billing.pp".to_string(),
+            name: "Money".to_string(),
+        })
+    );
+}
+
+#[test]
+fn ambiguous_import() -> Result<(), GenError> {
+    let common: SourceFile = SourceFile::default()
+        .with_package("common")
+        .with_declaration(Message::from("Money").with_field(("amount", Float64)));
+    let legacy: SourceFile = SourceFile::default()
+        .with_package("legacy")
+        .with_declaration(Message::from("Money"));
+    let invoice: Message = Message::from("Invoice")
+        .with_field(MessageField::from(("total", Named("Money".to_string()))).with_field_number(1));
+    let billing: SourceFile = SourceFile::default()
+        .with_import("common.pp")
+        .with_import("legacy.pp")
+        .with_declaration(invoice.clone());
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("common.pp", common)
+        .with_file("legacy.pp", legacy)
+        .with_file("billing.pp", billing);
+
+    let result: Option<GenError> = GenSourceSet::default().gen(&source_set).err();
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new(
+            "billing.pp",
+            "name `Money` is declared by more than one import (`common.pp`, `legacy.pp`)",
+        )]
+        .into(),
+    );
+    assert_eq!(result, Some(expected));
+
+    // unvalidated generation resolves the name to the first import, like the `use` statements
+    let billing: SourceFile = SourceFile::default()
+        .with_import("common.pp")
+        .with_declaration(invoice);
+    let first_only: SourceSet = source_set.clone().with_file("billing.pp", billing);
+    let gen: GenSourceFile = GenSourceFile::default();
+    let mut result: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    gen.gen_in_set(&source_set, "billing.pp")?
+        .write(&mut result, 0);
+    let mut expected: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    gen.gen_in_set(&first_only, "billing.pp")?
+        .write(&mut expected, 0);
+    assert_eq!(result.export(), expected.export());
+
+    Ok(())
+}
@@ -1,12 +1,85 @@
+use std::collections::BTreeMap;
+
 use code_gen::rust::{PrimitiveType as RustPrimitive, TypeTag as RustType};
+use code_gen::WithName;
 
+use crate::gen::rust::{MapBacking, Naming};
 use crate::gen::GenError;
-use crate::tree::{Message, PrimitiveType, TypeTag, WithTypeTag};
+use crate::tree::{
+    Enum, Message, MessageField, PrimitiveType, SourceFile, TypeTag, Variant, WithTypeTag,
+};
 
 /// Responsible for type conversions & utilities.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
 pub struct Typing {
-    _nothing: (),
+    naming: Naming,
+    map_backing: MapBacking,
+    messages: BTreeMap<String, Message>,
+    enums: BTreeMap<String, Enum>,
+    variants: BTreeMap<String, Variant>,
+}
+
+impl Typing {
+    //! Options
+
+    /// Sets the rust type backing map fields.
+    pub fn with_map_backing(mut self, map_backing: MapBacking) -> Self {
+        self.map_backing = map_backing;
+        self
+    }
+
+    /// Sets the naming. (used for the names of named types)
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Gets the rust type backing map fields.
+    pub fn map_backing(&self) -> MapBacking {
+        self.map_backing
+    }
+}
+
+impl Typing {
+    //! Declarations
+
+    /// Adds the message, enum & variant declarations of the source file for resolving named types.
+    ///
+    /// The declarations replace previously added declarations with the same name.
+    pub fn with_source_file(mut self, source_file: &SourceFile) -> Self {
+        for message in source_file.messages() {
+            self.messages
+                .insert(message.name().to_string(), message.clone());
+        }
+        for e in source_file.enums() {
+            self.enums.insert(e.name().to_string(), e.clone());
+        }
+        for variant in source_file.variants() {
+            self.variants
+                .insert(variant.name().to_string(), variant.clone());
+        }
+        self
+    }
+
+    /// Resolves the enum declaration for the named type. (`None` if the name is not an enum)
+    pub fn enumeration(&self, name: &str) -> Option<&Enum> {
+        self.enums.get(name)
+    }
+
+    /// Resolves the variant declaration for the named type. (`None` if the name is not a variant)
+    pub fn variant(&self, name: &str) -> Option<&Variant> {
+        self.variants.get(name)
+    }
+
+    /// Resolves the message declaration for the named type.
+    pub fn message(&self, name: &str) -> Result<&Message, GenError> {
+        self.messages
+            .get(name)
+            .ok_or_else(|| GenError::UnresolvedType {
+                location: String::default(),
+                name: name.to_string(),
+            })
+    }
 }
 
 impl Typing {
@@ -20,11 +93,175 @@ impl Typing {
                 PrimitiveType::UnsignedInt16 => true,
                 PrimitiveType::UnsignedInt32 => true,
                 PrimitiveType::UnsignedInt64 => true,
+                PrimitiveType::SignedInt8 => true,
+                PrimitiveType::SignedInt16 => true,
+                PrimitiveType::SignedInt32 => true,
+                PrimitiveType::SignedInt64 => true,
+                PrimitiveType::Float32 => true,
+                PrimitiveType::Float64 => true,
+                PrimitiveType::Boolean => true,
             },
+            TypeTag::String => false,
+            TypeTag::Bytes => false,
+            TypeTag::Named(name) => {
+                if self.enumeration(name).is_some() {
+                    true
+                } else if self.variant(name).is_some() {
+                    false
+                } else {
+                    self.message(name)?;
+                    false
+                }
+            }
+            TypeTag::List(_) => false,
+            TypeTag::Map(_, _) => false,
         };
         Ok(is_copy)
     }
 
+    /// Checks if the declared type is converted to a rust type implementing `Eq`, `Ord` & `Hash`.
+    pub fn is_eq(&self, declared_type: &TypeTag) -> Result<bool, GenError> {
+        self.is_eq_in(declared_type, &mut Vec::default())
+    }
+
+    /// Checks if the declared type is converted to a rust type implementing `Eq`, `Ord` & `Hash`.
+    ///
+    /// The declarations being checked are assumed to implement the traits. (recursive types
+    /// implement them when their other members do)
+    fn is_eq_in(
+        &self,
+        declared_type: &TypeTag,
+        checking: &mut Vec<String>,
+    ) -> Result<bool, GenError> {
+        let is_eq: bool = match declared_type {
+            TypeTag::Primitive(primitive) => match primitive {
+                PrimitiveType::UnsignedInt8 => true,
+                PrimitiveType::UnsignedInt16 => true,
+                PrimitiveType::UnsignedInt32 => true,
+                PrimitiveType::UnsignedInt64 => true,
+                PrimitiveType::SignedInt8 => true,
+                PrimitiveType::SignedInt16 => true,
+                PrimitiveType::SignedInt32 => true,
+                PrimitiveType::SignedInt64 => true,
+                PrimitiveType::Float32 => false,
+                PrimitiveType::Float64 => false,
+                PrimitiveType::Boolean => true,
+            },
+            TypeTag::String => true,
+            TypeTag::Bytes => true,
+            TypeTag::Named(name) if checking.contains(name) => true,
+            TypeTag::Named(name) if self.enumeration(name).is_some() => true,
+            TypeTag::Named(name) => {
+                checking.push(name.clone());
+                let is_eq: bool = match self.variant(name) {
+                    Some(variant) => self.cases_eq_in(variant, checking)?,
+                    None => self.fields_eq_in(self.message(name)?, checking)?,
+                };
+                checking.pop();
+                is_eq
+            }
+            TypeTag::List(element) => self.is_eq_in(element, checking)?,
+            TypeTag::Map(key, value) => {
+                self.map_backing.is_ordered()
+                    && self.is_eq_in(key, checking)?
+                    && self.is_eq_in(value, checking)?
+            }
+        };
+        Ok(is_eq)
+    }
+
+    /// Checks if the declared type is converted to a rust type implementing `PartialOrd`.
+    pub fn is_partial_ord(&self, declared_type: &TypeTag) -> Result<bool, GenError> {
+        self.is_partial_ord_in(declared_type, &mut Vec::default())
+    }
+
+    /// Checks if the declared type is converted to a rust type implementing `PartialOrd`.
+    ///
+    /// The declarations being checked are assumed to implement the trait. (recursive types
+    /// implement it when their other members do)
+    fn is_partial_ord_in(
+        &self,
+        declared_type: &TypeTag,
+        checking: &mut Vec<String>,
+    ) -> Result<bool, GenError> {
+        let is_partial_ord: bool = match declared_type {
+            TypeTag::Primitive(_) => true,
+            TypeTag::String => true,
+            TypeTag::Bytes => true,
+            TypeTag::Named(name) if checking.contains(name) => true,
+            TypeTag::Named(name) if self.enumeration(name).is_some() => true,
+            TypeTag::Named(name) => {
+                checking.push(name.clone());
+                let is_partial_ord: bool = match self.variant(name) {
+                    Some(variant) => self.cases_partial_ord_in(variant, checking)?,
+                    None => self.fields_partial_ord_in(self.message(name)?, checking)?,
+                };
+                checking.pop();
+                is_partial_ord
+            }
+            TypeTag::List(element) => self.is_partial_ord_in(element, checking)?,
+            TypeTag::Map(key, value) => {
+                self.map_backing.is_ordered()
+                    && self.is_partial_ord_in(key, checking)?
+                    && self.is_partial_ord_in(value, checking)?
+            }
+        };
+        Ok(is_partial_ord)
+    }
+
+    /// Checks if the declared type supports the fixed-width encoding option.
+    ///
+    /// Floating point types are always fixed-width so the option does not apply to them.
+    pub fn has_fixed_encoding(&self, declared_type: &TypeTag) -> bool {
+        match declared_type {
+            TypeTag::Primitive(primitive) => match primitive {
+                PrimitiveType::UnsignedInt8 => false,
+                PrimitiveType::UnsignedInt16 => true,
+                PrimitiveType::UnsignedInt32 => true,
+                PrimitiveType::UnsignedInt64 => true,
+                PrimitiveType::SignedInt8 => false,
+                PrimitiveType::SignedInt16 => true,
+                PrimitiveType::SignedInt32 => true,
+                PrimitiveType::SignedInt64 => true,
+                PrimitiveType::Float32 => false,
+                PrimitiveType::Float64 => false,
+                PrimitiveType::Boolean => false,
+            },
+            TypeTag::String => false,
+            TypeTag::Bytes => false,
+            TypeTag::Named(_) => false,
+            TypeTag::List(element) => self.has_fixed_encoding(element),
+            TypeTag::Map(_, _) => false,
+        }
+    }
+
+    /// Checks if the declared type is a list or a map.
+    pub fn is_collection(declared_type: &TypeTag) -> bool {
+        matches!(declared_type, TypeTag::List(_) | TypeTag::Map(_, _))
+    }
+
+    /// Checks if the declared type supports the packed encoding option.
+    ///
+    /// Only lists of primitive or enum elements can be packed.
+    pub fn has_packed_encoding(&self, declared_type: &TypeTag) -> bool {
+        match declared_type {
+            TypeTag::List(element) => {
+                matches!(**element, TypeTag::Primitive(_)) || self.is_enum(element)
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks if the declared type is a named enum type.
+    pub fn is_enum(&self, declared_type: &TypeTag) -> bool {
+        matches!(declared_type, TypeTag::Named(name) if self.enumeration(name).is_some())
+    }
+
+    /// Checks if the declared type is a named variant type.
+    pub fn is_variant(&self, declared_type: &TypeTag) -> bool {
+        matches!(declared_type, TypeTag::Named(name) if self.variant(name).is_some())
+    }
+
     /// Checks if all the fields in the message convert to a rust `Copy` type.
     pub fn all_copy(&self, message: &Message) -> Result<bool, GenError> {
         for field in message.fields() {
@@ -34,18 +271,157 @@ impl Typing {
         }
         Ok(true)
     }
+
+    /// Checks if all the fields in the message convert to rust types implementing `PartialOrd`.
+    pub fn all_partial_ord(&self, message: &Message) -> Result<bool, GenError> {
+        self.fields_partial_ord_in(message, &mut vec![message.name().to_string()])
+    }
+
+    fn fields_partial_ord_in(
+        &self,
+        message: &Message,
+        checking: &mut Vec<String>,
+    ) -> Result<bool, GenError> {
+        for field in message.fields() {
+            if !self.is_partial_ord_in(field.type_tag(), checking)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Checks if all the fields in the message convert to rust types implementing `Eq`, `Ord` &
+    /// `Hash`.
+    pub fn all_eq(&self, message: &Message) -> Result<bool, GenError> {
+        self.fields_eq_in(message, &mut vec![message.name().to_string()])
+    }
+
+    fn fields_eq_in(
+        &self,
+        message: &Message,
+        checking: &mut Vec<String>,
+    ) -> Result<bool, GenError> {
+        for field in message.fields() {
+            if !self.is_eq_in(field.type_tag(), checking)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl Typing {
+    //! Variant Properties
+
+    /// Checks if all the cases in the variant convert to rust types implementing `PartialOrd`.
+    pub fn all_cases_partial_ord(&self, variant: &Variant) -> Result<bool, GenError> {
+        self.cases_partial_ord_in(variant, &mut vec![variant.name().to_string()])
+    }
+
+    fn cases_partial_ord_in(
+        &self,
+        variant: &Variant,
+        checking: &mut Vec<String>,
+    ) -> Result<bool, GenError> {
+        for case in variant.cases() {
+            if !self.is_partial_ord_in(case.type_tag(), checking)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Checks if all the cases in the variant convert to rust types implementing `Eq`, `Ord` &
+    /// `Hash`.
+    pub fn all_cases_eq(&self, variant: &Variant) -> Result<bool, GenError> {
+        self.cases_eq_in(variant, &mut vec![variant.name().to_string()])
+    }
+
+    fn cases_eq_in(&self, variant: &Variant, checking: &mut Vec<String>) -> Result<bool, GenError> {
+        for case in variant.cases() {
+            if !self.is_eq_in(case.type_tag(), checking)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 impl Typing {
     //! Fields
 
+    /// Gets the struct field type for the message field.
+    ///
+    /// Optional fields are wrapped in `Option`. Lists & maps are never wrapped, empty is absent.
+    pub fn message_field_type(&self, field: &MessageField) -> Result<RustType, GenError> {
+        let tag: RustType = self.field_type(field.type_tag())?;
+        if field.is_required() || Self::is_collection(field.type_tag()) {
+            Ok(tag)
+        } else {
+            Ok(tag.to_option())
+        }
+    }
+
     /// Gets the field type for the declared type.
     pub fn field_type(&self, declared_type: &TypeTag) -> Result<RustType, GenError> {
         match declared_type {
             TypeTag::Primitive(primitive) => self.primitive_field_type(*primitive),
+            _ => Ok(RustType::from(self.type_name(declared_type)?)),
         }
     }
 
+    /// Gets the borrowed field type for the declared type. (`&str` for `String`, etc.)
+    ///
+    /// Returns `None` for types that are returned by value.
+    pub fn borrowed_field_type(
+        &self,
+        declared_type: &TypeTag,
+    ) -> Result<Option<RustType>, GenError> {
+        Ok(self.borrowed_type_name(declared_type)?.map(RustType::from))
+    }
+
+    /// Gets the rust type name for the declared type.
+    pub fn type_name(&self, declared_type: &TypeTag) -> Result<String, GenError> {
+        match declared_type {
+            // the declared primitive names match the rust primitive names
+            TypeTag::Primitive(primitive) => Ok(primitive.to_string()),
+            TypeTag::String => Ok("String".to_string()),
+            TypeTag::Bytes => Ok("Vec<u8>".to_string()),
+            TypeTag::Named(name) => {
+                if let Some(e) = self.enumeration(name) {
+                    self.naming.type_name(e.name())
+                } else if let Some(variant) = self.variant(name) {
+                    self.naming.type_name(variant.name())
+                } else {
+                    self.naming.type_name(self.message(name)?.name())
+                }
+            }
+            TypeTag::List(element) => Ok(format!("Vec<{}>", self.type_name(element)?)),
+            TypeTag::Map(key, value) => Ok(format!(
+                "{}<{}, {}>",
+                self.map_backing.type_name(),
+                self.type_name(key)?,
+                self.type_name(value)?
+            )),
+        }
+    }
+
+    /// Gets the borrowed rust type name for the declared type. (`&str` for `String`, etc.)
+    ///
+    /// Returns `None` for types that are returned by value.
+    pub fn borrowed_type_name(&self, declared_type: &TypeTag) -> Result<Option<String>, GenError> {
+        let name: Option<String> = match declared_type {
+            TypeTag::Primitive(_) => None,
+            TypeTag::String => Some("&str".to_string()),
+            TypeTag::Bytes => Some("&[u8]".to_string()),
+            TypeTag::Named(name) if self.enumeration(name).is_some() => None,
+            TypeTag::Named(_) => Some(format!("&{}", self.type_name(declared_type)?)),
+            TypeTag::List(element) => Some(format!("&[{}]", self.type_name(element)?)),
+            TypeTag::Map(_, _) => Some(format!("&{}", self.type_name(declared_type)?)),
+        };
+        Ok(name)
+    }
+
     /// Gets the field type for the declared primitive type.
     pub fn primitive_field_type(
         &self,
@@ -56,6 +432,13 @@ impl Typing {
             PrimitiveType::UnsignedInt16 => RustPrimitive::UnsignedInt16.to_type_tag(),
             PrimitiveType::UnsignedInt32 => RustPrimitive::UnsignedInt32.to_type_tag(),
             PrimitiveType::UnsignedInt64 => RustPrimitive::UnsignedInt64.to_type_tag(),
+            PrimitiveType::SignedInt8 => RustPrimitive::SignedInt8.to_type_tag(),
+            PrimitiveType::SignedInt16 => RustPrimitive::SignedInt16.to_type_tag(),
+            PrimitiveType::SignedInt32 => RustPrimitive::SignedInt32.to_type_tag(),
+            PrimitiveType::SignedInt64 => RustPrimitive::SignedInt64.to_type_tag(),
+            PrimitiveType::Float32 => RustPrimitive::Float32.to_type_tag(),
+            PrimitiveType::Float64 => RustPrimitive::Float64.to_type_tag(),
+            PrimitiveType::Boolean => RustPrimitive::Boolean.to_type_tag(),
         };
         Ok(tag)
     }
@@ -0,0 +1,19 @@
+/// The handling of unknown fields when decoding messages.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub enum UnknownFields {
+    /// Unknown fields are skipped.
+    #[default]
+    Skip,
+
+    /// Unknown fields are kept in an `unknown_fields` member & re-emitted when encoding.
+    Preserve,
+}
+
+impl UnknownFields {
+    //! Properties
+
+    /// Checks if unknown fields are preserved.
+    pub const fn is_preserve(&self) -> bool {
+        matches!(self, Self::Preserve)
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashSet;
+
+use code_gen::rust::Access::Public;
+use code_gen::rust::{
+    Enum as RustEnum, EnumVariant, Function, ImplBlock, Match, MatchCase,
+    PrimitiveType as RustPrimitive, Receiver, Reference, Signature, Source, TypeTag as RustType,
+    WhileLet, WithAccess, WithAttributes, WithComments as WithRustComments, WithDerives,
+    WithFnGenerics, WithFunctions, WithReceiver, WithResult, WithUnsafeFlag, WithVarParams,
+    WithVariants,
+};
+use code_gen::{Semi, WithName, WithStatements};
+
+use crate::gen::rust::{
+    CodecTrait, GenMessageDecode, GenMessageEncode, Naming, RustExp, RustGenOptions, Typing,
+    UNRECOGNIZED_CASE_NAME,
+};
+use crate::gen::GenError;
+use crate::tree::{Variant, WithTypeTag};
+use crate::validate::validate_variant;
+
+/// Responsible for generating code for variant types.
+#[derive(Copy, Clone, Debug)]
+pub struct GenVariant<'a> {
+    naming: &'a Naming,
+    typing: &'a Typing,
+    options: &'a RustGenOptions,
+}
+
+impl<'a> GenVariant<'a> {
+    //! Construction
+
+    /// Creates a new gen variant.
+    pub const fn new(naming: &'a Naming, typing: &'a Typing, options: &'a RustGenOptions) -> Self {
+        Self {
+            naming,
+            typing,
+            options,
+        }
+    }
+}
+
+impl<'a> GenVariant<'a> {
+    //! Gen
+
+    /// Generates the source code for the variant.
+    ///
+    /// The variant is validated on its own. (named types are resolved by the typing)
+    pub fn gen(&self, variant: &Variant) -> Result<Source, GenError> {
+        self.gen_source(variant).map_err(|e| e.at(variant.name()))
+    }
+
+    /// Generates the source code for the variant without locating the errors.
+    fn gen_source(&self, variant: &Variant) -> Result<Source, GenError> {
+        validate_variant(variant)?;
+        self.check_cases(variant)?;
+
+        let mut source: Source = Source::default();
+        source.add_statement(self.gen_enum(variant)?);
+        source.add_statement(self.gen_impl_default(variant)?);
+        if self.options.implements(CodecTrait::EncodedLen) {
+            source.add_statement(self.gen_impl_encoded_len(variant)?);
+        }
+        if self.options.implements(CodecTrait::EncodeToSlice) {
+            source.add_statement(self.gen_impl_encode_to_slice(variant)?);
+        }
+        if self.options.implements(CodecTrait::EncodeToWrite) {
+            source.add_statement(self.gen_impl_encode_to_write(variant)?);
+        }
+        if self
+            .options
+            .implements(CodecTrait::DecodeFromReadLengthPrefixed)
+        {
+            source.add_statement(self.gen_impl_decode_from_read_length_prefixed(variant)?);
+        }
+        if self.options.implements(CodecTrait::DecodeFromRead) {
+            source.add_statement(self.gen_impl_decode_from_read(variant)?);
+        }
+        Ok(source)
+    }
+
+    /// Checks the variant cases for colliding names & unsupported types. (duplicate names &
+    /// numbers are reported by the validator)
+    fn check_cases(&self, variant: &Variant) -> Result<(), GenError> {
+        let mut rust_names: HashSet<String> = HashSet::default();
+        rust_names.insert(UNRECOGNIZED_CASE_NAME.to_string());
+        for case in variant.cases() {
+            if Typing::is_collection(case.type_tag()) {
+                return Err(GenError::UnsupportedType {
+                    message: variant.name().to_string(),
+                    field: case.name().to_string(),
+                    type_tag: case.type_tag().clone(),
+                });
+            }
+            Naming::check_collision(
+                &mut rust_names,
+                variant.name(),
+                case.name(),
+                self.naming
+                    .case_name(variant.name(), case.name())
+                    .map_err(|e| e.at(format!("{}.{}", variant.name(), case.name())))?,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> GenVariant<'a> {
+    //! Declaration
+
+    /// Generates the enum declaration for the variant.
+    fn gen_enum(&self, variant: &Variant) -> Result<RustEnum, GenError> {
+        let mut e: RustEnum = self.naming.type_name(variant.name())?.into();
+        e.set_access(Public);
+
+        for line in variant.to_string().lines() {
+            e.add_comment(line);
+        }
+        self.gen_derives(&mut e, variant)?;
+        for attribute in self.options.attributes(variant.name()) {
+            e.add_attribute(attribute.as_str());
+        }
+
+        for case in variant.cases() {
+            let case_name: String = self.naming.case_name(variant.name(), case.name())?;
+            e.add_variant(
+                EnumVariant::from(case_name).with_field(self.typing.field_type(case.type_tag())?),
+            );
+        }
+        let mut unrecognized: EnumVariant =
+            EnumVariant::from(UNRECOGNIZED_CASE_NAME).with_field("Vec<u8>");
+        unrecognized.add_comment(
+            "The encoded field of a case not declared by this version of the variant.",
+        );
+        e.add_variant(unrecognized);
+
+        Ok(e)
+    }
+
+    /// Generates the derives for the enum declaration.
+    ///
+    /// The derives are inferred from the case types unless they are set in the options. (except
+    /// `Default`, which is implemented as the unrecognized empty case)
+    fn gen_derives(&self, e: &mut RustEnum, variant: &Variant) -> Result<(), GenError> {
+        if let Some(derives) = self.options.derives() {
+            for derive in derives.iter().filter(|derive| *derive != "Default") {
+                e.add_derive(derive.as_str());
+            }
+            return Ok(());
+        }
+
+        e.add_derive("Clone");
+        if self.typing.all_cases_eq(variant)? {
+            e.add_derive("Ord");
+            e.add_derive("PartialOrd");
+            e.add_derive("Eq");
+            e.add_derive("PartialEq");
+            e.add_derive("Hash");
+        } else if self.typing.all_cases_partial_ord(variant)? {
+            e.add_derive("PartialOrd");
+            e.add_derive("PartialEq");
+        } else {
+            e.add_derive("PartialEq");
+        }
+        e.add_derive("Debug");
+        Ok(())
+    }
+}
+
+impl<'a> GenVariant<'a> {
+    //! Default
+
+    /// Generates the impl block for the `Default` trait. (no case, encoded as empty)
+    fn gen_impl_default(&self, variant: &Variant) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(variant.name())?.into();
+        block.set_for_trait("Default");
+
+        let signature: Signature = Signature::from("default").with_result("Self");
+        let mut function: Function = Function::from(signature);
+        function.add_statement(RustExp::call(
+            format!("Self::{}", UNRECOGNIZED_CASE_NAME),
+            [RustExp::call("Vec::default", [])],
+        ));
+        block.add_function(function);
+
+        Ok(block)
+    }
+}
+
+impl<'a> GenVariant<'a> {
+    //! Encoding
+
+    /// Generates the impl block for implementing `EncodedLen`.
+    fn gen_impl_encoded_len(&self, variant: &Variant) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(variant.name())?.into();
+        block.set_for_trait("EncodedLen");
+
+        let signature: Signature = Signature::from("encoded_len")
+            .with_receiver(Receiver::Borrowed)
+            .with_result(RustPrimitive::UnsignedIntSize);
+        let mut function: Function = Function::from(signature);
+        function
+            .add_statement(self.gen_encode_match(variant, |exp| exp.method("encoded_len", []))?);
+        block.add_function(function);
+
+        Ok(block)
+    }
+
+    /// Generates the impl block for implementing `EncodeToSlice`.
+    fn gen_impl_encode_to_slice(&self, variant: &Variant) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(variant.name())?.into();
+        block.set_for_trait("EncodeToSlice");
+
+        let signature: Signature = Signature::from("encode_to_slice_unchecked")
+            .with_receiver(Receiver::Borrowed)
+            .with_param((
+                "target",
+                RustPrimitive::UnsignedInt8
+                    .to_type_tag()
+                    .to_slice()
+                    .to_reference(Reference::MUT),
+            ))
+            .with_result(RustPrimitive::UnsignedIntSize);
+        let mut function: Function = Function::from(signature).with_unsafe();
+        function.add_statement(self.gen_encode_match(variant, |exp| {
+            exp.method("encode_to_slice_unchecked", [RustExp::from("target")])
+        })?);
+        block.add_function(function);
+
+        Ok(block)
+    }
+
+    /// Generates the impl block for implementing `EncodeToWrite`.
+    fn gen_impl_encode_to_write(&self, variant: &Variant) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(variant.name())?.into();
+        block.set_for_trait("EncodeToWrite");
+
+        let result_type: RustType = RustType::Named("Result".to_string())
+            .with_generic("usize")
+            .with_generic("io::Error");
+        let signature: Signature = Signature::from("encode_to_write")
+            .with_receiver(Receiver::Borrowed)
+            .with_generic(("W", "io::Write"))
+            .with_param((
+                "w",
+                RustType::Named("W".to_string()).to_reference(Reference::MUT),
+            ))
+            .with_result(result_type);
+        let mut function: Function = Function::from(signature);
+        function.add_statement(self.gen_encode_match(variant, |exp| {
+            exp.method("encode_to_write", [RustExp::from("w")])
+        })?);
+        block.add_function(function);
+
+        Ok(block)
+    }
+
+    /// Generates the match statement applying the encoding call to the set case.
+    ///
+    /// The set case is encoded as a single tagged field with the case number as the field number.
+    /// Unrecognized cases are written back as the encoded field they were decoded from.
+    fn gen_encode_match<F>(&self, variant: &Variant, call: F) -> Result<Match, GenError>
+    where
+        F: Fn(RustExp) -> RustExp,
+    {
+        let gen: GenMessageEncode = GenMessageEncode::new(self.naming, self.typing, self.options);
+
+        let mut match_statement: Match = Match::from("self");
+        for case in variant.cases() {
+            let case_name: String = self.naming.case_name(variant.name(), case.name())?;
+            let exp: RustExp = gen.gen_tagged_exp(case.type_tag(), case.number(), "value");
+            match_statement.add_match_case(
+                MatchCase::from(format!("Self::{}(value)", case_name)).with_statement(call(exp)),
+            );
+        }
+        let exp: RustExp = RustExp::call("RawBytes::new", [RustExp::from("bytes")]);
+        match_statement.add_match_case(
+            MatchCase::from(format!("Self::{}(bytes)", UNRECOGNIZED_CASE_NAME))
+                .with_statement(call(exp)),
+        );
+        Ok(match_statement)
+    }
+}
+
+impl<'a> GenVariant<'a> {
+    //! Decoding
+
+    /// Generates the impl block for implementing the `DecodeFromReadLengthPrefixed` trait.
+    fn gen_impl_decode_from_read_length_prefixed(
+        &self,
+        variant: &Variant,
+    ) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(variant.name())?.into();
+        block.set_for_trait("DecodeFromReadLengthPrefixed");
+        Ok(block)
+    }
+
+    /// Generates the impl block for implementing the `DecodeFromRead` trait.
+    ///
+    /// The last decoded case is set. Undeclared case numbers are decoded as unrecognized cases.
+    fn gen_impl_decode_from_read(&self, variant: &Variant) -> Result<ImplBlock, GenError> {
+        let mut block: ImplBlock = self.naming.type_name(variant.name())?.into();
+        block.set_for_trait("DecodeFromRead");
+
+        let result_type: RustType = RustType::from("Result")
+            .with_generic("Self")
+            .with_generic("io::Error");
+        let signature: Signature = Signature::from("decode_from_read")
+            .with_generic(("R", "io::Read"))
+            .with_param(("r", RustType::from("R").to_reference(Reference::MUT)))
+            .with_result(result_type);
+        let mut function: Function = Function::from(signature);
+        function.add_statement(Semi::from(GenMessageDecode::gen_result_binding(true)));
+
+        let mut while_let: WhileLet =
+            WhileLet::new("Some", "header", GenMessageDecode::gen_header_exp());
+        while_let.add_statement(self.gen_decode_match(variant)?);
+        function.add_statement(while_let);
+
+        function.add_statement(RustExp::call("Ok", [RustExp::from("result")]));
+        block.add_function(function);
+
+        Ok(block)
+    }
+
+    /// Generates the match statement decoding the case with the field number.
+    fn gen_decode_match(&self, variant: &Variant) -> Result<Match, GenError> {
+        let gen: GenMessageDecode = GenMessageDecode::new(self.naming, self.typing, self.options);
+
+        let mut match_statement: Match = Match::from(GenMessageDecode::gen_field_number_exp());
+        for case in variant.cases() {
+            let case_name: String = self.naming.case_name(variant.name(), case.name())?;
+            let read_fn: RustExp = gen.gen_read_fn(case.type_tag(), false)?;
+            let value: RustExp = RustExp::call(read_fn, [RustExp::from("r")]).to_try();
+            let result_set: RustExp = RustExp::assign(
+                "result",
+                RustExp::call(format!("Self::{}", case_name), [value]),
+            );
+            match_statement.add_match_case(
+                MatchCase::from(case.number().to_string()).with_statement(Semi::from(result_set)),
+            );
+        }
+        let bytes: RustExp =
+            RustExp::let_binding(true, "bytes", "Vec<u8>", RustExp::call("Vec::default", []));
+        let copy_field: RustExp = GenMessageDecode::gen_copy_field_exp(RustExp::from("bytes"));
+        let result_set: RustExp = RustExp::assign(
+            "result",
+            RustExp::call(
+                format!("Self::{}", UNRECOGNIZED_CASE_NAME),
+                [RustExp::from("bytes")],
+            ),
+        );
+        match_statement.add_match_case(
+            MatchCase::from("_")
+                .with_statement(Semi::from(bytes))
+                .with_statement(Semi::from(copy_field))
+                .with_statement(Semi::from(result_set)),
+        );
+        Ok(match_statement)
+    }
+}
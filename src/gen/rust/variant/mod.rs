@@ -0,0 +1,6 @@
+pub use gen_variant::*;
+
+mod gen_variant;
+
+#[cfg(test)]
+mod tests;
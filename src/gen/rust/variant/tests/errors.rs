@@ -0,0 +1,60 @@
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{List, Named};
+use crate::tree::{Message, MessageField, SourceFile, Variant};
+use crate::validate::{Diagnostic, MAX_FIELD_NUMBER};
+
+#[test]
+fn duplicate_case_number() {
+    let variant: Variant = Variant::from("Dup")
+        .with_case(("one", UnsignedInt8, 1))
+        .with_case(("two", UnsignedInt16, 1));
+    let result: Result<_, GenError> = GenMessage::default().gen_variant(&variant);
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new("Dup.two", "duplicate field number 1")].into(),
+    );
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn unsupported_list_case() {
+    let variant: Variant =
+        Variant::from("Invalid").with_case(("one", List(Box::new(UnsignedInt8.into())), 1));
+    let result: Result<_, GenError> = GenMessage::default().gen_variant(&variant);
+    let expected: GenError = GenError::UnsupportedType {
+        message: "Invalid".to_string(),
+        field: "one".to_string(),
+        type_tag: List(Box::new(UnsignedInt8.into())),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn recursive_variant() {
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(Variant::from("Node").with_case(("leaf", Named("Leaf".to_string()), 1)))
+        .with_declaration(Message::from("Leaf").with_field(
+            MessageField::from(("node", Named("Node".to_string()))).with_field_number(1),
+        ));
+    let result: Result<_, GenError> = GenMessage::default().gen_declarations(&source_file, &[]);
+    let expected: GenError = GenError::RecursiveMessage {
+        message: "Node".to_string(),
+    };
+    assert_eq!(result.err(), Some(expected));
+}
+
+#[test]
+fn case_number_out_of_range() {
+    let variant: Variant =
+        Variant::from("Shape").with_case(("side", Float64, MAX_FIELD_NUMBER + 1));
+    let result: Result<_, GenError> = GenMessage::default().gen_variant(&variant);
+    let expected: GenError = GenError::InvalidSchema(
+        vec![Diagnostic::new(
+            "Shape.side",
+            "field number 536870912 is out of range (1..=536870911)",
+        )]
+        .into(),
+    );
+    assert_eq!(result.err(), Some(expected));
+}
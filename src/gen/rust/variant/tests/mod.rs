@@ -0,0 +1,3 @@
+mod errors;
+mod options;
+mod variants;
@@ -0,0 +1,38 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::{CodecTrait, GenMessage, RustGenOptions};
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::String as Text;
+use crate::tree::Variant;
+
+#[test]
+fn options() -> Result<(), GenError> {
+    let shape: Variant = Variant::from("Shape")
+        .with_case(("side", UnsignedInt32, 1))
+        .with_case(("label", Text, 2));
+
+    let options: RustGenOptions = RustGenOptions::default()
+        .with_derives(["Clone", "Debug", "Default"])
+        .with_attribute("Shape", "non_exhaustive")
+        .with_codec_traits([CodecTrait::EncodedLen, CodecTrait::EncodeToWrite]);
+    let source: Source = GenMessage::default()
+        .with_options(options)
+        .gen_variant(&shape)?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 4 * 1024);
+    source.write(&mut b, 0);
+    let result: String = b.export();
+    let result: String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("options.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use code_gen::rust::Source;
+use code_gen::{CodeBuffer, Statement};
+
+use crate::gen::rust::GenMessage;
+use crate::gen::GenError;
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{Named, String};
+use crate::tree::{Message, MessageField, SourceFile, Variant, VariantCase, WithComments};
+
+#[test]
+fn variants() -> Result<(), GenError> {
+    let circle: Message = Message::from("Circle")
+        .with_field(MessageField::from(("radius", Float64)).with_field_number(1));
+
+    let mut shape: Variant = "Shape".into();
+    shape.add_comment("A shape.");
+    shape.add_case(
+        VariantCase::from(("circle", Named("Circle".to_string()), 1)).with_comment("A circle."),
+    );
+    shape.add_case(("side", UnsignedInt32, 2));
+    shape.add_case(("label", String, 3));
+
+    let drawing: Message = Message::from("Drawing")
+        .with_field(MessageField::from(("shape", Named("Shape".to_string()))).with_field_number(1));
+
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(circle)
+        .with_declaration(shape)
+        .with_declaration(drawing);
+    let source: Source = GenMessage::default().gen_declarations(&source_file, &[])?;
+
+    let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+    source.write(&mut b, 0);
+    let result: std::string::String = b.export();
+    let result: std::string::String = result
+        .split("\n")
+        .map(|s| s.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let expected: &str = include_str!("variants.txt");
+
+    assert_eq!(result, expected);
+
+    Ok(())
+}
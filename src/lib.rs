@@ -1,2 +1,7 @@
+pub mod compat;
 pub mod gen;
+pub mod parse;
+#[cfg(feature = "runtime")]
+pub mod runtime;
 pub mod tree;
+pub mod validate;
@@ -0,0 +1,139 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::parse::token::Token;
+use crate::parse::{ParseError, Span};
+
+/// The single-character symbols.
+const SYMBOLS: &str = "{}:;=[]<>,().";
+
+/// Responsible for splitting source text into tokens.
+#[derive(Clone, Debug)]
+pub(crate) struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> From<&'a str> for Lexer<'a> {
+    fn from(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl<'a> Lexer<'a> {
+    //! Characters
+
+    /// Gets the position of the next character.
+    fn span(&self) -> Span {
+        Span::new(self.line, self.column)
+    }
+
+    /// Peeks at the next character.
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// Consumes the next character.
+    fn next_char(&mut self) -> Option<char> {
+        let c: char = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Consumes characters while the predicate holds.
+    fn take_while<P>(&mut self, predicate: P) -> String
+    where
+        P: Fn(char) -> bool,
+    {
+        let mut result: String = String::default();
+        while let Some(c) = self.peek() {
+            if !predicate(c) {
+                break;
+            }
+            result.push(c);
+            self.next_char();
+        }
+        result
+    }
+}
+
+impl<'a> Lexer<'a> {
+    //! Lex
+
+    /// Lexes the source text into tokens.
+    pub fn lex(mut self) -> Result<Vec<(Span, Token)>, ParseError> {
+        let mut tokens: Vec<(Span, Token)> = Vec::default();
+        while let Some(c) = self.peek() {
+            let span: Span = self.span();
+            if c.is_whitespace() {
+                self.next_char();
+            } else if c == '/' {
+                tokens.push((span, self.lex_comment(span)?));
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let ident: String = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                tokens.push((span, Token::Ident(ident)));
+            } else if c.is_ascii_digit() {
+                tokens.push((span, self.lex_number(span)?));
+            } else if c == '"' {
+                tokens.push((span, self.lex_text(span)?));
+            } else if c == '-' {
+                self.next_char();
+                if self.peek() != Some('>') {
+                    return Err(ParseError::new(span, "expected `->`"));
+                }
+                self.next_char();
+                tokens.push((span, Token::Arrow));
+            } else if SYMBOLS.contains(c) {
+                self.next_char();
+                tokens.push((span, Token::Symbol(c)));
+            } else {
+                return Err(ParseError::new(
+                    span,
+                    format!("unexpected character `{}`", c),
+                ));
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Lexes a line comment.
+    fn lex_comment(&mut self, span: Span) -> Result<Token, ParseError> {
+        self.next_char();
+        if self.next_char() != Some('/') {
+            return Err(ParseError::new(span, "expected `//`"));
+        }
+        let comment: String = self.take_while(|c| c != '\n');
+        let comment: &str = comment.trim_end();
+        let comment: &str = comment.strip_prefix(' ').unwrap_or(comment);
+        Ok(Token::Comment(comment.to_string()))
+    }
+
+    /// Lexes an unsigned integer literal.
+    fn lex_number(&mut self, span: Span) -> Result<Token, ParseError> {
+        let digits: String = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        digits
+            .parse::<u64>()
+            .map(Token::Number)
+            .map_err(|_| ParseError::new(span, format!("invalid number `{}`", digits)))
+    }
+
+    /// Lexes a double-quoted string literal.
+    fn lex_text(&mut self, span: Span) -> Result<Token, ParseError> {
+        self.next_char();
+        let text: String = self.take_while(|c| c != '"' && c != '\n');
+        if self.next_char() != Some('"') {
+            return Err(ParseError::new(span, "unterminated string literal"));
+        }
+        Ok(Token::Text(text))
+    }
+}
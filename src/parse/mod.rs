@@ -0,0 +1,12 @@
+pub use parse_error::*;
+pub use parser::*;
+pub use span::*;
+
+mod lexer;
+mod parse_error;
+mod parser;
+mod span;
+mod token;
+
+#[cfg(test)]
+mod tests;
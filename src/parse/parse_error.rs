@@ -0,0 +1,48 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::parse::Span;
+
+/// An error parsing source text.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct ParseError {
+    span: Span,
+    message: String,
+}
+
+impl ParseError {
+    //! Construction
+
+    /// Creates a new parse error.
+    pub fn new<S>(span: Span, message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl ParseError {
+    //! Properties
+
+    /// Gets the position of the error in the source text.
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the error message.
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.span, self.message)
+    }
+}
+
+impl Error for ParseError {}
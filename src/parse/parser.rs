@@ -0,0 +1,518 @@
+use crate::parse::lexer::Lexer;
+use crate::parse::token::Token;
+use crate::parse::{ParseError, Span};
+use crate::tree::{
+    Enum, EnumCase, Import, Message, MessageField, Package, PrimitiveType, Reserved, Rpc, Service,
+    SourceFile, TypeTag, Variant, VariantCase, WithComments,
+};
+
+/// Parses the source text into a source file.
+pub fn parse_source_file(source: &str) -> Result<SourceFile, ParseError> {
+    Parser::new(source)?.parse_source_file()
+}
+
+/// Responsible for parsing source text into source trees.
+#[derive(Clone, Debug)]
+pub struct Parser {
+    tokens: Vec<(Span, Token)>,
+    position: usize,
+    end: Span,
+}
+
+impl Parser {
+    //! Construction
+
+    /// Creates a new parser for the source text.
+    pub fn new(source: &str) -> Result<Self, ParseError> {
+        let tokens: Vec<(Span, Token)> = Lexer::from(source).lex()?;
+        let line: usize = source.split('\n').count();
+        let column: usize = source.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        Ok(Self {
+            tokens,
+            position: 0,
+            end: Span::new(line, column),
+        })
+    }
+}
+
+impl Parser {
+    //! Tokens
+
+    /// Peeks at the next token.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    /// Gets the position of the next token.
+    fn span(&self) -> Span {
+        self.tokens
+            .get(self.position)
+            .map(|(span, _)| *span)
+            .unwrap_or(self.end)
+    }
+
+    /// Consumes the next token.
+    fn next_token(&mut self) -> Option<Token> {
+        let token: Token = self.tokens.get(self.position)?.1.clone();
+        self.position += 1;
+        Some(token)
+    }
+
+    /// Creates an error for the unexpected next token.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let message: String = match self.peek() {
+            Some(token) => format!("expected {}, found `{}`", expected, token),
+            None => format!("expected {}, found end of input", expected),
+        };
+        ParseError::new(self.span(), message)
+    }
+
+    /// Checks if the next token is the symbol.
+    fn next_is_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&Token::Symbol(symbol))
+    }
+
+    /// Checks if the next token is the keyword.
+    fn next_is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident == keyword)
+    }
+
+    /// Consumes the symbol.
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), ParseError> {
+        if self.next_is_symbol(symbol) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", symbol)))
+        }
+    }
+
+    /// Consumes the keyword.
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        if self.next_is_keyword(keyword) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", keyword)))
+        }
+    }
+
+    /// Consumes an identifier.
+    fn expect_ident(&mut self) -> Result<String, ParseError> {
+        if let Some(Token::Ident(ident)) = self.peek() {
+            let ident: String = ident.clone();
+            self.position += 1;
+            Ok(ident)
+        } else {
+            Err(self.unexpected("an identifier"))
+        }
+    }
+
+    /// Consumes a string literal.
+    fn expect_text(&mut self) -> Result<String, ParseError> {
+        if let Some(Token::Text(text)) = self.peek() {
+            let text: String = text.clone();
+            self.position += 1;
+            Ok(text)
+        } else {
+            Err(self.unexpected("a string literal"))
+        }
+    }
+
+    /// Consumes an unsigned 32-bit integer literal.
+    fn expect_u32(&mut self) -> Result<u32, ParseError> {
+        let span: Span = self.span();
+        if let Some(Token::Number(number)) = self.peek() {
+            let number: u64 = *number;
+            self.position += 1;
+            u32::try_from(number)
+                .map_err(|_| ParseError::new(span, format!("number out of range: {}", number)))
+        } else {
+            Err(self.unexpected("a number"))
+        }
+    }
+
+    /// Consumes the consecutive comment tokens.
+    fn parse_comments(&mut self) -> Vec<String> {
+        let mut comments: Vec<String> = Vec::default();
+        while let Some(Token::Comment(comment)) = self.peek() {
+            comments.push(comment.clone());
+            self.position += 1;
+        }
+        comments
+    }
+
+    /// Rejects the just consumed comments. (comments must precede a declaration)
+    fn reject_comments(&self, comments: &[String]) -> Result<(), ParseError> {
+        if comments.is_empty() {
+            Ok(())
+        } else {
+            let span: Span = self.tokens[self.position - comments.len()].0;
+            Err(ParseError::new(span, "dangling comment"))
+        }
+    }
+}
+
+impl Parser {
+    //! Source File
+
+    /// Parses the tokens into a source file.
+    ///
+    /// The optional package & the imports precede the declarations. (the comments preceding them
+    /// are attached to them)
+    pub fn parse_source_file(&mut self) -> Result<SourceFile, ParseError> {
+        let mut source_file: SourceFile = SourceFile::default();
+        let mut comments: Vec<String> = self.parse_comments();
+        if self.next_is_keyword("package") {
+            source_file.set_package(self.parse_package(comments)?);
+            comments = self.parse_comments();
+        }
+        while self.next_is_keyword("import") {
+            source_file.add_import(self.parse_import(comments)?);
+            comments = self.parse_comments();
+        }
+        loop {
+            if self.peek().is_none() {
+                self.reject_comments(&comments)?;
+                break;
+            } else if self.next_is_keyword("message") {
+                source_file.add_declaration(self.parse_message(comments)?);
+            } else if self.next_is_keyword("enum") {
+                source_file.add_declaration(self.parse_enum(comments)?);
+            } else if self.next_is_keyword("variant") {
+                source_file.add_declaration(self.parse_variant(comments)?);
+            } else if self.next_is_keyword("service") {
+                source_file.add_declaration(self.parse_service(comments)?);
+            } else {
+                return Err(self.unexpected("a declaration"));
+            }
+            comments = self.parse_comments();
+        }
+        Ok(source_file)
+    }
+
+    /// Parses a package declaration. (`.` separated identifiers)
+    fn parse_package(&mut self, comments: Vec<String>) -> Result<Package, ParseError> {
+        self.expect_keyword("package")?;
+        let mut name: String = self.expect_ident()?;
+        while self.next_is_symbol('.') {
+            self.next_token();
+            name.push('.');
+            name.push_str(self.expect_ident()?.as_str());
+        }
+        self.expect_symbol(';')?;
+        let mut package: Package = name.into();
+        for comment in comments {
+            package.add_comment(comment);
+        }
+        Ok(package)
+    }
+
+    /// Parses an import.
+    fn parse_import(&mut self, comments: Vec<String>) -> Result<Import, ParseError> {
+        self.expect_keyword("import")?;
+        let path: String = self.expect_text()?;
+        self.expect_symbol(';')?;
+        let mut import: Import = path.into();
+        for comment in comments {
+            import.add_comment(comment);
+        }
+        Ok(import)
+    }
+}
+
+impl Parser {
+    //! Messages
+
+    /// Parses a message declaration.
+    fn parse_message(&mut self, comments: Vec<String>) -> Result<Message, ParseError> {
+        self.expect_keyword("message")?;
+        let mut message: Message = self.expect_ident()?.into();
+        for comment in comments {
+            message.add_comment(comment);
+        }
+
+        self.expect_symbol('{')?;
+        loop {
+            let comments: Vec<String> = self.parse_comments();
+            if self.next_is_symbol('}') {
+                self.reject_comments(&comments)?;
+                self.next_token();
+                break;
+            }
+            if self.next_is_reserved() {
+                self.reject_comments(&comments)?;
+                message.add_reserved(self.parse_reserved()?);
+            } else {
+                message.add_field(self.parse_message_field(comments)?);
+            }
+        }
+        Ok(message)
+    }
+
+    /// Parses a message field.
+    fn parse_message_field(&mut self, comments: Vec<String>) -> Result<MessageField, ParseError> {
+        let name: String = self.expect_ident()?;
+        self.expect_symbol(':')?;
+        let mut field: MessageField = (name, self.parse_type_tag()?).into();
+        for comment in comments {
+            field.add_comment(comment);
+        }
+
+        if self.next_is_symbol('=') {
+            self.next_token();
+            field.set_field_number(self.expect_u32()?);
+        }
+        if self.next_is_symbol('[') {
+            self.next_token();
+            loop {
+                self.parse_message_field_option(&mut field)?;
+                if self.next_is_symbol(',') {
+                    self.next_token();
+                } else {
+                    break;
+                }
+            }
+            self.expect_symbol(']')?;
+        }
+        self.expect_symbol(';')?;
+
+        Ok(field)
+    }
+
+    /// Checks if the next tokens start a reserved statement. (not a field named `reserved`)
+    fn next_is_reserved(&self) -> bool {
+        self.next_is_keyword("reserved")
+            && !matches!(
+                self.tokens.get(self.position + 1),
+                Some((_, Token::Symbol(':')))
+            )
+    }
+
+    /// Parses a reserved statement. (`reserved 3, 7 to 9;` or `reserved "old_name";`)
+    fn parse_reserved(&mut self) -> Result<Reserved, ParseError> {
+        self.expect_keyword("reserved")?;
+        let reserved: Reserved = if let Some(Token::Text(_)) = self.peek() {
+            let mut names: Vec<String> = Vec::default();
+            loop {
+                names.push(self.expect_text()?);
+                if !self.next_is_symbol(',') {
+                    break;
+                }
+                self.next_token();
+            }
+            Reserved::Names(names)
+        } else {
+            let mut ranges: Vec<(u32, u32)> = Vec::default();
+            loop {
+                let start: u32 = self.expect_u32()?;
+                let end: u32 = if self.next_is_keyword("to") {
+                    self.next_token();
+                    self.expect_u32()?
+                } else {
+                    start
+                };
+                ranges.push((start, end));
+                if !self.next_is_symbol(',') {
+                    break;
+                }
+                self.next_token();
+            }
+            Reserved::Numbers(ranges)
+        };
+        self.expect_symbol(';')?;
+        Ok(reserved)
+    }
+
+    /// Parses a message field option. (`fixed` or `packed`)
+    fn parse_message_field_option(&mut self, field: &mut MessageField) -> Result<(), ParseError> {
+        if self.next_is_keyword("fixed") {
+            self.next_token();
+            field.set_fixed(true);
+        } else if self.next_is_keyword("packed") {
+            self.next_token();
+            field.set_packed(true);
+        } else {
+            return Err(self.unexpected("`fixed` or `packed`"));
+        }
+        Ok(())
+    }
+}
+
+impl Parser {
+    //! Enums
+
+    /// Parses an enum declaration.
+    fn parse_enum(&mut self, comments: Vec<String>) -> Result<Enum, ParseError> {
+        self.expect_keyword("enum")?;
+        let mut e: Enum = self.expect_ident()?.into();
+        for comment in comments {
+            e.add_comment(comment);
+        }
+
+        self.expect_symbol('{')?;
+        loop {
+            let comments: Vec<String> = self.parse_comments();
+            if self.next_is_symbol('}') {
+                self.reject_comments(&comments)?;
+                self.next_token();
+                break;
+            }
+            e.add_case(self.parse_enum_case(comments)?);
+        }
+        Ok(e)
+    }
+
+    /// Parses an enum case.
+    fn parse_enum_case(&mut self, comments: Vec<String>) -> Result<EnumCase, ParseError> {
+        let name: String = self.expect_ident()?;
+        self.expect_symbol('=')?;
+        let mut case: EnumCase = (name, self.expect_u32()?).into();
+        for comment in comments {
+            case.add_comment(comment);
+        }
+        self.expect_symbol(';')?;
+        Ok(case)
+    }
+}
+
+impl Parser {
+    //! Variants
+
+    /// Parses a variant declaration.
+    fn parse_variant(&mut self, comments: Vec<String>) -> Result<Variant, ParseError> {
+        self.expect_keyword("variant")?;
+        let mut variant: Variant = self.expect_ident()?.into();
+        for comment in comments {
+            variant.add_comment(comment);
+        }
+
+        self.expect_symbol('{')?;
+        loop {
+            let comments: Vec<String> = self.parse_comments();
+            if self.next_is_symbol('}') {
+                self.reject_comments(&comments)?;
+                self.next_token();
+                break;
+            }
+            variant.add_case(self.parse_variant_case(comments)?);
+        }
+        Ok(variant)
+    }
+
+    /// Parses a variant case.
+    fn parse_variant_case(&mut self, comments: Vec<String>) -> Result<VariantCase, ParseError> {
+        let name: String = self.expect_ident()?;
+        self.expect_symbol(':')?;
+        let type_tag: TypeTag = self.parse_type_tag()?;
+        self.expect_symbol('=')?;
+        let mut case: VariantCase = (name, type_tag, self.expect_u32()?).into();
+        for comment in comments {
+            case.add_comment(comment);
+        }
+        self.expect_symbol(';')?;
+        Ok(case)
+    }
+}
+
+impl Parser {
+    //! Services
+
+    /// Parses a service declaration.
+    fn parse_service(&mut self, comments: Vec<String>) -> Result<Service, ParseError> {
+        self.expect_keyword("service")?;
+        let mut service: Service = self.expect_ident()?.into();
+        for comment in comments {
+            service.add_comment(comment);
+        }
+
+        self.expect_symbol('{')?;
+        loop {
+            let comments: Vec<String> = self.parse_comments();
+            if self.next_is_symbol('}') {
+                self.reject_comments(&comments)?;
+                self.next_token();
+                break;
+            }
+            service.add_rpc(self.parse_rpc(comments)?);
+        }
+        Ok(service)
+    }
+
+    /// Parses a service rpc. (`rpc Name(Request) -> Response;`)
+    fn parse_rpc(&mut self, comments: Vec<String>) -> Result<Rpc, ParseError> {
+        self.expect_keyword("rpc")?;
+        let name: String = self.expect_ident()?;
+        self.expect_symbol('(')?;
+        let request: String = self.expect_ident()?;
+        self.expect_symbol(')')?;
+        if self.peek() == Some(&Token::Arrow) {
+            self.next_token();
+        } else {
+            return Err(self.unexpected("`->`"));
+        }
+        let response: String = self.expect_ident()?;
+        self.expect_symbol(';')?;
+
+        let mut rpc: Rpc = (name, request, response).into();
+        for comment in comments {
+            rpc.add_comment(comment);
+        }
+        Ok(rpc)
+    }
+}
+
+impl Parser {
+    //! Types
+
+    /// Parses a type tag.
+    ///
+    /// `[T]` is a list, `map<K, V>` is a map & names other than the built-in types are named types.
+    fn parse_type_tag(&mut self) -> Result<TypeTag, ParseError> {
+        if self.next_is_symbol('[') {
+            self.next_token();
+            let element: TypeTag = self.parse_type_tag()?;
+            self.expect_symbol(']')?;
+            return Ok(TypeTag::List(Box::new(element)));
+        }
+
+        let name: String = self.expect_ident()?;
+        if name == "map" && self.next_is_symbol('<') {
+            self.next_token();
+            let key: TypeTag = self.parse_type_tag()?;
+            self.expect_symbol(',')?;
+            let value: TypeTag = self.parse_type_tag()?;
+            self.expect_symbol('>')?;
+            return Ok(TypeTag::Map(Box::new(key), Box::new(value)));
+        }
+
+        let tag: TypeTag = match name.as_str() {
+            "string" => TypeTag::String,
+            "bytes" => TypeTag::Bytes,
+            _ => match Self::primitive_type(name.as_str()) {
+                Some(primitive) => primitive.to_type_tag(),
+                None => TypeTag::Named(name),
+            },
+        };
+        Ok(tag)
+    }
+
+    /// Gets the primitive type with the name.
+    fn primitive_type(name: &str) -> Option<PrimitiveType> {
+        let primitive: PrimitiveType = match name {
+            "u8" => PrimitiveType::UnsignedInt8,
+            "u16" => PrimitiveType::UnsignedInt16,
+            "u32" => PrimitiveType::UnsignedInt32,
+            "u64" => PrimitiveType::UnsignedInt64,
+            "i8" => PrimitiveType::SignedInt8,
+            "i16" => PrimitiveType::SignedInt16,
+            "i32" => PrimitiveType::SignedInt32,
+            "i64" => PrimitiveType::SignedInt64,
+            "f32" => PrimitiveType::Float32,
+            "f64" => PrimitiveType::Float64,
+            "bool" => PrimitiveType::Boolean,
+            _ => return None,
+        };
+        Some(primitive)
+    }
+}
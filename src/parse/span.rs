@@ -0,0 +1,43 @@
+use std::fmt::{Display, Formatter};
+
+/// A line & column position in source text. (both are 1-based)
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Span {
+    line: usize,
+    column: usize,
+}
+
+impl Span {
+    //! Construction
+
+    /// Creates a new span.
+    pub const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl Default for Span {
+    fn default() -> Self {
+        Self::new(1, 1)
+    }
+}
+
+impl Span {
+    //! Properties
+
+    /// Gets the line number.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the column number.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
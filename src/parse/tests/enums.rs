@@ -0,0 +1,27 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::Named;
+use crate::tree::{Enum, EnumCase, Message, MessageField, SourceFile, WithComments};
+
+#[test]
+fn enums() -> Result<(), ParseError> {
+    let source: &str = "// The status.\nenum Status {\n    // Active.\n    ACTIVE = 1;\n    DISABLED = 2;\n}\n\nmessage User {\n    id: u64;\n    status: Status = 1;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let e: Enum = Enum::from("Status")
+        .with_comment("The status.")
+        .with_case(EnumCase::from(("ACTIVE", 1)).with_comment("Active."))
+        .with_case(("DISABLED", 2));
+    let message: Message = Message::from("User")
+        .with_field(("id", UnsignedInt64))
+        .with_field(
+            MessageField::from(("status", Named("Status".to_string()))).with_field_number(1),
+        );
+    let expected: SourceFile = SourceFile::default()
+        .with_declaration(e)
+        .with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
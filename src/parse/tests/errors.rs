@@ -0,0 +1,48 @@
+use crate::parse::{parse_source_file, ParseError, Span};
+
+fn error(source: &str) -> ParseError {
+    parse_source_file(source).expect_err(source)
+}
+
+#[test]
+fn errors() {
+    let e: ParseError = error("messag One {}");
+    assert_eq!(e.span(), Span::new(1, 1));
+    assert_eq!(e.message(), "expected a declaration, found `messag`");
+
+    let e: ParseError = error("message One {\n    one: 7 = 1;\n}");
+    assert_eq!(e.span(), Span::new(2, 10));
+    assert_eq!(e.to_string(), "2:10: expected an identifier, found `7`");
+
+    let e: ParseError = error("message One {\n    one: u8 = 4294967296;\n}");
+    assert_eq!(e.span(), Span::new(2, 15));
+
+    let e: ParseError = error("message One {\n    one: u8 = 1\n}");
+    assert_eq!(e.span(), Span::new(3, 1));
+    assert_eq!(e.message(), "expected `;`, found `}`");
+
+    let e: ParseError = error("message One {");
+    assert_eq!(e.span(), Span::new(1, 14));
+    assert_eq!(e.message(), "expected an identifier, found end of input");
+
+    let e: ParseError = error("message One { $ }");
+    assert_eq!(e.span(), Span::new(1, 15));
+    assert_eq!(e.message(), "unexpected character `$`");
+}
+
+#[test]
+fn dangling_comments() {
+    let e: ParseError = error("message One {\n    one: u8 = 1;\n    // Two.\n}");
+    assert_eq!(e.span(), Span::new(3, 5));
+    assert_eq!(e.message(), "dangling comment");
+
+    let e: ParseError = error("enum Status {\n    ACTIVE = 1;\n    // Disabled.\n}");
+    assert_eq!(e.span(), Span::new(3, 5));
+
+    let e: ParseError = error("message One {\n    // Reserved.\n    reserved 2;\n}");
+    assert_eq!(e.span(), Span::new(2, 5));
+
+    let e: ParseError = error("message One {}\n\n// The end.\n");
+    assert_eq!(e.span(), Span::new(3, 1));
+    assert_eq!(e.to_string(), "3:1: dangling comment");
+}
@@ -0,0 +1,22 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, SourceFile};
+
+#[test]
+fn fixed_ints() -> Result<(), ParseError> {
+    let source: &str = "message FixedInts {\n    id: u64 [fixed];\n    hash: u32 = 1 [fixed];\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("FixedInts")
+        .with_field(MessageField::from(("id", UnsignedInt64)).with_fixed())
+        .with_field(
+            MessageField::from(("hash", UnsignedInt32))
+                .with_field_number(1)
+                .with_fixed(),
+        );
+    let expected: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
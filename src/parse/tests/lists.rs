@@ -0,0 +1,25 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{List, String as Text};
+use crate::tree::{Message, MessageField, SourceFile};
+
+#[test]
+fn lists() -> Result<(), ParseError> {
+    let source: &str =
+        "message Lists {\n    tags: [u32] = 1 [fixed, packed];\n    names: [string] = 2;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("Lists")
+        .with_field(
+            MessageField::from(("tags", List(Box::new(UnsignedInt32.into()))))
+                .with_field_number(1)
+                .with_fixed()
+                .with_packed(),
+        )
+        .with_field(MessageField::from(("names", List(Box::new(Text)))).with_field_number(2));
+    let expected: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{Map, String as Text};
+use crate::tree::{Message, MessageField, SourceFile};
+
+#[test]
+fn maps() -> Result<(), ParseError> {
+    let source: &str = "message Maps {\n    scores: map<string, u32> = 1;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("Maps").with_field(
+        MessageField::from((
+            "scores",
+            Map(Box::new(Text), Box::new(UnsignedInt32.into())),
+        ))
+        .with_field_number(1),
+    );
+    let expected: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
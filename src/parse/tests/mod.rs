@@ -0,0 +1,11 @@
+mod enums;
+mod errors;
+mod fixed_ints;
+mod lists;
+mod maps;
+mod packages;
+mod reserved;
+mod services;
+mod strings;
+mod unsigned_ints;
+mod variants;
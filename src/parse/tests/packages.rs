@@ -0,0 +1,60 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::UnsignedInt64;
+use crate::tree::TypeTag::Named;
+use crate::tree::{Import, Message, Package, SourceFile, WithComments};
+
+#[test]
+fn package_and_imports() -> Result<(), ParseError> {
+    let source: &str = "package acme.billing;\nimport \"common.pp\";\nimport \"users.pp\";\n\nmessage Invoice {\n    total: Money;\n    user_id: u64;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("Invoice")
+        .with_field(("total", Named("Money".to_string())))
+        .with_field(("user_id", UnsignedInt64));
+    let expected: SourceFile = SourceFile::default()
+        .with_package("acme.billing")
+        .with_import("common.pp")
+        .with_import("users.pp")
+        .with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
+
+#[test]
+fn package_and_import_comments() -> Result<(), ParseError> {
+    let source: &str = "// The billing schema.\n// (version 2)\npackage acme.billing;\n// The money.\nimport \"common.pp\";\nimport \"users.pp\";\n\nmessage Invoice {}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let expected: SourceFile = SourceFile::default()
+        .with_package(
+            Package::from("acme.billing")
+                .with_comment("The billing schema.")
+                .with_comment("(version 2)"),
+        )
+        .with_import(Import::from("common.pp").with_comment("The money."))
+        .with_import("users.pp")
+        .with_declaration(Message::from("Invoice"));
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
+
+#[test]
+fn import_after_declaration() {
+    let source: &str = "message Invoice {}\nimport \"common.pp\";\n";
+    let error: ParseError = parse_source_file(source).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "2:1: expected a declaration, found `import`"
+    );
+}
+
+#[test]
+fn invalid_package() {
+    let source: &str = "package acme.;\n";
+    let error: ParseError = parse_source_file(source).unwrap_err();
+    assert_eq!(error.to_string(), "1:14: expected an identifier, found `;`");
+}
@@ -0,0 +1,39 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, Reserved, SourceFile};
+
+#[test]
+fn reserved() -> Result<(), ParseError> {
+    let source: &str = "message User {\n    reserved: u8 = 1;\n    reserved 3, 7 to 9;\n    reserved \"old_name\", \"older_name\";\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("reserved", UnsignedInt8)).with_field_number(1))
+        .with_reserved(Reserved::Numbers(vec![(3, 3), (7, 9)]))
+        .with_reserved(Reserved::Names(vec![
+            "old_name".to_string(),
+            "older_name".to_string(),
+        ]));
+    let expected: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
+
+#[test]
+fn reserved_between_fields() -> Result<(), ParseError> {
+    let source: &str = "message User {\n    reserved 1;\n    two: u8 = 2;\n    reserved \"three\";\n    four: u8 = 4;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("User")
+        .with_reserved(Reserved::Numbers(vec![(1, 1)]))
+        .with_field(MessageField::from(("two", UnsignedInt8)).with_field_number(2))
+        .with_reserved(Reserved::Names(vec!["three".to_string()]))
+        .with_field(MessageField::from(("four", UnsignedInt8)).with_field_number(4));
+    let expected: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
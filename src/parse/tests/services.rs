@@ -0,0 +1,27 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::{Rpc, Service, SourceFile, WithComments};
+
+#[test]
+fn services() -> Result<(), ParseError> {
+    let source: &str = "// The users.\nservice Users {\n    // Gets a user.\n    rpc GetUser(GetUserRequest) -> GetUserResponse;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let service: Service = Service::from("Users").with_comment("The users.").with_rpc(
+        Rpc::from(("GetUser", "GetUserRequest", "GetUserResponse")).with_comment("Gets a user."),
+    );
+    let expected: SourceFile = SourceFile::default().with_declaration(service);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
+
+#[test]
+fn missing_arrow() {
+    let source: &str = "service Users {\n    rpc GetUser(GetUserRequest) GetUserResponse;\n}\n";
+    let error: ParseError = parse_source_file(source).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "2:33: expected `->`, found `GetUserResponse`"
+    );
+}
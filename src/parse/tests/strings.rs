@@ -0,0 +1,18 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::TypeTag::{Bytes, String as Text};
+use crate::tree::{Message, MessageField, SourceFile};
+
+#[test]
+fn strings() -> Result<(), ParseError> {
+    let source: &str = "message Strings {\n    name: string;\n    data: bytes = 1;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("Strings")
+        .with_field(MessageField::from(("name", Text)))
+        .with_field(MessageField::from(("data", Bytes)).with_field_number(1));
+    let expected: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
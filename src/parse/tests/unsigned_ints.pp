@@ -0,0 +1,13 @@
+// A message with unsigned integer fields.
+message UnsignedInts {
+    // The first field.
+    one: u8 = 1;
+    // The second field.
+    two: u16 = 2;
+    // The third field.
+    three: u32 = 3;
+    // The fourth field.
+    four: u64 = 4;
+}
+
+message Empty {}
@@ -0,0 +1,41 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::{Message, MessageField, SourceFile, WithComments};
+
+#[test]
+fn unsigned_ints() -> Result<(), ParseError> {
+    let source: &str = include_str!("unsigned_ints.pp");
+    let result: SourceFile = parse_source_file(source)?;
+
+    let message: Message = Message::from("UnsignedInts")
+        .with_comment("A message with unsigned integer fields.")
+        .with_field(
+            MessageField::from(("one", UnsignedInt8))
+                .with_field_number(1)
+                .with_comment("The first field."),
+        )
+        .with_field(
+            MessageField::from(("two", UnsignedInt16))
+                .with_field_number(2)
+                .with_comment("The second field."),
+        )
+        .with_field(
+            MessageField::from(("three", UnsignedInt32))
+                .with_field_number(3)
+                .with_comment("The third field."),
+        )
+        .with_field(
+            MessageField::from(("four", UnsignedInt64))
+                .with_field_number(4)
+                .with_comment("The fourth field."),
+        );
+    let expected: SourceFile = SourceFile::default()
+        .with_declaration(message)
+        .with_declaration(Message::from("Empty"));
+    assert_eq!(result, expected);
+
+    assert_eq!(result.to_string(), source);
+    assert_eq!(parse_source_file(result.to_string().as_str())?, result);
+
+    Ok(())
+}
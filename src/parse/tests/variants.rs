@@ -0,0 +1,22 @@
+use crate::parse::{parse_source_file, ParseError};
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::Named;
+use crate::tree::{SourceFile, Variant, VariantCase, WithComments};
+
+#[test]
+fn variants() -> Result<(), ParseError> {
+    let source: &str = "// A shape.\nvariant Shape {\n    // A circle.\n    circle: Circle = 1;\n    side: f64 = 2;\n}\n";
+    let result: SourceFile = parse_source_file(source)?;
+
+    let variant: Variant = Variant::from("Shape")
+        .with_comment("A shape.")
+        .with_case(
+            VariantCase::from(("circle", Named("Circle".to_string()), 1)).with_comment("A circle."),
+        )
+        .with_case(("side", Float64, 2));
+    let expected: SourceFile = SourceFile::default().with_declaration(variant);
+    assert_eq!(result, expected);
+    assert_eq!(result.to_string(), source);
+
+    Ok(())
+}
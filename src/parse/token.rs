@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter};
+
+/// A lexical token.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum Token {
+    /// A line comment. (without the leading `// `)
+    Comment(String),
+
+    /// An identifier or keyword.
+    Ident(String),
+
+    /// An unsigned integer literal.
+    Number(u64),
+
+    /// A double-quoted string literal. (without the quotes)
+    Text(String),
+
+    /// A single-character symbol.
+    Symbol(char),
+
+    /// The `->` symbol.
+    Arrow,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Comment(comment) => write!(f, "// {}", comment),
+            Self::Ident(ident) => write!(f, "{}", ident),
+            Self::Number(number) => write!(f, "{}", number),
+            Self::Text(text) => write!(f, "\"{}\"", text),
+            Self::Symbol(symbol) => write!(f, "{}", symbol),
+            Self::Arrow => write!(f, "->"),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::io;
+
+use super::{write, EncodeToSlice, EncodeToWrite, EncodedLen, FieldHeader, VarIntSize, WireType};
+
+/// Encodes a required byte string value with its length prefix. (without a field header)
+#[derive(Copy, Clone, Debug)]
+pub struct ByteStringValue<'a, T: ?Sized> {
+    value: &'a T,
+}
+
+/// Encodes an optional byte string value as a tagged field.
+#[derive(Copy, Clone, Debug)]
+pub struct ByteStringField<'a, T: ?Sized> {
+    field_number: u32,
+    value: Option<&'a T>,
+}
+
+/// Encodes a list of byte string values as a tagged field per value.
+#[derive(Copy, Clone, Debug)]
+pub struct ByteStringList<'a, T> {
+    field_number: u32,
+    values: &'a [T],
+}
+
+/// Encodes a required `string` value.
+pub type StringValue<'a> = ByteStringValue<'a, str>;
+
+/// Encodes an optional `string` field.
+pub type StringField<'a> = ByteStringField<'a, str>;
+
+/// Encodes a list of `string` values.
+pub type StringList<'a> = ByteStringList<'a, String>;
+
+/// Encodes a required `bytes` value.
+pub type BytesValue<'a> = ByteStringValue<'a, [u8]>;
+
+/// Encodes an optional `bytes` field.
+pub type BytesField<'a> = ByteStringField<'a, [u8]>;
+
+/// Encodes a list of `bytes` values.
+pub type BytesList<'a> = ByteStringList<'a, Vec<u8>>;
+
+impl<'a, T: ?Sized> ByteStringValue<'a, T> {
+    //! Construction
+
+    /// Creates a new value encoder.
+    pub const fn new(value: &'a T) -> Self {
+        Self { value }
+    }
+}
+
+impl<'a, T: ?Sized> ByteStringField<'a, T> {
+    //! Construction
+
+    /// Creates a new field encoder.
+    pub const fn new(field_number: u32, value: Option<&'a T>) -> Self {
+        Self {
+            field_number,
+            value,
+        }
+    }
+}
+
+impl<'a, T> ByteStringList<'a, T> {
+    //! Construction
+
+    /// Creates a new list encoder.
+    pub const fn new(field_number: u32, values: &'a [T]) -> Self {
+        Self {
+            field_number,
+            values,
+        }
+    }
+}
+
+/// Gets the encoded length of the length-prefixed bytes.
+fn bytes_len(bytes: &[u8]) -> usize {
+    bytes.len().var_int_size() + bytes.len()
+}
+
+/// Encodes the length-prefixed bytes to the start of the target. Returns the encoded length.
+fn bytes_to_slice(bytes: &[u8], target: &mut [u8]) -> usize {
+    let prefix_len: usize = write::encode_u64_var(bytes.len() as u64, target);
+    target[prefix_len..(prefix_len + bytes.len())].copy_from_slice(bytes);
+    prefix_len + bytes.len()
+}
+
+/// Writes the length-prefixed bytes. Returns the encoded length.
+fn bytes_to_write<W>(bytes: &[u8], w: &mut W) -> Result<usize, io::Error>
+where
+    W: io::Write,
+{
+    let prefix_len: usize = write::write_u64_var(w, bytes.len() as u64)?;
+    w.write_all(bytes)?;
+    Ok(prefix_len + bytes.len())
+}
+
+/// Gets the header of a byte string field.
+const fn header(field_number: u32) -> FieldHeader {
+    FieldHeader::new(field_number, WireType::LengthPrefixed)
+}
+
+impl<'a, T> EncodedLen for ByteStringValue<'a, T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn encoded_len(&self) -> usize {
+        bytes_len(self.value.as_ref())
+    }
+}
+
+impl<'a, T> EncodeToSlice for ByteStringValue<'a, T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        bytes_to_slice(self.value.as_ref(), target)
+    }
+}
+
+impl<'a, T> EncodeToWrite for ByteStringValue<'a, T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        bytes_to_write(self.value.as_ref(), w)
+    }
+}
+
+impl<'a, T> EncodedLen for ByteStringField<'a, T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn encoded_len(&self) -> usize {
+        match self.value {
+            Some(value) => header(self.field_number).encoded_len() + bytes_len(value.as_ref()),
+            None => 0,
+        }
+    }
+}
+
+impl<'a, T> EncodeToSlice for ByteStringField<'a, T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        match self.value {
+            Some(value) => {
+                let encoded_len: usize =
+                    header(self.field_number).encode_to_slice_unchecked(target);
+                encoded_len + bytes_to_slice(value.as_ref(), &mut target[encoded_len..])
+            }
+            None => 0,
+        }
+    }
+}
+
+impl<'a, T> EncodeToWrite for ByteStringField<'a, T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        match self.value {
+            Some(value) => {
+                Ok(header(self.field_number).encode_to_write(w)?
+                    + bytes_to_write(value.as_ref(), w)?)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl<'a, T> EncodedLen for ByteStringList<'a, T>
+where
+    T: AsRef<[u8]>,
+{
+    fn encoded_len(&self) -> usize {
+        self.values
+            .iter()
+            .map(|value| ByteStringField::new(self.field_number, Some(value)).encoded_len())
+            .sum()
+    }
+}
+
+impl<'a, T> EncodeToSlice for ByteStringList<'a, T>
+where
+    T: AsRef<[u8]>,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        let mut encoded_len: usize = 0;
+        for value in self.values {
+            encoded_len += ByteStringField::new(self.field_number, Some(value))
+                .encode_to_slice_unchecked(&mut target[encoded_len..]);
+        }
+        encoded_len
+    }
+}
+
+impl<'a, T> EncodeToWrite for ByteStringList<'a, T>
+where
+    T: AsRef<[u8]>,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        let mut encoded_len: usize = 0;
+        for value in self.values {
+            encoded_len +=
+                ByteStringField::new(self.field_number, Some(value)).encode_to_write(w)?;
+        }
+        Ok(encoded_len)
+    }
+}
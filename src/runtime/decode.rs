@@ -0,0 +1,35 @@
+use std::io;
+
+use super::{read, ReadLimit};
+
+/// A value that can be decoded from a read.
+pub trait DecodeFromRead: Sized {
+    /// Decodes the value from the remaining input of the read.
+    fn decode_from_read<R>(r: &mut R) -> Result<Self, io::Error>
+    where
+        R: io::Read;
+}
+
+/// A value that can be decoded from a read with a length prefix.
+pub trait DecodeFromReadLengthPrefixed: DecodeFromRead {
+    /// Decodes the value from the read. The value is prefixed with its var-int encoded length.
+    ///
+    /// The value is decoded through a `dyn io::Read` so values nested in values of the same type
+    /// decode with the same reader type. (nesting `ReadLimit<&mut R>` for recursive messages would
+    /// never finish monomorphizing)
+    fn decode_from_read_length_prefixed<R>(r: &mut R) -> Result<Self, io::Error>
+    where
+        R: io::Read,
+    {
+        let len: usize = read::read_length(r)?;
+        let mut limit: ReadLimit<&mut dyn io::Read> = ReadLimit::new(r, len);
+        let value: Self = Self::decode_from_read(&mut limit)?;
+        if limit.remaining() != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the length-prefixed value ended early",
+            ));
+        }
+        Ok(value)
+    }
+}
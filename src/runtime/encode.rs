@@ -0,0 +1,43 @@
+use std::io;
+
+/// A value with a known encoded length.
+pub trait EncodedLen {
+    /// Gets the encoded length. (in bytes)
+    fn encoded_len(&self) -> usize;
+}
+
+/// A value that can be encoded to a slice.
+pub trait EncodeToSlice: EncodedLen {
+    /// Encodes the value to the start of the target. Returns the encoded length.
+    ///
+    /// # Safety
+    /// The target must be at least `encoded_len()` bytes long.
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize;
+
+    /// Encodes the value to the start of the target. Returns the encoded length.
+    ///
+    /// Returns `None` if the target is shorter than the encoded length.
+    fn encode_to_slice(&self, target: &mut [u8]) -> Option<usize> {
+        if target.len() < self.encoded_len() {
+            None
+        } else {
+            Some(unsafe { self.encode_to_slice_unchecked(target) })
+        }
+    }
+
+    /// Encodes the value to a new vec.
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let mut target: Vec<u8> = vec![0; self.encoded_len()];
+        let encoded_len: usize = unsafe { self.encode_to_slice_unchecked(target.as_mut_slice()) };
+        debug_assert_eq!(encoded_len, target.len());
+        target
+    }
+}
+
+/// A value that can be encoded to a write.
+pub trait EncodeToWrite {
+    /// Encodes the value to the write. Returns the encoded length.
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write;
+}
@@ -0,0 +1,230 @@
+use std::io;
+
+use super::scalar::{
+    list_len, list_to_slice, list_to_write, packed_len, packed_to_slice, packed_to_write,
+};
+use super::{EncodeToSlice, EncodeToWrite, EncodedLen, UnsignedInt32Field, UnsignedInt32Value};
+
+/// Encodes a required enum value as its var-int case number. (without a field header)
+#[derive(Copy, Clone, Debug)]
+pub struct EnumValue<T> {
+    value: T,
+}
+
+/// Encodes an optional enum value as a tagged field.
+#[derive(Copy, Clone, Debug)]
+pub struct EnumField<T> {
+    field_number: u32,
+    value: Option<T>,
+}
+
+/// Encodes a list of enum values as a tagged field per value.
+#[derive(Copy, Clone, Debug)]
+pub struct EnumList<'a, T> {
+    field_number: u32,
+    values: &'a [T],
+}
+
+/// Encodes a list of enum values as a single length-prefixed field.
+#[derive(Copy, Clone, Debug)]
+pub struct EnumPacked<'a, T> {
+    field_number: u32,
+    values: &'a [T],
+}
+
+/// Gets the case numbers of the enum values.
+fn numbers<T>(values: &[T]) -> impl Iterator<Item = u32> + Clone + '_
+where
+    T: Copy + Into<u32>,
+{
+    values.iter().map(|value| (*value).into())
+}
+
+impl<T> EnumValue<T> {
+    //! Construction
+
+    /// Creates a new value encoder.
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T> EnumField<T> {
+    //! Construction
+
+    /// Creates a new field encoder.
+    pub const fn new(field_number: u32, value: Option<T>) -> Self {
+        Self {
+            field_number,
+            value,
+        }
+    }
+}
+
+impl<'a, T> EnumList<'a, T> {
+    //! Construction
+
+    /// Creates a new list encoder.
+    pub const fn new(field_number: u32, values: &'a [T]) -> Self {
+        Self {
+            field_number,
+            values,
+        }
+    }
+}
+
+impl<'a, T> EnumPacked<'a, T> {
+    //! Construction
+
+    /// Creates a new packed list encoder.
+    pub const fn new(field_number: u32, values: &'a [T]) -> Self {
+        Self {
+            field_number,
+            values,
+        }
+    }
+}
+
+impl<T> EnumValue<T>
+where
+    T: Copy + Into<u32>,
+{
+    //! Case Number
+
+    /// Gets the encoder for the case number.
+    fn number(&self) -> UnsignedInt32Value {
+        UnsignedInt32Value::new(false, self.value.into())
+    }
+}
+
+impl<T> EncodedLen for EnumValue<T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encoded_len(&self) -> usize {
+        self.number().encoded_len()
+    }
+}
+
+impl<T> EncodeToSlice for EnumValue<T>
+where
+    T: Copy + Into<u32>,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        self.number().encode_to_slice_unchecked(target)
+    }
+}
+
+impl<T> EncodeToWrite for EnumValue<T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        self.number().encode_to_write(w)
+    }
+}
+
+impl<T> EnumField<T>
+where
+    T: Copy + Into<u32>,
+{
+    //! Case Number
+
+    /// Gets the encoder for the case number.
+    fn number(&self) -> UnsignedInt32Field {
+        UnsignedInt32Field::new(self.field_number, false, self.value.map(Into::into))
+    }
+}
+
+impl<T> EncodedLen for EnumField<T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encoded_len(&self) -> usize {
+        self.number().encoded_len()
+    }
+}
+
+impl<T> EncodeToSlice for EnumField<T>
+where
+    T: Copy + Into<u32>,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        self.number().encode_to_slice_unchecked(target)
+    }
+}
+
+impl<T> EncodeToWrite for EnumField<T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        self.number().encode_to_write(w)
+    }
+}
+
+impl<'a, T> EncodedLen for EnumList<'a, T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encoded_len(&self) -> usize {
+        list_len(self.field_number, false, numbers(self.values))
+    }
+}
+
+impl<'a, T> EncodeToSlice for EnumList<'a, T>
+where
+    T: Copy + Into<u32>,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        list_to_slice(self.field_number, false, numbers(self.values), target)
+    }
+}
+
+impl<'a, T> EncodeToWrite for EnumList<'a, T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        list_to_write(self.field_number, false, numbers(self.values), w)
+    }
+}
+
+impl<'a, T> EncodedLen for EnumPacked<'a, T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encoded_len(&self) -> usize {
+        packed_len(self.field_number, false, numbers(self.values))
+    }
+}
+
+impl<'a, T> EncodeToSlice for EnumPacked<'a, T>
+where
+    T: Copy + Into<u32>,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        packed_to_slice(self.field_number, false, numbers(self.values), target)
+    }
+}
+
+impl<'a, T> EncodeToWrite for EnumPacked<'a, T>
+where
+    T: Copy + Into<u32>,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        packed_to_write(self.field_number, false, numbers(self.values), w)
+    }
+}
@@ -0,0 +1,143 @@
+use std::io;
+
+use super::{read, write, EncodeToSlice, EncodeToWrite, EncodedLen, VarIntSize, WireType};
+
+/// The header of an encoded field. (the field number & wire type)
+///
+/// The header is encoded as the var-int `(field_number << 3) | wire_type`. The field number is
+/// absolute, so fields may be written in any order. (it is not a delta from the previous field)
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct FieldHeader {
+    field_number: u32,
+    wire_type: WireType,
+}
+
+impl FieldHeader {
+    //! Construction
+
+    /// Creates a new field header.
+    pub const fn new(field_number: u32, wire_type: WireType) -> Self {
+        Self {
+            field_number,
+            wire_type,
+        }
+    }
+}
+
+impl FieldHeader {
+    //! Properties
+
+    /// Gets the field number.
+    pub const fn field_number(&self) -> u32 {
+        self.field_number
+    }
+
+    /// Gets the wire type.
+    pub const fn wire_type(&self) -> WireType {
+        self.wire_type
+    }
+
+    /// Gets the encoded tag.
+    const fn tag(&self) -> u64 {
+        ((self.field_number as u64) << 3) | (self.wire_type.bits() as u64)
+    }
+}
+
+impl EncodedLen for FieldHeader {
+    fn encoded_len(&self) -> usize {
+        self.tag().var_int_size()
+    }
+}
+
+impl EncodeToSlice for FieldHeader {
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        write::encode_u64_var(self.tag(), target)
+    }
+}
+
+impl EncodeToWrite for FieldHeader {
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        write::write_u64_var(w, self.tag())
+    }
+}
+
+impl FieldHeader {
+    //! Decode
+
+    /// Decodes the field header from the read.
+    ///
+    /// Returns `None` if the read is at the end of the input.
+    pub fn decode_from_read_prefix_optional<R>(r: &mut R) -> Result<Option<Self>, io::Error>
+    where
+        R: io::Read + ?Sized,
+    {
+        let first: u8 = match read::read_byte_optional(r)? {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        let tag: u64 = read::read_u64_var_continued(first, r)?;
+        let wire_type: WireType = WireType::from_bits((tag & 0b111) as u8)
+            .ok_or_else(|| read::invalid_data("invalid wire type"))?;
+        match u32::try_from(tag >> 3) {
+            Ok(field_number) if field_number != 0 => Ok(Some(Self::new(field_number, wire_type))),
+            _ => Err(read::invalid_data("invalid field number")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::runtime::{EncodeToSlice, EncodeToWrite, FieldHeader, WireType};
+
+    #[test]
+    fn encode() {
+        let header: FieldHeader = FieldHeader::new(1, WireType::VarInt);
+        assert_eq!(header.encode_to_vec(), vec![0x08]);
+
+        let header: FieldHeader = FieldHeader::new(16, WireType::LengthPrefixed);
+        assert_eq!(header.encode_to_vec(), vec![0x85, 0x01]);
+
+        let mut written: Vec<u8> = Vec::default();
+        assert_eq!(header.encode_to_write(&mut written).unwrap(), 2);
+        assert_eq!(written, vec![0x85, 0x01]);
+    }
+
+    #[test]
+    fn decode_from_read_prefix_optional() {
+        let test_cases: &[(&[u8], Option<FieldHeader>)] = &[
+            (&[], None),
+            (&[0x08], Some(FieldHeader::new(1, WireType::VarInt))),
+            (
+                &[0x85, 0x01],
+                Some(FieldHeader::new(16, WireType::LengthPrefixed)),
+            ),
+        ];
+        for (bytes, expected) in test_cases {
+            let mut r: &[u8] = bytes;
+            let result: Option<FieldHeader> =
+                FieldHeader::decode_from_read_prefix_optional(&mut r).unwrap();
+            assert_eq!(result, *expected);
+            assert!(r.is_empty());
+        }
+    }
+
+    #[test]
+    fn decode_from_read_prefix_optional_error() {
+        let test_cases: &[(&[u8], io::ErrorKind)] = &[
+            (&[0x0E], io::ErrorKind::InvalidData),
+            (&[0x00], io::ErrorKind::InvalidData),
+            (&[0x88], io::ErrorKind::UnexpectedEof),
+        ];
+        for (bytes, expected) in test_cases {
+            let mut r: &[u8] = bytes;
+            let error: io::Error =
+                FieldHeader::decode_from_read_prefix_optional(&mut r).unwrap_err();
+            assert_eq!(error.kind(), *expected, "bytes={:?}", bytes);
+        }
+    }
+}
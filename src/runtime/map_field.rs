@@ -0,0 +1,89 @@
+use std::io;
+
+use super::{write, EncodeToSlice, EncodeToWrite, EncodedLen, FieldHeader, VarIntSize, WireType};
+
+/// Encodes the entries of a map as a tagged field per entry.
+///
+/// Each entry is length-prefixed & holds the key as field 1 & the value as field 2.
+#[derive(Copy, Clone, Debug)]
+pub struct MapField<I> {
+    field_number: u32,
+    entries: I,
+}
+
+impl<I> MapField<I> {
+    //! Construction
+
+    /// Creates a new map field encoder for the entry encoders.
+    pub const fn new(field_number: u32, entries: I) -> Self {
+        Self {
+            field_number,
+            entries,
+        }
+    }
+
+    /// Gets the entry header.
+    const fn header(&self) -> FieldHeader {
+        FieldHeader::new(self.field_number, WireType::LengthPrefixed)
+    }
+}
+
+impl<I, K, V> EncodedLen for MapField<I>
+where
+    I: Iterator<Item = (K, V)> + Clone,
+    K: EncodedLen,
+    V: EncodedLen,
+{
+    fn encoded_len(&self) -> usize {
+        self.entries
+            .clone()
+            .map(|(key, value)| {
+                let len: usize = key.encoded_len() + value.encoded_len();
+                self.header().encoded_len() + len.var_int_size() + len
+            })
+            .sum()
+    }
+}
+
+impl<I, K, V> EncodeToSlice for MapField<I>
+where
+    I: Iterator<Item = (K, V)> + Clone,
+    K: EncodeToSlice,
+    V: EncodeToSlice,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        let mut encoded_len: usize = 0;
+        for (key, value) in self.entries.clone() {
+            let len: usize = key.encoded_len() + value.encoded_len();
+            encoded_len += self
+                .header()
+                .encode_to_slice_unchecked(&mut target[encoded_len..]);
+            encoded_len += write::encode_u64_var(len as u64, &mut target[encoded_len..]);
+            encoded_len += key.encode_to_slice_unchecked(&mut target[encoded_len..]);
+            encoded_len += value.encode_to_slice_unchecked(&mut target[encoded_len..]);
+        }
+        encoded_len
+    }
+}
+
+impl<I, K, V> EncodeToWrite for MapField<I>
+where
+    I: Iterator<Item = (K, V)> + Clone,
+    K: EncodedLen + EncodeToWrite,
+    V: EncodedLen + EncodeToWrite,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        let mut encoded_len: usize = 0;
+        for (key, value) in self.entries.clone() {
+            let len: usize = key.encoded_len() + value.encoded_len();
+            encoded_len += self.header().encode_to_write(w)?;
+            encoded_len += write::write_u64_var(w, len as u64)?;
+            encoded_len += key.encode_to_write(w)?;
+            encoded_len += value.encode_to_write(w)?;
+        }
+        Ok(encoded_len)
+    }
+}
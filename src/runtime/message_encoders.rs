@@ -0,0 +1,191 @@
+use std::io;
+
+use super::{write, EncodeToSlice, EncodeToWrite, EncodedLen, FieldHeader, VarIntSize, WireType};
+
+/// Encodes a required message value with its length prefix. (without a field header)
+///
+/// Variants are encoded like messages.
+#[derive(Copy, Clone, Debug)]
+pub struct MessageValue<'a, T> {
+    value: &'a T,
+}
+
+/// Encodes an optional message value as a tagged field.
+#[derive(Copy, Clone, Debug)]
+pub struct MessageField<'a, T> {
+    field_number: u32,
+    value: Option<&'a T>,
+}
+
+/// Encodes a list of message values as a tagged field per value.
+#[derive(Copy, Clone, Debug)]
+pub struct MessageList<'a, T> {
+    field_number: u32,
+    values: &'a [T],
+}
+
+impl<'a, T> MessageValue<'a, T> {
+    //! Construction
+
+    /// Creates a new value encoder.
+    pub const fn new(value: &'a T) -> Self {
+        Self { value }
+    }
+}
+
+impl<'a, T> MessageField<'a, T> {
+    //! Construction
+
+    /// Creates a new field encoder.
+    pub const fn new(field_number: u32, value: Option<&'a T>) -> Self {
+        Self {
+            field_number,
+            value,
+        }
+    }
+}
+
+impl<'a, T> MessageList<'a, T> {
+    //! Construction
+
+    /// Creates a new list encoder.
+    pub const fn new(field_number: u32, values: &'a [T]) -> Self {
+        Self {
+            field_number,
+            values,
+        }
+    }
+}
+
+impl<'a, T> EncodedLen for MessageValue<'a, T>
+where
+    T: EncodedLen,
+{
+    fn encoded_len(&self) -> usize {
+        let len: usize = self.value.encoded_len();
+        len.var_int_size() + len
+    }
+}
+
+impl<'a, T> EncodeToSlice for MessageValue<'a, T>
+where
+    T: EncodeToSlice,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        let len: usize = self.value.encoded_len();
+        let prefix_len: usize = write::encode_u64_var(len as u64, target);
+        prefix_len
+            + self
+                .value
+                .encode_to_slice_unchecked(&mut target[prefix_len..])
+    }
+}
+
+impl<'a, T> EncodeToWrite for MessageValue<'a, T>
+where
+    T: EncodedLen + EncodeToWrite,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        write::write_length_prefixed(w, self.value)
+    }
+}
+
+impl<'a, T> MessageField<'a, T> {
+    //! Header
+
+    /// Gets the field header.
+    const fn header(&self) -> FieldHeader {
+        FieldHeader::new(self.field_number, WireType::LengthPrefixed)
+    }
+}
+
+impl<'a, T> EncodedLen for MessageField<'a, T>
+where
+    T: EncodedLen,
+{
+    fn encoded_len(&self) -> usize {
+        match self.value {
+            Some(value) => self.header().encoded_len() + MessageValue::new(value).encoded_len(),
+            None => 0,
+        }
+    }
+}
+
+impl<'a, T> EncodeToSlice for MessageField<'a, T>
+where
+    T: EncodeToSlice,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        match self.value {
+            Some(value) => {
+                let encoded_len: usize = self.header().encode_to_slice_unchecked(target);
+                encoded_len
+                    + MessageValue::new(value).encode_to_slice_unchecked(&mut target[encoded_len..])
+            }
+            None => 0,
+        }
+    }
+}
+
+impl<'a, T> EncodeToWrite for MessageField<'a, T>
+where
+    T: EncodedLen + EncodeToWrite,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        match self.value {
+            Some(value) => {
+                Ok(self.header().encode_to_write(w)?
+                    + MessageValue::new(value).encode_to_write(w)?)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl<'a, T> EncodedLen for MessageList<'a, T>
+where
+    T: EncodedLen,
+{
+    fn encoded_len(&self) -> usize {
+        self.values
+            .iter()
+            .map(|value| MessageField::new(self.field_number, Some(value)).encoded_len())
+            .sum()
+    }
+}
+
+impl<'a, T> EncodeToSlice for MessageList<'a, T>
+where
+    T: EncodeToSlice,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        let mut encoded_len: usize = 0;
+        for value in self.values {
+            encoded_len += MessageField::new(self.field_number, Some(value))
+                .encode_to_slice_unchecked(&mut target[encoded_len..]);
+        }
+        encoded_len
+    }
+}
+
+impl<'a, T> EncodeToWrite for MessageList<'a, T>
+where
+    T: EncodedLen + EncodeToWrite,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        let mut encoded_len: usize = 0;
+        for value in self.values {
+            encoded_len += MessageField::new(self.field_number, Some(value)).encode_to_write(w)?;
+        }
+        Ok(encoded_len)
+    }
+}
@@ -0,0 +1,32 @@
+//! The runtime targeted by the generated code. (requires the `runtime` feature)
+
+pub use byte_string_encoders::*;
+pub use decode::*;
+pub use encode::*;
+pub use enum_encoders::*;
+pub use field_header::*;
+pub use map_field::*;
+pub use message_encoders::*;
+pub use raw_bytes::*;
+pub use read_limit::*;
+pub use scalar::*;
+pub use scalar_encoders::*;
+pub use var_int_size::*;
+pub use wire_type::*;
+
+mod byte_string_encoders;
+mod decode;
+mod encode;
+mod enum_encoders;
+mod field_header;
+mod map_field;
+mod message_encoders;
+mod raw_bytes;
+mod read_limit;
+mod scalar;
+mod scalar_encoders;
+mod var_int_size;
+mod wire_type;
+
+pub mod read;
+pub mod write;
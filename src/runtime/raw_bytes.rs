@@ -0,0 +1,41 @@
+use std::io;
+
+use super::{EncodeToSlice, EncodeToWrite, EncodedLen};
+
+/// Encodes already encoded bytes as is.
+#[derive(Copy, Clone, Debug)]
+pub struct RawBytes<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> RawBytes<'a> {
+    //! Construction
+
+    /// Creates a new raw bytes.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> EncodedLen for RawBytes<'a> {
+    fn encoded_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+impl<'a> EncodeToSlice for RawBytes<'a> {
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        target[..self.bytes.len()].copy_from_slice(self.bytes);
+        self.bytes.len()
+    }
+}
+
+impl<'a> EncodeToWrite for RawBytes<'a> {
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        w.write_all(self.bytes)?;
+        Ok(self.bytes.len())
+    }
+}
@@ -0,0 +1,519 @@
+//! Functions reading encoded values.
+
+use std::io;
+use std::io::Read;
+
+use super::{write, EncodeToSlice, FieldHeader, ReadLimit, WireType};
+
+/// Creates an invalid data error with the message.
+pub fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Converts the end of input error to an error for the missing required field.
+///
+/// A missing required field is only known when decoding, so it is reported by the runtime as an
+/// `InvalidData` error. (the generated code has no `GenError` at runtime, which only reports
+/// schemas the generator cannot generate, such as required lists & maps)
+pub fn missing_required_field(e: io::Error, name: &str) -> io::Error {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("missing required field: {}", name),
+        )
+    } else {
+        e
+    }
+}
+
+/// Reads a single byte. Returns `None` at the end of the input.
+pub fn read_byte_optional<R>(r: &mut R) -> Result<Option<u8>, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let mut buffer: [u8; 1] = [0];
+    loop {
+        match r.read(&mut buffer) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(buffer[0])),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Reads the fixed number of bytes.
+fn read_array<R, const N: usize>(r: &mut R) -> Result<[u8; N], io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let mut buffer: [u8; N] = [0; N];
+    r.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Reads the bytes of a var-int value. (the first byte has already been read)
+fn read_var_int_bytes<R>(first: u8, r: &mut R, target: &mut Vec<u8>) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let mut byte: u8 = first;
+    target.push(byte);
+    while byte & 0x80 != 0 {
+        if target.len() == write::MAX_VAR_INT_SIZE {
+            return Err(invalid_data("var-int too long"));
+        }
+        byte = read_array::<R, 1>(r)?[0];
+        target.push(byte);
+    }
+    Ok(())
+}
+
+/// Reads a var-int value. (the first byte has already been read)
+pub fn read_u64_var_continued<R>(first: u8, r: &mut R) -> Result<u64, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let mut bytes: Vec<u8> = Vec::with_capacity(write::MAX_VAR_INT_SIZE);
+    read_var_int_bytes(first, r, &mut bytes)?;
+    let mut value: u64 = 0;
+    for (i, byte) in bytes.iter().enumerate() {
+        let bits: u64 = (byte & 0x7F) as u64;
+        if i == write::MAX_VAR_INT_SIZE - 1 && bits > 1 {
+            return Err(invalid_data("var-int overflow"));
+        }
+        value |= bits << (7 * i);
+    }
+    Ok(value)
+}
+
+/// Decodes the zig-zag encoded value.
+const fn decode_zig_zag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Reads a `u8` value.
+pub fn read_u8<R>(r: &mut R) -> Result<u8, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(read_array::<R, 1>(r)?[0])
+}
+
+/// Reads an `i8` value.
+pub fn read_i8<R>(r: &mut R) -> Result<i8, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(read_u8(r)? as i8)
+}
+
+/// Reads a `bool` value.
+pub fn read_bool<R>(r: &mut R) -> Result<bool, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    match read_u8(r)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(invalid_data("invalid bool")),
+    }
+}
+
+/// Reads a var-int `u16` value.
+pub fn read_u16_var<R>(r: &mut R) -> Result<u16, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    u16::try_from(read_u64_var(r)?).map_err(|_| invalid_data("var-int overflow"))
+}
+
+/// Reads a fixed `u16` value.
+pub fn read_u16_fixed<R>(r: &mut R) -> Result<u16, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(u16::from_le_bytes(read_array(r)?))
+}
+
+/// Reads a var-int `i16` value. (zig-zag encoded)
+pub fn read_i16_var<R>(r: &mut R) -> Result<i16, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    i16::try_from(decode_zig_zag(read_u64_var(r)?)).map_err(|_| invalid_data("var-int overflow"))
+}
+
+/// Reads a fixed `i16` value.
+pub fn read_i16_fixed<R>(r: &mut R) -> Result<i16, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(i16::from_le_bytes(read_array(r)?))
+}
+
+/// Reads a var-int `u32` value.
+pub fn read_u32_var<R>(r: &mut R) -> Result<u32, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    u32::try_from(read_u64_var(r)?).map_err(|_| invalid_data("var-int overflow"))
+}
+
+/// Reads a fixed `u32` value.
+pub fn read_u32_fixed<R>(r: &mut R) -> Result<u32, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(u32::from_le_bytes(read_array(r)?))
+}
+
+/// Reads a var-int `i32` value. (zig-zag encoded)
+pub fn read_i32_var<R>(r: &mut R) -> Result<i32, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    i32::try_from(decode_zig_zag(read_u64_var(r)?)).map_err(|_| invalid_data("var-int overflow"))
+}
+
+/// Reads a fixed `i32` value.
+pub fn read_i32_fixed<R>(r: &mut R) -> Result<i32, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(i32::from_le_bytes(read_array(r)?))
+}
+
+/// Reads a var-int `u64` value.
+pub fn read_u64_var<R>(r: &mut R) -> Result<u64, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let first: u8 = read_u8(r)?;
+    read_u64_var_continued(first, r)
+}
+
+/// Reads a fixed `u64` value.
+pub fn read_u64_fixed<R>(r: &mut R) -> Result<u64, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(u64::from_le_bytes(read_array(r)?))
+}
+
+/// Reads a var-int `i64` value. (zig-zag encoded)
+pub fn read_i64_var<R>(r: &mut R) -> Result<i64, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(decode_zig_zag(read_u64_var(r)?))
+}
+
+/// Reads a fixed `i64` value.
+pub fn read_i64_fixed<R>(r: &mut R) -> Result<i64, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(i64::from_le_bytes(read_array(r)?))
+}
+
+/// Reads an `f32` value.
+pub fn read_f32<R>(r: &mut R) -> Result<f32, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(f32::from_le_bytes(read_array(r)?))
+}
+
+/// Reads an `f64` value.
+pub fn read_f64<R>(r: &mut R) -> Result<f64, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    Ok(f64::from_le_bytes(read_array(r)?))
+}
+
+/// Reads a var-int length.
+pub fn read_length<R>(r: &mut R) -> Result<usize, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    usize::try_from(read_u64_var(r)?).map_err(|_| invalid_data("length overflow"))
+}
+
+/// Reads the number of bytes to the end of the target.
+fn read_exact_len<R>(r: &mut R, len: usize, target: &mut Vec<u8>) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let read: usize = (&mut *r).take(len as u64).read_to_end(target)?;
+    if read == len {
+        Ok(())
+    } else {
+        Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+    }
+}
+
+/// Reads a length-prefixed byte string.
+pub fn read_bytes<R>(r: &mut R) -> Result<Vec<u8>, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    let len: usize = read_length(r)?;
+    let mut bytes: Vec<u8> = Vec::default();
+    read_exact_len(r, len, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads a length-prefixed UTF-8 string.
+pub fn read_string<R>(r: &mut R) -> Result<String, io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    String::from_utf8(read_bytes(r)?).map_err(|_| invalid_data("invalid utf-8"))
+}
+
+/// Reads the elements of a list field & pushes them to the target.
+///
+/// Packed fields read elements to the end of the length prefix. Unpacked fields read a single
+/// element.
+pub fn read_packable<'r, R, T, F>(
+    r: &'r mut R,
+    wire_type: WireType,
+    mut read: F,
+    target: &mut Vec<T>,
+) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+    F: FnMut(&mut ReadLimit<&'r mut R>) -> Result<T, io::Error>,
+{
+    if wire_type == WireType::LengthPrefixed {
+        let len: usize = read_length(r)?;
+        let mut limit: ReadLimit<&'r mut R> = ReadLimit::new(r, len);
+        while limit.remaining() != 0 {
+            target.push(read(&mut limit)?);
+        }
+    } else {
+        let mut limit: ReadLimit<&'r mut R> = ReadLimit::new(r, usize::MAX);
+        target.push(read(&mut limit)?);
+    }
+    Ok(())
+}
+
+/// Reads a length-prefixed map entry & extends the target with it.
+///
+/// The key is field 1 & the value is field 2. Missing keys & values are defaulted.
+pub fn read_map_entry<'r, R, K, V, FK, FV, E>(
+    r: &'r mut R,
+    mut read_key: FK,
+    mut read_value: FV,
+    target: &mut E,
+) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+    K: Default,
+    V: Default,
+    FK: FnMut(&mut ReadLimit<&'r mut R>) -> Result<K, io::Error>,
+    FV: FnMut(&mut ReadLimit<&'r mut R>) -> Result<V, io::Error>,
+    E: Extend<(K, V)>,
+{
+    let len: usize = read_length(r)?;
+    let mut limit: ReadLimit<&'r mut R> = ReadLimit::new(r, len);
+    let mut key: K = K::default();
+    let mut value: V = V::default();
+    while let Some(header) = FieldHeader::decode_from_read_prefix_optional(&mut limit)? {
+        match header.field_number() {
+            1 => key = read_key(&mut limit)?,
+            2 => value = read_value(&mut limit)?,
+            _ => skip_value(&mut limit, header.wire_type())?,
+        }
+    }
+    if limit.remaining() != 0 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    target.extend([(key, value)]);
+    Ok(())
+}
+
+/// Skips the value with the wire type.
+pub fn skip_value<R>(r: &mut R, wire_type: WireType) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    copy_value(r, wire_type, &mut Vec::default())
+}
+
+/// Copies the encoded field to the end of the target. (the header has already been read)
+pub fn copy_field<R>(r: &mut R, header: &FieldHeader, target: &mut Vec<u8>) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    target.extend_from_slice(header.encode_to_vec().as_slice());
+    copy_value(r, header.wire_type(), target)
+}
+
+/// Copies the encoded value with the wire type to the end of the target.
+fn copy_value<R>(r: &mut R, wire_type: WireType, target: &mut Vec<u8>) -> Result<(), io::Error>
+where
+    R: io::Read + ?Sized,
+{
+    match wire_type {
+        WireType::VarInt => {
+            let first: u8 = read_u8(r)?;
+            let mut bytes: Vec<u8> = Vec::default();
+            read_var_int_bytes(first, r, &mut bytes)?;
+            target.extend_from_slice(bytes.as_slice());
+            Ok(())
+        }
+        WireType::LengthPrefixed => {
+            let len: usize = read_length(r)?;
+            write::write_u64_var(target, len as u64)?;
+            read_exact_len(r, len, target)
+        }
+        fixed => read_exact_len(r, fixed.fixed_len().unwrap_or_default(), target),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io;
+
+    use crate::runtime::read::*;
+    use crate::runtime::{
+        EncodeToSlice, FieldHeader, SignedInt32Value, SignedInt64Value, UnsignedInt16Value,
+        UnsignedInt32Field, UnsignedInt32Packed, UnsignedInt64Value, WireType,
+    };
+
+    #[test]
+    fn read_var() {
+        for value in [0, 1, 0x7F, 0x80, 300, u64::MAX] {
+            let bytes: Vec<u8> = UnsignedInt64Value::new(false, value).encode_to_vec();
+            assert_eq!(read_u64_var(&mut bytes.as_slice()).unwrap(), value);
+        }
+
+        let bytes: Vec<u8> = UnsignedInt16Value::new(false, u16::MAX).encode_to_vec();
+        assert_eq!(read_u16_var(&mut bytes.as_slice()).unwrap(), u16::MAX);
+        let bytes: Vec<u8> = UnsignedInt64Value::new(false, 0x10000).encode_to_vec();
+        let error: io::Error = read_u16_var(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_var_invalid() {
+        let test_cases: &[&[u8]] = &[
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02],
+            &[
+                0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00,
+            ],
+        ];
+        for bytes in test_cases {
+            let error: io::Error = read_u64_var(&mut &bytes[..]).unwrap_err();
+            assert_eq!(
+                error.kind(),
+                io::ErrorKind::InvalidData,
+                "bytes={:?}",
+                bytes
+            );
+        }
+        let error: io::Error = read_u64_var(&mut &[0x80][..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn read_zig_zag() {
+        let test_cases: &[(i64, &[u8])] = &[
+            (0, &[0x00]),
+            (-1, &[0x01]),
+            (1, &[0x02]),
+            (-2, &[0x03]),
+            (63, &[0x7E]),
+            (-64, &[0x7F]),
+        ];
+        for (value, expected) in test_cases {
+            let bytes: Vec<u8> = SignedInt64Value::new(false, *value).encode_to_vec();
+            assert_eq!(bytes, *expected, "value={}", value);
+            assert_eq!(read_i64_var(&mut bytes.as_slice()).unwrap(), *value);
+        }
+        for value in [i32::MIN, i32::MAX] {
+            let bytes: Vec<u8> = SignedInt32Value::new(false, value).encode_to_vec();
+            assert_eq!(read_i32_var(&mut bytes.as_slice()).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn read_bool_invalid() {
+        assert!(read_bool(&mut &[0x01][..]).unwrap());
+        let error: io::Error = read_bool(&mut &[0x02][..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_string_value() {
+        let mut r: &[u8] = &[0x02, b'h', b'i', 0x01];
+        assert_eq!(read_string(&mut r).unwrap(), "hi");
+        assert_eq!(r, &[0x01]);
+
+        let error: io::Error = read_string(&mut &[0x03, b'h', b'i'][..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        let error: io::Error = read_string(&mut &[0x01, 0xFF][..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn missing_required_field_error() {
+        let error: io::Error = read_u8(&mut &[][..])
+            .map_err(|e| missing_required_field(e, "name"))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "missing required field: name");
+    }
+
+    #[test]
+    fn read_packable_values() {
+        let values: &[u32] = &[1, 300, u32::MAX];
+        let bytes: Vec<u8> = UnsignedInt32Packed::new(1, false, values).encode_to_vec();
+        let mut r: &[u8] = bytes.as_slice();
+        let header: FieldHeader = FieldHeader::decode_from_read_prefix_optional(&mut r)
+            .unwrap()
+            .unwrap();
+        let mut result: Vec<u32> = Vec::default();
+        read_packable(&mut r, header.wire_type(), read_u32_var, &mut result).unwrap();
+        assert_eq!(result, values);
+        assert!(r.is_empty());
+
+        let mut r: &[u8] = &[0xAC, 0x02];
+        read_packable(&mut r, WireType::VarInt, read_u32_var, &mut result).unwrap();
+        assert_eq!(result, [1, 300, u32::MAX, 300]);
+    }
+
+    #[test]
+    fn read_map_entry_values() {
+        let mut bytes: Vec<u8> = vec![0x07];
+        bytes.extend(UnsignedInt32Field::new(3, false, Some(7)).encode_to_vec());
+        bytes.extend(UnsignedInt32Field::new(2, false, Some(300)).encode_to_vec());
+        bytes.extend([0x09, 0x01]);
+        let mut r: &[u8] = bytes.as_slice();
+        let mut result: HashMap<u8, u32> = HashMap::default();
+        read_map_entry(&mut r, read_u8, read_u32_var, &mut result).unwrap();
+        assert_eq!(result, HashMap::from([(1, 300)]));
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn copy_and_skip_fields() {
+        let mut bytes: Vec<u8> = UnsignedInt32Field::new(1, false, Some(300)).encode_to_vec();
+        bytes.extend([0x2D, 0x02, b'h', b'i']);
+        bytes.extend(UnsignedInt32Field::new(3, true, Some(7)).encode_to_vec());
+        let mut r: &[u8] = bytes.as_slice();
+        let mut copied: Vec<u8> = Vec::default();
+        while let Some(header) = FieldHeader::decode_from_read_prefix_optional(&mut r).unwrap() {
+            if header.field_number() == 5 {
+                copy_field(&mut r, &header, &mut copied).unwrap();
+            } else {
+                skip_value(&mut r, header.wire_type()).unwrap();
+            }
+        }
+        assert_eq!(copied, vec![0x2D, 0x02, b'h', b'i']);
+    }
+}
@@ -0,0 +1,48 @@
+use std::io;
+
+/// A read limited to a number of bytes.
+///
+/// The limit reads as the end of the input once the bytes have been read.
+#[derive(Debug)]
+pub struct ReadLimit<R> {
+    inner: R,
+    remaining: usize,
+}
+
+impl<R> ReadLimit<R> {
+    //! Construction
+
+    /// Creates a new read limit.
+    pub const fn new(inner: R, limit: usize) -> Self {
+        Self {
+            inner,
+            remaining: limit,
+        }
+    }
+}
+
+impl<R> ReadLimit<R> {
+    //! Properties
+
+    /// Gets the number of bytes remaining.
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Converts the limit to the inner read.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> io::Read for ReadLimit<R>
+where
+    R: io::Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max: usize = buf.len().min(self.remaining);
+        let read: usize = self.inner.read(&mut buf[..max])?;
+        self.remaining -= read;
+        Ok(read)
+    }
+}
@@ -0,0 +1,343 @@
+use std::io;
+
+use super::{write, EncodeToSlice, EncodeToWrite, EncodedLen, FieldHeader, VarIntSize, WireType};
+
+/// The maximum encoded length of a scalar value.
+pub const MAX_SCALAR_LEN: usize = write::MAX_VAR_INT_SIZE;
+
+/// A primitive value encoded as a var-int or fixed-length value.
+pub trait Scalar: Copy {
+    /// Gets the wire type of the encoding.
+    fn wire_type(fixed: bool) -> WireType;
+
+    /// Encodes the value to the start of the buffer. Returns the encoded length.
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize;
+}
+
+/// Encodes the zig-zag encoding of the value.
+const fn encode_zig_zag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Copies the bytes to the start of the buffer. Returns the encoded length.
+fn encode_bytes<const N: usize>(bytes: [u8; N], buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+    buffer[..N].copy_from_slice(&bytes);
+    N
+}
+
+impl Scalar for u8 {
+    fn wire_type(_fixed: bool) -> WireType {
+        WireType::Fixed1Byte
+    }
+
+    fn encode_scalar(self, _fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        encode_bytes(self.to_le_bytes(), buffer)
+    }
+}
+
+impl Scalar for i8 {
+    fn wire_type(_fixed: bool) -> WireType {
+        WireType::Fixed1Byte
+    }
+
+    fn encode_scalar(self, _fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        encode_bytes(self.to_le_bytes(), buffer)
+    }
+}
+
+impl Scalar for bool {
+    fn wire_type(_fixed: bool) -> WireType {
+        WireType::Fixed1Byte
+    }
+
+    fn encode_scalar(self, _fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        encode_bytes([self as u8], buffer)
+    }
+}
+
+impl Scalar for u16 {
+    fn wire_type(fixed: bool) -> WireType {
+        if fixed {
+            WireType::Fixed2Byte
+        } else {
+            WireType::VarInt
+        }
+    }
+
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        if fixed {
+            encode_bytes(self.to_le_bytes(), buffer)
+        } else {
+            write::encode_u64_var(self as u64, buffer)
+        }
+    }
+}
+
+impl Scalar for i16 {
+    fn wire_type(fixed: bool) -> WireType {
+        if fixed {
+            WireType::Fixed2Byte
+        } else {
+            WireType::VarInt
+        }
+    }
+
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        if fixed {
+            encode_bytes(self.to_le_bytes(), buffer)
+        } else {
+            write::encode_u64_var(encode_zig_zag(self as i64), buffer)
+        }
+    }
+}
+
+impl Scalar for u32 {
+    fn wire_type(fixed: bool) -> WireType {
+        if fixed {
+            WireType::Fixed4Byte
+        } else {
+            WireType::VarInt
+        }
+    }
+
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        if fixed {
+            encode_bytes(self.to_le_bytes(), buffer)
+        } else {
+            write::encode_u64_var(self as u64, buffer)
+        }
+    }
+}
+
+impl Scalar for i32 {
+    fn wire_type(fixed: bool) -> WireType {
+        if fixed {
+            WireType::Fixed4Byte
+        } else {
+            WireType::VarInt
+        }
+    }
+
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        if fixed {
+            encode_bytes(self.to_le_bytes(), buffer)
+        } else {
+            write::encode_u64_var(encode_zig_zag(self as i64), buffer)
+        }
+    }
+}
+
+impl Scalar for u64 {
+    fn wire_type(fixed: bool) -> WireType {
+        if fixed {
+            WireType::Fixed8Byte
+        } else {
+            WireType::VarInt
+        }
+    }
+
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        if fixed {
+            encode_bytes(self.to_le_bytes(), buffer)
+        } else {
+            write::encode_u64_var(self, buffer)
+        }
+    }
+}
+
+impl Scalar for i64 {
+    fn wire_type(fixed: bool) -> WireType {
+        if fixed {
+            WireType::Fixed8Byte
+        } else {
+            WireType::VarInt
+        }
+    }
+
+    fn encode_scalar(self, fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        if fixed {
+            encode_bytes(self.to_le_bytes(), buffer)
+        } else {
+            write::encode_u64_var(encode_zig_zag(self), buffer)
+        }
+    }
+}
+
+impl Scalar for f32 {
+    fn wire_type(_fixed: bool) -> WireType {
+        WireType::Fixed4Byte
+    }
+
+    fn encode_scalar(self, _fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        encode_bytes(self.to_le_bytes(), buffer)
+    }
+}
+
+impl Scalar for f64 {
+    fn wire_type(_fixed: bool) -> WireType {
+        WireType::Fixed8Byte
+    }
+
+    fn encode_scalar(self, _fixed: bool, buffer: &mut [u8; MAX_SCALAR_LEN]) -> usize {
+        encode_bytes(self.to_le_bytes(), buffer)
+    }
+}
+
+/// Gets the encoded length of the scalar value.
+pub(crate) fn scalar_len<T>(value: T, fixed: bool) -> usize
+where
+    T: Scalar,
+{
+    value.encode_scalar(fixed, &mut [0; MAX_SCALAR_LEN])
+}
+
+/// Encodes the scalar value to the start of the target. Returns the encoded length.
+pub(crate) fn scalar_to_slice<T>(value: T, fixed: bool, target: &mut [u8]) -> usize
+where
+    T: Scalar,
+{
+    let mut buffer: [u8; MAX_SCALAR_LEN] = [0; MAX_SCALAR_LEN];
+    let encoded_len: usize = value.encode_scalar(fixed, &mut buffer);
+    target[..encoded_len].copy_from_slice(&buffer[..encoded_len]);
+    encoded_len
+}
+
+/// Writes the scalar value. Returns the encoded length.
+pub(crate) fn scalar_to_write<T, W>(value: T, fixed: bool, w: &mut W) -> Result<usize, io::Error>
+where
+    T: Scalar,
+    W: io::Write,
+{
+    let mut buffer: [u8; MAX_SCALAR_LEN] = [0; MAX_SCALAR_LEN];
+    let encoded_len: usize = value.encode_scalar(fixed, &mut buffer);
+    w.write_all(&buffer[..encoded_len])?;
+    Ok(encoded_len)
+}
+
+/// Gets the encoded length of the scalar values as a tagged field per value.
+pub(crate) fn list_len<T, I>(field_number: u32, fixed: bool, values: I) -> usize
+where
+    T: Scalar,
+    I: Iterator<Item = T>,
+{
+    let header: FieldHeader = FieldHeader::new(field_number, T::wire_type(fixed));
+    values
+        .map(|value| header.encoded_len() + scalar_len(value, fixed))
+        .sum()
+}
+
+/// Encodes the scalar values as a tagged field per value. Returns the encoded length.
+pub(crate) fn list_to_slice<T, I>(
+    field_number: u32,
+    fixed: bool,
+    values: I,
+    target: &mut [u8],
+) -> usize
+where
+    T: Scalar,
+    I: Iterator<Item = T>,
+{
+    let header: FieldHeader = FieldHeader::new(field_number, T::wire_type(fixed));
+    let mut encoded_len: usize = 0;
+    for value in values {
+        encoded_len += unsafe { header.encode_to_slice_unchecked(&mut target[encoded_len..]) };
+        encoded_len += scalar_to_slice(value, fixed, &mut target[encoded_len..]);
+    }
+    encoded_len
+}
+
+/// Writes the scalar values as a tagged field per value. Returns the encoded length.
+pub(crate) fn list_to_write<T, I, W>(
+    field_number: u32,
+    fixed: bool,
+    values: I,
+    w: &mut W,
+) -> Result<usize, io::Error>
+where
+    T: Scalar,
+    I: Iterator<Item = T>,
+    W: io::Write,
+{
+    let header: FieldHeader = FieldHeader::new(field_number, T::wire_type(fixed));
+    let mut encoded_len: usize = 0;
+    for value in values {
+        encoded_len += header.encode_to_write(w)?;
+        encoded_len += scalar_to_write(value, fixed, w)?;
+    }
+    Ok(encoded_len)
+}
+
+/// Gets the length of the packed values. (without the header & length prefix)
+fn packed_values_len<T, I>(fixed: bool, values: I) -> usize
+where
+    T: Scalar,
+    I: Iterator<Item = T>,
+{
+    values.map(|value| scalar_len(value, fixed)).sum()
+}
+
+/// Gets the encoded length of the scalar values as a single length-prefixed field.
+///
+/// Empty lists are not encoded.
+pub(crate) fn packed_len<T, I>(field_number: u32, fixed: bool, values: I) -> usize
+where
+    T: Scalar,
+    I: Iterator<Item = T> + Clone,
+{
+    if values.clone().next().is_none() {
+        return 0;
+    }
+    let len: usize = packed_values_len(fixed, values);
+    FieldHeader::new(field_number, WireType::LengthPrefixed).encoded_len()
+        + len.var_int_size()
+        + len
+}
+
+/// Encodes the scalar values as a single length-prefixed field. Returns the encoded length.
+pub(crate) fn packed_to_slice<T, I>(
+    field_number: u32,
+    fixed: bool,
+    values: I,
+    target: &mut [u8],
+) -> usize
+where
+    T: Scalar,
+    I: Iterator<Item = T> + Clone,
+{
+    if values.clone().next().is_none() {
+        return 0;
+    }
+    let header: FieldHeader = FieldHeader::new(field_number, WireType::LengthPrefixed);
+    let mut encoded_len: usize = unsafe { header.encode_to_slice_unchecked(target) };
+    let len: usize = packed_values_len(fixed, values.clone());
+    encoded_len += write::encode_u64_var(len as u64, &mut target[encoded_len..]);
+    for value in values {
+        encoded_len += scalar_to_slice(value, fixed, &mut target[encoded_len..]);
+    }
+    encoded_len
+}
+
+/// Writes the scalar values as a single length-prefixed field. Returns the encoded length.
+pub(crate) fn packed_to_write<T, I, W>(
+    field_number: u32,
+    fixed: bool,
+    values: I,
+    w: &mut W,
+) -> Result<usize, io::Error>
+where
+    T: Scalar,
+    I: Iterator<Item = T> + Clone,
+    W: io::Write,
+{
+    if values.clone().next().is_none() {
+        return Ok(0);
+    }
+    let header: FieldHeader = FieldHeader::new(field_number, WireType::LengthPrefixed);
+    let mut encoded_len: usize = header.encode_to_write(w)?;
+    let len: usize = packed_values_len(fixed, values.clone());
+    encoded_len += write::write_u64_var(w, len as u64)?;
+    for value in values {
+        encoded_len += scalar_to_write(value, fixed, w)?;
+    }
+    Ok(encoded_len)
+}
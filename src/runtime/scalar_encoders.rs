@@ -0,0 +1,383 @@
+use std::io;
+
+use super::scalar::{
+    list_len, list_to_slice, list_to_write, packed_len, packed_to_slice, packed_to_write,
+    scalar_len, scalar_to_slice, scalar_to_write,
+};
+use super::{EncodeToSlice, EncodeToWrite, EncodedLen, FieldHeader, Scalar};
+
+/// Encodes a required scalar value. (without a field header)
+#[derive(Copy, Clone, Debug)]
+pub struct ScalarValue<T> {
+    fixed: bool,
+    value: T,
+}
+
+/// Encodes an optional scalar value as a tagged field.
+#[derive(Copy, Clone, Debug)]
+pub struct ScalarField<T> {
+    field_number: u32,
+    fixed: bool,
+    value: Option<T>,
+}
+
+/// Encodes a list of scalar values as a tagged field per value.
+#[derive(Copy, Clone, Debug)]
+pub struct ScalarList<'a, T> {
+    field_number: u32,
+    fixed: bool,
+    values: &'a [T],
+}
+
+/// Encodes a list of scalar values as a single length-prefixed field.
+#[derive(Copy, Clone, Debug)]
+pub struct ScalarPacked<'a, T> {
+    field_number: u32,
+    fixed: bool,
+    values: &'a [T],
+}
+
+impl<T> EncodedLen for ScalarValue<T>
+where
+    T: Scalar,
+{
+    fn encoded_len(&self) -> usize {
+        scalar_len(self.value, self.fixed)
+    }
+}
+
+impl<T> EncodeToSlice for ScalarValue<T>
+where
+    T: Scalar,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        scalar_to_slice(self.value, self.fixed, target)
+    }
+}
+
+impl<T> EncodeToWrite for ScalarValue<T>
+where
+    T: Scalar,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        scalar_to_write(self.value, self.fixed, w)
+    }
+}
+
+impl<T> ScalarField<T>
+where
+    T: Scalar,
+{
+    //! Header
+
+    /// Gets the field header.
+    fn header(&self) -> FieldHeader {
+        FieldHeader::new(self.field_number, T::wire_type(self.fixed))
+    }
+}
+
+impl<T> EncodedLen for ScalarField<T>
+where
+    T: Scalar,
+{
+    fn encoded_len(&self) -> usize {
+        match self.value {
+            Some(value) => self.header().encoded_len() + scalar_len(value, self.fixed),
+            None => 0,
+        }
+    }
+}
+
+impl<T> EncodeToSlice for ScalarField<T>
+where
+    T: Scalar,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        match self.value {
+            Some(value) => {
+                let encoded_len: usize = self.header().encode_to_slice_unchecked(target);
+                encoded_len + scalar_to_slice(value, self.fixed, &mut target[encoded_len..])
+            }
+            None => 0,
+        }
+    }
+}
+
+impl<T> EncodeToWrite for ScalarField<T>
+where
+    T: Scalar,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        match self.value {
+            Some(value) => {
+                Ok(self.header().encode_to_write(w)? + scalar_to_write(value, self.fixed, w)?)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl<'a, T> EncodedLen for ScalarList<'a, T>
+where
+    T: Scalar,
+{
+    fn encoded_len(&self) -> usize {
+        list_len(self.field_number, self.fixed, self.values.iter().copied())
+    }
+}
+
+impl<'a, T> EncodeToSlice for ScalarList<'a, T>
+where
+    T: Scalar,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        list_to_slice(
+            self.field_number,
+            self.fixed,
+            self.values.iter().copied(),
+            target,
+        )
+    }
+}
+
+impl<'a, T> EncodeToWrite for ScalarList<'a, T>
+where
+    T: Scalar,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        list_to_write(
+            self.field_number,
+            self.fixed,
+            self.values.iter().copied(),
+            w,
+        )
+    }
+}
+
+impl<'a, T> EncodedLen for ScalarPacked<'a, T>
+where
+    T: Scalar,
+{
+    fn encoded_len(&self) -> usize {
+        packed_len(self.field_number, self.fixed, self.values.iter().copied())
+    }
+}
+
+impl<'a, T> EncodeToSlice for ScalarPacked<'a, T>
+where
+    T: Scalar,
+{
+    unsafe fn encode_to_slice_unchecked(&self, target: &mut [u8]) -> usize {
+        packed_to_slice(
+            self.field_number,
+            self.fixed,
+            self.values.iter().copied(),
+            target,
+        )
+    }
+}
+
+impl<'a, T> EncodeToWrite for ScalarPacked<'a, T>
+where
+    T: Scalar,
+{
+    fn encode_to_write<W>(&self, w: &mut W) -> Result<usize, io::Error>
+    where
+        W: io::Write,
+    {
+        packed_to_write(
+            self.field_number,
+            self.fixed,
+            self.values.iter().copied(),
+            w,
+        )
+    }
+}
+
+/// Declares the encoder aliases & constructors for a scalar type without a fixed encoding.
+macro_rules! scalar_encoders {
+    ($scalar:ty, $value:ident, $field:ident, $list:ident, $packed:ident) => {
+        #[doc = concat!("Encodes a required `", stringify!($scalar), "` value.")]
+        pub type $value = ScalarValue<$scalar>;
+
+        #[doc = concat!("Encodes an optional `", stringify!($scalar), "` field.")]
+        pub type $field = ScalarField<$scalar>;
+
+        #[doc = concat!("Encodes a list of `", stringify!($scalar), "` values.")]
+        pub type $list<'a> = ScalarList<'a, $scalar>;
+
+        #[doc = concat!("Encodes a packed list of `", stringify!($scalar), "` values.")]
+        pub type $packed<'a> = ScalarPacked<'a, $scalar>;
+
+        impl ScalarValue<$scalar> {
+            /// Creates a new value encoder.
+            pub const fn new(value: $scalar) -> Self {
+                Self {
+                    fixed: false,
+                    value,
+                }
+            }
+        }
+
+        impl ScalarField<$scalar> {
+            /// Creates a new field encoder.
+            pub const fn new(field_number: u32, value: Option<$scalar>) -> Self {
+                Self {
+                    field_number,
+                    fixed: false,
+                    value,
+                }
+            }
+        }
+
+        impl<'a> ScalarList<'a, $scalar> {
+            /// Creates a new list encoder.
+            pub const fn new(field_number: u32, values: &'a [$scalar]) -> Self {
+                Self {
+                    field_number,
+                    fixed: false,
+                    values,
+                }
+            }
+        }
+
+        impl<'a> ScalarPacked<'a, $scalar> {
+            /// Creates a new packed list encoder.
+            pub const fn new(field_number: u32, values: &'a [$scalar]) -> Self {
+                Self {
+                    field_number,
+                    fixed: false,
+                    values,
+                }
+            }
+        }
+    };
+    ($scalar:ty, $value:ident, $field:ident, $list:ident, $packed:ident, fixed) => {
+        #[doc = concat!("Encodes a required `", stringify!($scalar), "` value.")]
+        pub type $value = ScalarValue<$scalar>;
+
+        #[doc = concat!("Encodes an optional `", stringify!($scalar), "` field.")]
+        pub type $field = ScalarField<$scalar>;
+
+        #[doc = concat!("Encodes a list of `", stringify!($scalar), "` values.")]
+        pub type $list<'a> = ScalarList<'a, $scalar>;
+
+        #[doc = concat!("Encodes a packed list of `", stringify!($scalar), "` values.")]
+        pub type $packed<'a> = ScalarPacked<'a, $scalar>;
+
+        impl ScalarValue<$scalar> {
+            /// Creates a new value encoder.
+            pub const fn new(fixed: bool, value: $scalar) -> Self {
+                Self { fixed, value }
+            }
+        }
+
+        impl ScalarField<$scalar> {
+            /// Creates a new field encoder.
+            pub const fn new(field_number: u32, fixed: bool, value: Option<$scalar>) -> Self {
+                Self {
+                    field_number,
+                    fixed,
+                    value,
+                }
+            }
+        }
+
+        impl<'a> ScalarList<'a, $scalar> {
+            /// Creates a new list encoder.
+            pub const fn new(field_number: u32, fixed: bool, values: &'a [$scalar]) -> Self {
+                Self {
+                    field_number,
+                    fixed,
+                    values,
+                }
+            }
+        }
+
+        impl<'a> ScalarPacked<'a, $scalar> {
+            /// Creates a new packed list encoder.
+            pub const fn new(field_number: u32, fixed: bool, values: &'a [$scalar]) -> Self {
+                Self {
+                    field_number,
+                    fixed,
+                    values,
+                }
+            }
+        }
+    };
+}
+
+scalar_encoders!(
+    u8,
+    UnsignedInt8Value,
+    UnsignedInt8Field,
+    UnsignedInt8List,
+    UnsignedInt8Packed
+);
+scalar_encoders!(
+    u16,
+    UnsignedInt16Value,
+    UnsignedInt16Field,
+    UnsignedInt16List,
+    UnsignedInt16Packed,
+    fixed
+);
+scalar_encoders!(
+    u32,
+    UnsignedInt32Value,
+    UnsignedInt32Field,
+    UnsignedInt32List,
+    UnsignedInt32Packed,
+    fixed
+);
+scalar_encoders!(
+    u64,
+    UnsignedInt64Value,
+    UnsignedInt64Field,
+    UnsignedInt64List,
+    UnsignedInt64Packed,
+    fixed
+);
+scalar_encoders!(
+    i8,
+    SignedInt8Value,
+    SignedInt8Field,
+    SignedInt8List,
+    SignedInt8Packed
+);
+scalar_encoders!(
+    i16,
+    SignedInt16Value,
+    SignedInt16Field,
+    SignedInt16List,
+    SignedInt16Packed,
+    fixed
+);
+scalar_encoders!(
+    i32,
+    SignedInt32Value,
+    SignedInt32Field,
+    SignedInt32List,
+    SignedInt32Packed,
+    fixed
+);
+scalar_encoders!(
+    i64,
+    SignedInt64Value,
+    SignedInt64Field,
+    SignedInt64List,
+    SignedInt64Packed,
+    fixed
+);
+scalar_encoders!(f32, Float32Value, Float32Field, Float32List, Float32Packed);
+scalar_encoders!(f64, Float64Value, Float64Field, Float64List, Float64Packed);
+scalar_encoders!(bool, BooleanValue, BooleanField, BooleanList, BooleanPacked);
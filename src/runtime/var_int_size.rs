@@ -0,0 +1,48 @@
+/// A value with a var-int encoded size.
+pub trait VarIntSize {
+    /// Gets the var-int encoded size. (in bytes)
+    fn var_int_size(self) -> usize;
+}
+
+impl VarIntSize for u64 {
+    fn var_int_size(self) -> usize {
+        (64 - (self | 1).leading_zeros()).div_ceil(7) as usize
+    }
+}
+
+impl VarIntSize for u32 {
+    fn var_int_size(self) -> usize {
+        (self as u64).var_int_size()
+    }
+}
+
+impl VarIntSize for usize {
+    fn var_int_size(self) -> usize {
+        (self as u64).var_int_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::VarIntSize;
+
+    #[test]
+    fn var_int_size() {
+        let test_cases: &[(u64, usize)] = &[
+            (0, 1),
+            (1, 1),
+            (0x7F, 1),
+            (0x80, 2),
+            (0x3FFF, 2),
+            (0x4000, 3),
+            (u32::MAX as u64, 5),
+            (u64::MAX >> 1, 9),
+            (u64::MAX, 10),
+        ];
+        for (value, expected) in test_cases {
+            assert_eq!(value.var_int_size(), *expected, "value={}", value);
+        }
+        assert_eq!(u32::MAX.var_int_size(), 5);
+        assert_eq!(300usize.var_int_size(), 2);
+    }
+}
@@ -0,0 +1,87 @@
+/// The encoding of a field value.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum WireType {
+    /// A var-int value.
+    VarInt,
+
+    /// A 1-byte value.
+    Fixed1Byte,
+
+    /// A 2-byte value.
+    Fixed2Byte,
+
+    /// A 4-byte value.
+    Fixed4Byte,
+
+    /// An 8-byte value.
+    Fixed8Byte,
+
+    /// A value prefixed with its var-int encoded length.
+    LengthPrefixed,
+}
+
+impl WireType {
+    //! Bits
+
+    /// Gets the 3-bit tag of the wire type.
+    pub const fn bits(&self) -> u8 {
+        match self {
+            Self::VarInt => 0,
+            Self::Fixed1Byte => 1,
+            Self::Fixed2Byte => 2,
+            Self::Fixed4Byte => 3,
+            Self::Fixed8Byte => 4,
+            Self::LengthPrefixed => 5,
+        }
+    }
+
+    /// Gets the wire type with the 3-bit tag.
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0 => Some(Self::VarInt),
+            1 => Some(Self::Fixed1Byte),
+            2 => Some(Self::Fixed2Byte),
+            3 => Some(Self::Fixed4Byte),
+            4 => Some(Self::Fixed8Byte),
+            5 => Some(Self::LengthPrefixed),
+            _ => None,
+        }
+    }
+}
+
+impl WireType {
+    //! Properties
+
+    /// Gets the length of the fixed-length wire types.
+    pub const fn fixed_len(&self) -> Option<usize> {
+        match self {
+            Self::Fixed1Byte => Some(1),
+            Self::Fixed2Byte => Some(2),
+            Self::Fixed4Byte => Some(4),
+            Self::Fixed8Byte => Some(8),
+            Self::VarInt | Self::LengthPrefixed => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::WireType;
+    use crate::runtime::WireType::*;
+
+    #[test]
+    fn bits() {
+        for wire_type in [
+            VarInt,
+            Fixed1Byte,
+            Fixed2Byte,
+            Fixed4Byte,
+            Fixed8Byte,
+            LengthPrefixed,
+        ] {
+            assert_eq!(WireType::from_bits(wire_type.bits()), Some(wire_type));
+        }
+        assert_eq!(WireType::from_bits(6), None);
+        assert_eq!(WireType::from_bits(7), None);
+    }
+}
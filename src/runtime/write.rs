@@ -0,0 +1,99 @@
+//! Functions writing encoded values.
+
+use std::io;
+
+use super::{EncodeToWrite, EncodedLen, VarIntSize};
+
+/// The maximum var-int encoded size of a `u64`.
+pub const MAX_VAR_INT_SIZE: usize = 10;
+
+/// Encodes the var-int value to the start of the target. Returns the encoded length.
+///
+/// The target must be at least `value.var_int_size()` bytes long.
+pub fn encode_u64_var(mut value: u64, target: &mut [u8]) -> usize {
+    let mut i: usize = 0;
+    while value >= 0x80 {
+        target[i] = (value as u8) | 0x80;
+        value >>= 7;
+        i += 1;
+    }
+    target[i] = value as u8;
+    i + 1
+}
+
+/// Writes the var-int value. Returns the encoded length.
+pub fn write_u64_var<W>(w: &mut W, value: u64) -> Result<usize, io::Error>
+where
+    W: io::Write + ?Sized,
+{
+    let mut buffer: [u8; MAX_VAR_INT_SIZE] = [0; MAX_VAR_INT_SIZE];
+    let encoded_len: usize = encode_u64_var(value, &mut buffer);
+    w.write_all(&buffer[..encoded_len])?;
+    Ok(encoded_len)
+}
+
+/// Writes the var-int value. Returns the encoded length.
+pub fn write_u32_var<W>(w: &mut W, value: u32) -> Result<usize, io::Error>
+where
+    W: io::Write + ?Sized,
+{
+    write_u64_var(w, value as u64)
+}
+
+/// Writes the value prefixed with its var-int encoded length. Returns the encoded length.
+///
+/// The value is written through a `dyn io::Write` so values nested in values of the same type
+/// encode with the same write type. (nesting `&mut W` for recursive messages would never finish
+/// monomorphizing)
+pub fn write_length_prefixed<W, T>(w: &mut W, value: &T) -> Result<usize, io::Error>
+where
+    W: io::Write + ?Sized,
+    T: EncodedLen + EncodeToWrite,
+{
+    let len: usize = value.encoded_len();
+    let prefix_len: usize = write_u64_var(w, len as u64)?;
+    let mut w: &mut W = w;
+    let mut w: &mut dyn io::Write = &mut w;
+    let value_len: usize = value.encode_to_write(&mut w)?;
+    debug_assert_eq!(len, value_len);
+    debug_assert_eq!(len.var_int_size(), prefix_len);
+    Ok(prefix_len + value_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::write::{encode_u64_var, write_length_prefixed, write_u64_var};
+    use crate::runtime::UnsignedInt32Value;
+
+    #[test]
+    fn encode_u64_var_bytes() {
+        let test_cases: &[(u64, &[u8])] = &[
+            (0, &[0x00]),
+            (1, &[0x01]),
+            (0x7F, &[0x7F]),
+            (0x80, &[0x80, 0x01]),
+            (300, &[0xAC, 0x02]),
+            (
+                u64::MAX,
+                &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+            ),
+        ];
+        for (value, expected) in test_cases {
+            let mut target: [u8; 10] = [0; 10];
+            let encoded_len: usize = encode_u64_var(*value, &mut target);
+            assert_eq!(&target[..encoded_len], *expected, "value={}", value);
+
+            let mut written: Vec<u8> = Vec::default();
+            assert_eq!(write_u64_var(&mut written, *value).unwrap(), encoded_len);
+            assert_eq!(written, *expected);
+        }
+    }
+
+    #[test]
+    fn write_length_prefixed_value() {
+        let mut written: Vec<u8> = Vec::default();
+        let value: UnsignedInt32Value = UnsignedInt32Value::new(false, 300);
+        assert_eq!(write_length_prefixed(&mut written, &value).unwrap(), 3);
+        assert_eq!(written, vec![0x02, 0xAC, 0x02]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::WithComments;
+
+/// An enum case.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct EnumCase {
+    comments: Vec<String>,
+    name: String,
+    number: u32,
+}
+
+impl<S: Into<String>> From<(S, u32)> for EnumCase {
+    fn from(tuple: (S, u32)) -> Self {
+        Self {
+            comments: Vec::default(),
+            name: tuple.0.into(),
+            number: tuple.1,
+        }
+    }
+}
+
+impl WithComments for EnumCase {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for EnumCase {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl EnumCase {
+    //! Number
+
+    /// Gets the case number.
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+}
+
+impl Display for EnumCase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {};", self.name, self.number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::EnumCase;
+
+    #[test]
+    fn display() {
+        let case: EnumCase = ("ACTIVE", 1).into();
+        assert_eq!(case.to_string(), "ACTIVE = 1;");
+    }
+}
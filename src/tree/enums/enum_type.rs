@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::{EnumCase, WithComments};
+
+/// An enum declaration.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Enum {
+    comments: Vec<String>,
+    name: String,
+    cases: Vec<EnumCase>,
+}
+
+impl<S: Into<String>> From<S> for Enum {
+    fn from(name: S) -> Self {
+        Self {
+            comments: Vec::default(),
+            name: name.into(),
+            cases: Vec::default(),
+        }
+    }
+}
+
+impl WithComments for Enum {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for Enum {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl Enum {
+    //! Cases
+
+    /// Gets the cases.
+    pub fn cases(&self) -> &[EnumCase] {
+        self.cases.as_slice()
+    }
+
+    /// Adds the case.
+    pub fn with_case<C>(mut self, case: C) -> Self
+    where
+        C: Into<EnumCase>,
+    {
+        self.add_case(case);
+        self
+    }
+
+    /// Adds the case.
+    pub fn add_case<C>(&mut self, case: C)
+    where
+        C: Into<EnumCase>,
+    {
+        self.cases.push(case.into());
+    }
+}
+
+impl Display for Enum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for comment in &self.comments {
+            write!(f, "// {}\n", comment)?;
+        }
+        write!(f, "enum {} {{", self.name)?;
+        if self.cases.is_empty() {
+            write!(f, "}}")?;
+        } else {
+            write!(f, "\n")?;
+            for case in &self.cases {
+                for comment in case.comments() {
+                    write!(f, "    // {}\n", comment)?;
+                }
+                write!(f, "    {}\n", case)?;
+            }
+            write!(f, "}}")?;
+        }
+        write!(f, "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::{Enum, EnumCase, WithComments};
+
+    #[test]
+    fn display() {
+        let e: Enum = "Status".into();
+        assert_eq!(e.to_string(), "enum Status {}\n");
+
+        let e: Enum = e
+            .with_case(EnumCase::from(("ACTIVE", 1)).with_comment("Active."))
+            .with_case(("DISABLED", 2));
+        let expected: &str =
+            "enum Status {\n    // Active.\n    ACTIVE = 1;\n    DISABLED = 2;\n}\n";
+        assert_eq!(e.to_string(), expected);
+    }
+}
@@ -0,0 +1,5 @@
+pub use enum_case::*;
+pub use enum_type::*;
+
+mod enum_case;
+mod enum_type;
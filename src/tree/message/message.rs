@@ -2,7 +2,7 @@ use std::fmt::{Display, Formatter};
 
 use code_gen::WithName;
 
-use crate::tree::{MessageField, WithComments};
+use crate::tree::{MessageField, Reserved, WithComments};
 
 /// A message declaration.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
@@ -10,6 +10,7 @@ pub struct Message {
     comments: Vec<String>,
     name: String,
     fields: Vec<MessageField>,
+    reserved: Vec<(usize, Reserved)>,
 }
 
 impl<S: Into<String>> From<S> for Message {
@@ -18,6 +19,7 @@ impl<S: Into<String>> From<S> for Message {
             comments: Vec::default(),
             name: name.into(),
             fields: Vec::default(),
+            reserved: Vec::default(),
         }
     }
 }
@@ -49,6 +51,21 @@ impl Message {
         self.fields.as_slice()
     }
 
+    /// Gets the required fields in declaration order.
+    pub fn required_fields(&self) -> impl Iterator<Item = &MessageField> {
+        self.fields.iter().filter(|field| field.is_required())
+    }
+
+    /// Gets the optional fields in declaration order.
+    pub fn optional_fields(&self) -> impl Iterator<Item = &MessageField> {
+        self.fields.iter().filter(|field| !field.is_required())
+    }
+
+    /// Gets the fields in encoding order. (required fields first, then optional fields)
+    pub fn encoding_order(&self) -> impl Iterator<Item = &MessageField> {
+        self.required_fields().chain(self.optional_fields())
+    }
+
     /// Adds the field.
     pub fn with_field<F>(mut self, field: F) -> Self
     where
@@ -67,19 +84,78 @@ impl Message {
     }
 }
 
+impl Message {
+    //! Reserved
+
+    /// Gets the reserved statements in declaration order.
+    pub fn reserved(&self) -> impl Iterator<Item = &Reserved> {
+        self.reserved.iter().map(|(_, reserved)| reserved)
+    }
+
+    /// Gets the reserved statements declared between the previous field & the field at the index.
+    /// (the index `fields().len()` gets the reserved statements after the last field)
+    pub fn reserved_before(&self, index: usize) -> impl Iterator<Item = &Reserved> {
+        self.reserved
+            .iter()
+            .filter(move |(before, _)| *before == index)
+            .map(|(_, reserved)| reserved)
+    }
+
+    /// Adds the reserved statement.
+    pub fn with_reserved(mut self, reserved: Reserved) -> Self {
+        self.add_reserved(reserved);
+        self
+    }
+
+    /// Adds the reserved statement. (after the fields added so far)
+    pub fn add_reserved(&mut self, reserved: Reserved) {
+        self.reserved.push((self.fields.len(), reserved));
+    }
+
+    /// Checks if the field number is reserved.
+    pub fn is_reserved_number(&self, field_number: u32) -> bool {
+        self.reserved()
+            .any(|reserved| reserved.contains_number(field_number))
+    }
+
+    /// Checks if the field name is reserved.
+    pub fn is_reserved_name(&self, name: &str) -> bool {
+        self.reserved().any(|reserved| reserved.contains_name(name))
+    }
+
+    /// Gets the reserved field number ranges. (`start` to `end` inclusive)
+    pub fn reserved_numbers(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.reserved()
+            .flat_map(|reserved| match reserved {
+                Reserved::Numbers(ranges) => ranges.as_slice(),
+                Reserved::Names(_) => &[],
+            })
+            .copied()
+    }
+}
+
 impl Display for Message {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for comment in &self.comments {
             write!(f, "// {}\n", comment)?;
         }
         write!(f, "message {} {{", self.name)?;
-        if self.fields.is_empty() {
+        if self.fields.is_empty() && self.reserved.is_empty() {
             write!(f, "}}")?;
         } else {
             write!(f, "\n")?;
-            for field in &self.fields {
+            for (index, field) in self.fields.iter().enumerate() {
+                for reserved in self.reserved_before(index) {
+                    write!(f, "    {}\n", reserved)?;
+                }
+                for comment in field.comments() {
+                    write!(f, "    // {}\n", comment)?;
+                }
                 write!(f, "    {}\n", field)?;
             }
+            for reserved in self.reserved_before(self.fields.len()) {
+                write!(f, "    {}\n", reserved)?;
+            }
             write!(f, "}}")?;
         }
         write!(f, "\n")
@@ -88,8 +164,8 @@ impl Display for Message {
 
 #[cfg(test)]
 mod tests {
-    use crate::tree::Message;
     use crate::tree::PrimitiveType::{UnsignedInt16, UnsignedInt8};
+    use crate::tree::{Message, MessageField, Reserved, WithComments};
 
     #[test]
     fn display() {
@@ -107,5 +183,31 @@ mod tests {
         let result: String = message.to_string();
         let expected: &str = "message MyMessage {\n    one: u8;\n    two: u16;\n}\n";
         assert_eq!(result, expected);
+
+        let message: Message = message
+            .with_comment("The message.")
+            .with_field(MessageField::from(("three", UnsignedInt8)).with_comment("The field."));
+        let result: String = message.to_string();
+        let expected: &str = "// The message.\nmessage MyMessage {\n    one: u8;\n    two: u16;\n    // The field.\n    three: u8;\n}\n";
+        assert_eq!(result, expected);
+
+        let message: Message = Message::from("Reserved")
+            .with_reserved(Reserved::Numbers(vec![(3, 3), (7, 9)]))
+            .with_reserved(Reserved::Names(vec!["old_name".to_string()]));
+        let result: String = message.to_string();
+        let expected: &str =
+            "message Reserved {\n    reserved 3, 7 to 9;\n    reserved \"old_name\";\n}\n";
+        assert_eq!(result, expected);
+        assert!(message.is_reserved_number(7));
+        assert!(message.is_reserved_name("old_name"));
+
+        let message: Message = Message::from("Reserved")
+            .with_reserved(Reserved::Numbers(vec![(1, 1)]))
+            .with_field(("two", UnsignedInt8))
+            .with_reserved(Reserved::Names(vec!["three".to_string()]))
+            .with_field(("four", UnsignedInt16));
+        let result: String = message.to_string();
+        let expected: &str = "message Reserved {\n    reserved 1;\n    two: u8;\n    reserved \"three\";\n    four: u16;\n}\n";
+        assert_eq!(result, expected);
     }
 }
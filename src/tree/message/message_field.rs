@@ -10,6 +10,8 @@ pub struct MessageField {
     comments: Vec<String>,
     var: Var,
     field_number: Option<u32>,
+    fixed: bool,
+    packed: bool,
 }
 
 impl<V: Into<Var>> From<V> for MessageField {
@@ -18,6 +20,8 @@ impl<V: Into<Var>> From<V> for MessageField {
             comments: Vec::default(),
             var: var.into(),
             field_number: None,
+            fixed: false,
+            packed: false,
         }
     }
 }
@@ -61,6 +65,11 @@ impl MessageField {
         self.field_number
     }
 
+    /// Checks if the field is required. (required fields have no field number)
+    pub fn is_required(&self) -> bool {
+        self.field_number.is_none()
+    }
+
     // Sets the field number.
     pub fn with_field_number(mut self, field_number: u32) -> Self {
         self.set_field_number(field_number);
@@ -73,12 +82,62 @@ impl MessageField {
     }
 }
 
+impl MessageField {
+    //! Fixed
+
+    /// Checks if the field is encoded with fixed-width bytes instead of a varint.
+    pub fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    /// Sets the field to be encoded with fixed-width bytes instead of a varint.
+    pub fn with_fixed(mut self) -> Self {
+        self.set_fixed(true);
+        self
+    }
+
+    /// Sets whether the field is encoded with fixed-width bytes instead of a varint.
+    pub fn set_fixed(&mut self, fixed: bool) {
+        self.fixed = fixed;
+    }
+}
+
+impl MessageField {
+    //! Packed
+
+    /// Checks if the list field is encoded as a single length-delimited run of elements.
+    pub fn is_packed(&self) -> bool {
+        self.packed
+    }
+
+    /// Sets the list field to be encoded as a single length-delimited run of elements.
+    pub fn with_packed(mut self) -> Self {
+        self.set_packed(true);
+        self
+    }
+
+    /// Sets whether the list field is encoded as a single length-delimited run of elements.
+    pub fn set_packed(&mut self, packed: bool) {
+        self.packed = packed;
+    }
+}
+
 impl Display for MessageField {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.var)?;
         if let Some(field_number) = self.field_number {
             write!(f, " = {}", field_number)?;
         }
+        let mut options: Vec<&str> = Vec::default();
+        if self.fixed {
+            options.push("fixed");
+        }
+        if self.packed {
+            options.push("packed");
+        }
+        if !options.is_empty() {
+            write!(f, " [{}]", options.join(", "))?;
+        }
         write!(f, ";")
     }
 }
@@ -95,5 +154,11 @@ mod tests {
 
         let field: MessageField = field.with_field_number(1);
         assert_eq!(field.to_string(), "one: u8 = 1;");
+
+        let field: MessageField = field.with_fixed();
+        assert_eq!(field.to_string(), "one: u8 = 1 [fixed];");
+
+        let field: MessageField = field.with_packed();
+        assert_eq!(field.to_string(), "one: u8 = 1 [fixed, packed];");
     }
 }
@@ -1,5 +1,7 @@
 pub use message::*;
 pub use message_field::*;
+pub use reserved::*;
 
 mod message;
 mod message_field;
+mod reserved;
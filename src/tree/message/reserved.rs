@@ -0,0 +1,79 @@
+use std::fmt::{Display, Formatter};
+
+/// A reserved statement. (field numbers or field names that cannot be declared)
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum Reserved {
+    /// The reserved field number ranges. (`start` to `end` inclusive)
+    Numbers(Vec<(u32, u32)>),
+
+    /// The reserved field names.
+    Names(Vec<String>),
+}
+
+impl Reserved {
+    //! Contains
+
+    /// Checks if the field number is reserved.
+    pub fn contains_number(&self, field_number: u32) -> bool {
+        match self {
+            Self::Numbers(ranges) => ranges
+                .iter()
+                .any(|(start, end)| *start <= field_number && field_number <= *end),
+            Self::Names(_) => false,
+        }
+    }
+
+    /// Checks if the field name is reserved.
+    pub fn contains_name(&self, name: &str) -> bool {
+        match self {
+            Self::Numbers(_) => false,
+            Self::Names(names) => names.iter().any(|reserved| reserved == name),
+        }
+    }
+}
+
+impl Display for Reserved {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "reserved ")?;
+        match self {
+            Self::Numbers(ranges) => {
+                for (i, (start, end)) in ranges.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    if start == end {
+                        write!(f, "{}", start)?;
+                    } else {
+                        write!(f, "{} to {}", start, end)?;
+                    }
+                }
+            }
+            Self::Names(names) => {
+                for (i, name) in names.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "\"{}\"", name)?;
+                }
+            }
+        }
+        write!(f, ";")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::Reserved;
+
+    #[test]
+    fn display() {
+        let reserved: Reserved = Reserved::Numbers(vec![(3, 3), (7, 9)]);
+        assert_eq!(reserved.to_string(), "reserved 3, 7 to 9;");
+        assert!(reserved.contains_number(8));
+        assert!(!reserved.contains_number(4));
+
+        let reserved: Reserved = Reserved::Names(vec!["old_name".to_string()]);
+        assert_eq!(reserved.to_string(), "reserved \"old_name\";");
+        assert!(reserved.contains_name("old_name"));
+    }
+}
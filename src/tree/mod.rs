@@ -1,9 +1,15 @@
+pub use enums::*;
 pub use message::*;
+pub use service::*;
 pub use source::*;
 pub use util::*;
 pub use var::*;
+pub use variant::*;
 
+mod enums;
 mod message;
+mod service;
 mod source;
 mod util;
 mod var;
+mod variant;
@@ -0,0 +1,5 @@
+pub use rpc::*;
+pub use service::*;
+
+mod rpc;
+mod service;
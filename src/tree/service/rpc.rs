@@ -0,0 +1,87 @@
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::WithComments;
+
+/// A service rpc.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Rpc {
+    comments: Vec<String>,
+    name: String,
+    request: String,
+    response: String,
+}
+
+impl<N, Q, R> From<(N, Q, R)> for Rpc
+where
+    N: Into<String>,
+    Q: Into<String>,
+    R: Into<String>,
+{
+    fn from(tuple: (N, Q, R)) -> Self {
+        Self {
+            comments: Vec::default(),
+            name: tuple.0.into(),
+            request: tuple.1.into(),
+            response: tuple.2.into(),
+        }
+    }
+}
+
+impl WithComments for Rpc {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for Rpc {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl Rpc {
+    //! Messages
+
+    /// Gets the request message name.
+    pub fn request(&self) -> &str {
+        self.request.as_str()
+    }
+
+    /// Gets the response message name.
+    pub fn response(&self) -> &str {
+        self.response.as_str()
+    }
+}
+
+impl Display for Rpc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rpc {}({}) -> {};",
+            self.name, self.request, self.response
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::Rpc;
+
+    #[test]
+    fn display() {
+        let rpc: Rpc = ("GetUser", "GetUserRequest", "GetUserResponse").into();
+        assert_eq!(
+            rpc.to_string(),
+            "rpc GetUser(GetUserRequest) -> GetUserResponse;"
+        );
+    }
+}
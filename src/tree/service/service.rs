@@ -0,0 +1,123 @@
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::{Rpc, WithComments};
+
+/// A service declaration.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Service {
+    comments: Vec<String>,
+    name: String,
+    rpcs: Vec<Rpc>,
+}
+
+impl<S: Into<String>> From<S> for Service {
+    fn from(name: S) -> Self {
+        Self {
+            comments: Vec::default(),
+            name: name.into(),
+            rpcs: Vec::default(),
+        }
+    }
+}
+
+impl WithComments for Service {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for Service {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl Service {
+    //! Rpcs
+
+    /// Gets the rpcs.
+    pub fn rpcs(&self) -> &[Rpc] {
+        self.rpcs.as_slice()
+    }
+
+    /// Adds the rpc.
+    pub fn with_rpc<R>(mut self, rpc: R) -> Self
+    where
+        R: Into<Rpc>,
+    {
+        self.add_rpc(rpc);
+        self
+    }
+
+    /// Adds the rpc.
+    pub fn add_rpc<R>(&mut self, rpc: R)
+    where
+        R: Into<Rpc>,
+    {
+        self.rpcs.push(rpc.into());
+    }
+}
+
+impl Service {
+    //! Method IDs
+
+    /// Gets the rpcs with their method ids. (the 1-based declaration position)
+    pub fn method_ids(&self) -> impl Iterator<Item = (u32, &Rpc)> {
+        self.rpcs
+            .iter()
+            .enumerate()
+            .map(|(i, rpc)| (i as u32 + 1, rpc))
+    }
+}
+
+impl Display for Service {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for comment in &self.comments {
+            write!(f, "// {}\n", comment)?;
+        }
+        write!(f, "service {} {{", self.name)?;
+        if self.rpcs.is_empty() {
+            write!(f, "}}")?;
+        } else {
+            write!(f, "\n")?;
+            for rpc in &self.rpcs {
+                for comment in rpc.comments() {
+                    write!(f, "    // {}\n", comment)?;
+                }
+                write!(f, "    {}\n", rpc)?;
+            }
+            write!(f, "}}")?;
+        }
+        write!(f, "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::{Rpc, Service, WithComments};
+
+    #[test]
+    fn display() {
+        let service: Service = "Users".into();
+        assert_eq!(service.to_string(), "service Users {}\n");
+
+        let service: Service = service
+            .with_rpc(Rpc::from(("GetUser", "GetRequest", "GetResponse")).with_comment("Gets."))
+            .with_rpc(("DeleteUser", "DeleteRequest", "DeleteResponse"));
+        let expected: &str = "service Users {\n    // Gets.\n    rpc GetUser(GetRequest) -> GetResponse;\n    rpc DeleteUser(DeleteRequest) -> DeleteResponse;\n}\n";
+        assert_eq!(service.to_string(), expected);
+        assert_eq!(
+            service.method_ids().map(|(id, _)| id).collect::<Vec<u32>>(),
+            vec![1, 2]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt::{Display, Formatter};
+
+use crate::tree::WithComments;
+
+/// An import of another source file.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Import {
+    comments: Vec<String>,
+    path: String,
+}
+
+impl<S: Into<String>> From<S> for Import {
+    fn from(path: S) -> Self {
+        Self {
+            comments: Vec::default(),
+            path: path.into(),
+        }
+    }
+}
+
+impl WithComments for Import {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl Import {
+    //! Path
+
+    /// Gets the path of the imported source file. (the path in the source set)
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+}
+
+impl Display for Import {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "import \"{}\";", self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::Import;
+
+    #[test]
+    fn display() {
+        let import: Import = "common.pp".into();
+        assert_eq!(import.to_string(), "import \"common.pp\";");
+    }
+}
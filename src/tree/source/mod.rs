@@ -1,5 +1,11 @@
+pub use import::*;
+pub use package::*;
 pub use source_dec::*;
 pub use source_file::*;
+pub use source_set::*;
 
+mod import;
+mod package;
 mod source_dec;
 mod source_file;
+mod source_set;
@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+use std::str::Split;
+
+use code_gen::WithName;
+
+use crate::tree::WithComments;
+
+/// A package declaration.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Package {
+    comments: Vec<String>,
+    name: String,
+}
+
+impl<S: Into<String>> From<S> for Package {
+    fn from(name: S) -> Self {
+        Self {
+            comments: Vec::default(),
+            name: name.into(),
+        }
+    }
+}
+
+impl WithComments for Package {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for Package {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl Package {
+    //! Segments
+
+    /// Gets the `.` separated segments of the package name.
+    pub fn segments(&self) -> Split<'_, char> {
+        self.name.split('.')
+    }
+}
+
+impl Display for Package {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "package {};", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::Package;
+
+    #[test]
+    fn display() {
+        let package: Package = "acme.billing".into();
+        assert_eq!(package.to_string(), "package acme.billing;");
+        assert_eq!(
+            package.segments().collect::<Vec<&str>>(),
+            vec!["acme", "billing"]
+        );
+    }
+}
@@ -1,8 +1,67 @@
-use crate::tree::Message;
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::{Enum, Message, Service, Variant};
 
 /// A source file declaration.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 pub enum SourceDec {
     /// A message declaration.
     MessageDec(Message),
+
+    /// An enum declaration.
+    EnumDec(Enum),
+
+    /// A variant declaration.
+    VariantDec(Variant),
+
+    /// A service declaration.
+    ServiceDec(Service),
+}
+
+impl From<Message> for SourceDec {
+    fn from(message: Message) -> Self {
+        Self::MessageDec(message)
+    }
+}
+
+impl From<Enum> for SourceDec {
+    fn from(e: Enum) -> Self {
+        Self::EnumDec(e)
+    }
+}
+
+impl From<Variant> for SourceDec {
+    fn from(variant: Variant) -> Self {
+        Self::VariantDec(variant)
+    }
+}
+
+impl From<Service> for SourceDec {
+    fn from(service: Service) -> Self {
+        Self::ServiceDec(service)
+    }
+}
+
+impl WithName for SourceDec {
+    fn name(&self) -> &str {
+        match self {
+            Self::MessageDec(message) => message.name(),
+            Self::EnumDec(e) => e.name(),
+            Self::VariantDec(variant) => variant.name(),
+            Self::ServiceDec(service) => service.name(),
+        }
+    }
+}
+
+impl Display for SourceDec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MessageDec(message) => write!(f, "{}", message),
+            Self::EnumDec(e) => write!(f, "{}", e),
+            Self::VariantDec(variant) => write!(f, "{}", variant),
+            Self::ServiceDec(service) => write!(f, "{}", service),
+        }
+    }
 }
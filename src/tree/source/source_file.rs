@@ -1,11 +1,73 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
 use crate::tree::source::source_dec::SourceDec;
+use crate::tree::{
+    Enum, Import, Message, Package, Service, TypeTag, Variant, WithComments, WithTypeTag,
+};
 
 /// A source file.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
 pub struct SourceFile {
+    package: Option<Package>,
+    imports: Vec<Import>,
     declarations: Vec<SourceDec>,
 }
 
+impl SourceFile {
+    //! Package
+
+    /// Gets the optional package.
+    pub fn package(&self) -> Option<&Package> {
+        self.package.as_ref()
+    }
+
+    /// Sets the package.
+    pub fn with_package<P>(mut self, package: P) -> Self
+    where
+        P: Into<Package>,
+    {
+        self.set_package(package);
+        self
+    }
+
+    /// Sets the package.
+    pub fn set_package<P>(&mut self, package: P)
+    where
+        P: Into<Package>,
+    {
+        self.package = Some(package.into());
+    }
+}
+
+impl SourceFile {
+    //! Imports
+
+    /// Gets the imports.
+    pub fn imports(&self) -> &[Import] {
+        self.imports.as_slice()
+    }
+
+    /// Adds an import.
+    pub fn with_import<I>(mut self, import: I) -> Self
+    where
+        I: Into<Import>,
+    {
+        self.add_import(import);
+        self
+    }
+
+    /// Adds an import.
+    pub fn add_import<I>(&mut self, import: I)
+    where
+        I: Into<Import>,
+    {
+        self.imports.push(import.into());
+    }
+}
+
 impl SourceFile {
     //! Declarations
 
@@ -30,4 +92,195 @@ impl SourceFile {
         self.add_declaration(declaration);
         self
     }
+
+    /// Gets the declaration with the name.
+    pub fn declaration(&self, name: &str) -> Option<&SourceDec> {
+        self.declarations
+            .iter()
+            .find(|declaration| declaration.name() == name)
+    }
+}
+
+impl SourceFile {
+    //! References
+
+    /// Gets the names of the named types & rpc messages referenced by the declarations.
+    pub fn referenced_names(&self) -> BTreeSet<&str> {
+        let mut names: BTreeSet<&str> = BTreeSet::default();
+        for declaration in &self.declarations {
+            match declaration {
+                SourceDec::MessageDec(message) => {
+                    for field in message.fields() {
+                        Self::add_named_types(&mut names, field.type_tag());
+                    }
+                }
+                SourceDec::EnumDec(_) => {}
+                SourceDec::VariantDec(variant) => {
+                    for case in variant.cases() {
+                        Self::add_named_types(&mut names, case.type_tag());
+                    }
+                }
+                SourceDec::ServiceDec(service) => {
+                    for rpc in service.rpcs() {
+                        names.insert(rpc.request());
+                        names.insert(rpc.response());
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    fn add_named_types<'a>(names: &mut BTreeSet<&'a str>, type_tag: &'a TypeTag) {
+        match type_tag {
+            TypeTag::Named(name) => {
+                names.insert(name.as_str());
+            }
+            TypeTag::List(element) => Self::add_named_types(names, element),
+            TypeTag::Map(key, value) => {
+                Self::add_named_types(names, key);
+                Self::add_named_types(names, value);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl SourceFile {
+    //! Messages
+
+    /// Gets the message declarations.
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                SourceDec::MessageDec(message) => Some(message),
+                _ => None,
+            })
+    }
+
+    /// Gets the message declaration with the name.
+    pub fn message(&self, name: &str) -> Option<&Message> {
+        self.messages().find(|message| message.name() == name)
+    }
+}
+
+impl SourceFile {
+    //! Enums
+
+    /// Gets the enum declarations.
+    pub fn enums(&self) -> impl Iterator<Item = &Enum> {
+        self.declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                SourceDec::EnumDec(e) => Some(e),
+                _ => None,
+            })
+    }
+
+    /// Gets the enum declaration with the name.
+    pub fn enumeration(&self, name: &str) -> Option<&Enum> {
+        self.enums().find(|e| e.name() == name)
+    }
+}
+
+impl SourceFile {
+    //! Variants
+
+    /// Gets the variant declarations.
+    pub fn variants(&self) -> impl Iterator<Item = &Variant> {
+        self.declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                SourceDec::VariantDec(variant) => Some(variant),
+                _ => None,
+            })
+    }
+
+    /// Gets the variant declaration with the name.
+    pub fn variant(&self, name: &str) -> Option<&Variant> {
+        self.variants().find(|variant| variant.name() == name)
+    }
+}
+
+impl SourceFile {
+    //! Services
+
+    /// Gets the service declarations.
+    pub fn services(&self) -> impl Iterator<Item = &Service> {
+        self.declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                SourceDec::ServiceDec(service) => Some(service),
+                _ => None,
+            })
+    }
+}
+
+impl Display for SourceFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(package) = &self.package {
+            for comment in package.comments() {
+                write!(f, "// {}\n", comment)?;
+            }
+            write!(f, "{}\n", package)?;
+        }
+        for import in &self.imports {
+            for comment in import.comments() {
+                write!(f, "// {}\n", comment)?;
+            }
+            write!(f, "{}\n", import)?;
+        }
+        let has_header: bool = self.package.is_some() || !self.imports.is_empty();
+        if has_header && !self.declarations.is_empty() {
+            write!(f, "\n")?;
+        }
+        for (i, declaration) in self.declarations.iter().enumerate() {
+            if i != 0 {
+                write!(f, "\n")?;
+            }
+            write!(f, "{}", declaration)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::PrimitiveType::UnsignedInt8;
+    use crate::tree::{Message, SourceFile};
+
+    #[test]
+    fn display() {
+        let file: SourceFile = SourceFile::default();
+        assert_eq!(file.to_string(), "");
+
+        let file: SourceFile = file.with_declaration(Message::from("One"));
+        assert_eq!(file.to_string(), "message One {}\n");
+
+        let file: SourceFile =
+            file.with_declaration(Message::from("Two").with_field(("one", UnsignedInt8)));
+        let expected: &str = "message One {}\n\nmessage Two {\n    one: u8;\n}\n";
+        assert_eq!(file.to_string(), expected);
+
+        assert_eq!(file.messages().count(), 2);
+        assert_eq!(file.message("Two").map(|m| m.fields().len()), Some(1));
+        assert!(file.message("Three").is_none());
+        assert!(file.declaration("One").is_some());
+    }
+
+    #[test]
+    fn display_header() {
+        let file: SourceFile = SourceFile::default()
+            .with_package("acme.billing")
+            .with_import("common.pp");
+        assert_eq!(
+            file.to_string(),
+            "package acme.billing;\nimport \"common.pp\";\n"
+        );
+
+        let file: SourceFile = file.with_declaration(Message::from("Invoice"));
+        let expected: &str = "package acme.billing;\nimport \"common.pp\";\n\nmessage Invoice {}\n";
+        assert_eq!(file.to_string(), expected);
+    }
 }
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use crate::tree::{SourceDec, SourceFile};
+
+/// A set of source files keyed by path.
+///
+/// Imports are resolved against the paths in the set. Imports are not transitive.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SourceSet {
+    files: BTreeMap<String, SourceFile>,
+}
+
+impl SourceSet {
+    //! Files
+
+    /// Gets the source files with their paths. (sorted by path)
+    pub fn files(&self) -> impl Iterator<Item = (&str, &SourceFile)> {
+        self.files
+            .iter()
+            .map(|(path, source_file)| (path.as_str(), source_file))
+    }
+
+    /// Gets the source file with the path.
+    pub fn file(&self, path: &str) -> Option<&SourceFile> {
+        self.files.get(path)
+    }
+
+    /// Adds the source file with the path. (replaces an existing file with the same path)
+    pub fn add_file<P>(&mut self, path: P, source_file: SourceFile)
+    where
+        P: Into<String>,
+    {
+        self.files.insert(path.into(), source_file);
+    }
+
+    /// Adds the source file with the path. (replaces an existing file with the same path)
+    pub fn with_file<P>(mut self, path: P, source_file: SourceFile) -> Self
+    where
+        P: Into<String>,
+    {
+        self.add_file(path, source_file);
+        self
+    }
+}
+
+impl SourceSet {
+    //! Resolution
+
+    /// Gets the source files imported by the file with the path. (unresolved imports are skipped)
+    pub fn imported_files<'a>(
+        &'a self,
+        path: &str,
+    ) -> impl Iterator<Item = (&'a str, &'a SourceFile)> + 'a {
+        self.file(path)
+            .into_iter()
+            .flat_map(|source_file| source_file.imports())
+            .filter_map(|import| self.files.get_key_value(import.path()))
+            .map(|(path, source_file)| (path.as_str(), source_file))
+    }
+
+    /// Resolves the named type from the file with the path.
+    ///
+    /// Returns the path of the declaring file & the declaration. Declarations in the file take
+    /// precedence over imported declarations, imported declarations are searched in import order.
+    pub fn resolve(&self, path: &str, name: &str) -> Option<(&str, &SourceDec)> {
+        let (path, source_file): (&String, &SourceFile) = self.files.get_key_value(path)?;
+        if let Some(declaration) = source_file.declaration(name) {
+            return Some((path.as_str(), declaration));
+        }
+        self.imported_files(path)
+            .find_map(|(path, imported)| Some((path, imported.declaration(name)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::{Message, SourceFile, SourceSet};
+
+    #[test]
+    fn resolve() {
+        let common: SourceFile = SourceFile::default()
+            .with_declaration(Message::from("Money"))
+            .with_declaration(Message::from("Invoice"));
+        let billing: SourceFile = SourceFile::default()
+            .with_import("common.pp")
+            .with_import("missing.pp")
+            .with_declaration(Message::from("Invoice"));
+        let set: SourceSet = SourceSet::default()
+            .with_file("common.pp", common)
+            .with_file("billing.pp", billing);
+
+        assert_eq!(set.imported_files("billing.pp").count(), 1);
+        assert_eq!(
+            set.resolve("billing.pp", "Money").map(|(path, _)| path),
+            Some("common.pp")
+        );
+        assert_eq!(
+            set.resolve("billing.pp", "Invoice").map(|(path, _)| path),
+            Some("billing.pp")
+        );
+        assert!(set.resolve("common.pp", "Other").is_none());
+        assert!(set.resolve("other.pp", "Money").is_none());
+    }
+}
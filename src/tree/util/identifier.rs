@@ -0,0 +1,15 @@
+use std::str::Chars;
+
+/// Checks if the name is a valid identifier.
+///
+/// Identifiers start with an ASCII letter or underscore, followed by ASCII letters, digits or
+/// underscores. (`_` alone is not an identifier)
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars: Chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            name != "_" && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
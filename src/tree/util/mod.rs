@@ -1,3 +1,5 @@
+pub use identifier::*;
 pub use with_comments::*;
 
+mod identifier;
 mod with_comments;
@@ -18,6 +18,27 @@ pub enum PrimitiveType {
 
     /// An unsigned 64-bit integer.
     UnsignedInt64,
+
+    /// A signed 8-bit integer.
+    SignedInt8,
+
+    /// A signed 16-bit integer. (zigzag encoded)
+    SignedInt16,
+
+    /// A signed 32-bit integer. (zigzag encoded)
+    SignedInt32,
+
+    /// A signed 64-bit integer. (zigzag encoded)
+    SignedInt64,
+
+    /// A 32-bit floating point number.
+    Float32,
+
+    /// A 64-bit floating point number.
+    Float64,
+
+    /// A boolean.
+    Boolean,
 }
 
 impl PrimitiveType {
@@ -36,6 +57,13 @@ impl ToStaticStr for PrimitiveType {
             Self::UnsignedInt16 => "u16",
             Self::UnsignedInt32 => "u32",
             Self::UnsignedInt64 => "u64",
+            Self::SignedInt8 => "i8",
+            Self::SignedInt16 => "i16",
+            Self::SignedInt32 => "i32",
+            Self::SignedInt64 => "i64",
+            Self::Float32 => "f32",
+            Self::Float64 => "f64",
+            Self::Boolean => "bool",
         }
     }
 }
@@ -64,5 +92,12 @@ mod tests {
         assert_eq!(UnsignedInt16.to_string(), "u16");
         assert_eq!(UnsignedInt32.to_string(), "u32");
         assert_eq!(UnsignedInt64.to_string(), "u64");
+        assert_eq!(SignedInt8.to_string(), "i8");
+        assert_eq!(SignedInt16.to_string(), "i16");
+        assert_eq!(SignedInt32.to_string(), "i32");
+        assert_eq!(SignedInt64.to_string(), "i64");
+        assert_eq!(Float32.to_string(), "f32");
+        assert_eq!(Float64.to_string(), "f64");
+        assert_eq!(Boolean.to_string(), "bool");
     }
 }
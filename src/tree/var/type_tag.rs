@@ -7,6 +7,21 @@ use crate::tree::PrimitiveType;
 pub enum TypeTag {
     /// A primitive type.
     Primitive(PrimitiveType),
+
+    /// A UTF-8 string.
+    String,
+
+    /// A byte array.
+    Bytes,
+
+    /// A named type. (resolved against the source file declarations)
+    Named(String),
+
+    /// A list of elements.
+    List(Box<TypeTag>),
+
+    /// A map of keys to values.
+    Map(Box<TypeTag>, Box<TypeTag>),
 }
 
 impl From<PrimitiveType> for TypeTag {
@@ -19,6 +34,11 @@ impl Display for TypeTag {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Primitive(primitive) => write!(f, "{}", primitive),
+            Self::String => write!(f, "string"),
+            Self::Bytes => write!(f, "bytes"),
+            Self::Named(name) => write!(f, "{}", name),
+            Self::List(element) => write!(f, "[{}]", element),
+            Self::Map(key, value) => write!(f, "map<{}, {}>", key, value),
         }
     }
 }
@@ -32,5 +52,12 @@ mod tests {
     fn display() {
         let tag: TypeTag = UnsignedInt8.into();
         assert_eq!(tag.to_string(), "u8");
+        assert_eq!(TypeTag::String.to_string(), "string");
+        assert_eq!(TypeTag::Bytes.to_string(), "bytes");
+        assert_eq!(TypeTag::Named("Other".to_string()).to_string(), "Other");
+        assert_eq!(TypeTag::List(Box::new(tag.clone())).to_string(), "[u8]");
+
+        let tag: TypeTag = TypeTag::Map(Box::new(TypeTag::String), Box::new(tag));
+        assert_eq!(tag.to_string(), "map<string, u8>");
     }
 }
@@ -0,0 +1,5 @@
+pub use variant::*;
+pub use variant_case::*;
+
+mod variant;
+mod variant_case;
@@ -0,0 +1,113 @@
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::{VariantCase, WithComments};
+
+/// A variant declaration. (exactly one of the cases is set)
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Variant {
+    comments: Vec<String>,
+    name: String,
+    cases: Vec<VariantCase>,
+}
+
+impl<S: Into<String>> From<S> for Variant {
+    fn from(name: S) -> Self {
+        Self {
+            comments: Vec::default(),
+            name: name.into(),
+            cases: Vec::default(),
+        }
+    }
+}
+
+impl WithComments for Variant {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for Variant {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl Variant {
+    //! Cases
+
+    /// Gets the cases.
+    pub fn cases(&self) -> &[VariantCase] {
+        self.cases.as_slice()
+    }
+
+    /// Adds the case.
+    pub fn with_case<C>(mut self, case: C) -> Self
+    where
+        C: Into<VariantCase>,
+    {
+        self.add_case(case);
+        self
+    }
+
+    /// Adds the case.
+    pub fn add_case<C>(&mut self, case: C)
+    where
+        C: Into<VariantCase>,
+    {
+        self.cases.push(case.into());
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for comment in &self.comments {
+            write!(f, "// {}\n", comment)?;
+        }
+        write!(f, "variant {} {{", self.name)?;
+        if self.cases.is_empty() {
+            write!(f, "}}")?;
+        } else {
+            write!(f, "\n")?;
+            for case in &self.cases {
+                for comment in case.comments() {
+                    write!(f, "    // {}\n", comment)?;
+                }
+                write!(f, "    {}\n", case)?;
+            }
+            write!(f, "}}")?;
+        }
+        write!(f, "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::PrimitiveType::Float64;
+    use crate::tree::TypeTag::Named;
+    use crate::tree::{Variant, VariantCase, WithComments};
+
+    #[test]
+    fn display() {
+        let v: Variant = "Shape".into();
+        assert_eq!(v.to_string(), "variant Shape {}\n");
+
+        let v: Variant = v
+            .with_case(
+                VariantCase::from(("circle", Named("Circle".to_string()), 1))
+                    .with_comment("A circle."),
+            )
+            .with_case(("radius", Float64, 2));
+        let expected: &str =
+            "variant Shape {\n    // A circle.\n    circle: Circle = 1;\n    radius: f64 = 2;\n}\n";
+        assert_eq!(v.to_string(), expected);
+    }
+}
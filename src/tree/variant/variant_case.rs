@@ -0,0 +1,81 @@
+use std::fmt::{Display, Formatter};
+
+use code_gen::WithName;
+
+use crate::tree::{TypeTag, Var, WithComments, WithTypeTag, WithVar};
+
+/// A variant case.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct VariantCase {
+    comments: Vec<String>,
+    var: Var,
+    number: u32,
+}
+
+impl<S: Into<String>, T: Into<TypeTag>> From<(S, T, u32)> for VariantCase {
+    fn from(tuple: (S, T, u32)) -> Self {
+        Self {
+            comments: Vec::default(),
+            var: (tuple.0, tuple.1).into(),
+            number: tuple.2,
+        }
+    }
+}
+
+impl WithComments for VariantCase {
+    fn comments(&self) -> &[String] {
+        self.comments.as_slice()
+    }
+
+    fn add_comment<S>(&mut self, comment: S)
+    where
+        S: Into<String>,
+    {
+        self.comments.push(comment.into());
+    }
+}
+
+impl WithName for VariantCase {
+    fn name(&self) -> &str {
+        self.var.name()
+    }
+}
+
+impl WithTypeTag for VariantCase {
+    fn type_tag(&self) -> &TypeTag {
+        self.var.type_tag()
+    }
+}
+
+impl WithVar for VariantCase {
+    fn var(&self) -> &Var {
+        &self.var
+    }
+}
+
+impl VariantCase {
+    //! Number
+
+    /// Gets the case number. (the field number of the encoded case)
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+}
+
+impl Display for VariantCase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {};", self.var, self.number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::TypeTag::Named;
+    use crate::tree::VariantCase;
+
+    #[test]
+    fn display() {
+        let case: VariantCase = ("circle", Named("Circle".to_string()), 1).into();
+        assert_eq!(case.to_string(), "circle: Circle = 1;");
+    }
+}
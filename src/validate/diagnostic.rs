@@ -0,0 +1,60 @@
+use std::fmt::{Display, Formatter};
+
+/// A problem found validating a schema.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Diagnostic {
+    location: String,
+    message: String,
+}
+
+impl Diagnostic {
+    //! Construction
+
+    /// Creates a new diagnostic.
+    ///
+    /// The location is the declaration name, optionally followed by `.` & the element name.
+    pub fn new<L, M>(location: L, message: M) -> Self
+    where
+        L: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Diagnostic {
+    //! Properties
+
+    /// Gets the location of the problem. (`Message` or `Message.field`)
+    pub fn location(&self) -> &str {
+        self.location.as_str()
+    }
+
+    /// Gets the message describing the problem.
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.location, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validate::Diagnostic;
+
+    #[test]
+    fn display() {
+        let diagnostic: Diagnostic = Diagnostic::new("User.id", "field number 0 is reserved");
+        assert_eq!(
+            diagnostic.to_string(),
+            "`User.id`: field number 0 is reserved"
+        );
+    }
+}
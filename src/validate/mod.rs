@@ -0,0 +1,10 @@
+pub use diagnostic::*;
+pub use validate_error::*;
+pub use validator::*;
+
+mod diagnostic;
+mod validate_error;
+mod validator;
+
+#[cfg(test)]
+mod tests;
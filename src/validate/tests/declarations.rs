@@ -0,0 +1,73 @@
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::Named;
+use crate::tree::{Enum, Message, MessageField, Service, SourceFile, Variant};
+use crate::validate::{
+    validate, validate_enum, validate_message, validate_variant, Diagnostic, ValidateError,
+};
+
+#[test]
+fn declarations() {
+    let source_file: SourceFile = SourceFile::default()
+        .with_declaration(Message::from("User"))
+        .with_declaration(
+            Enum::from("User")
+                .with_case(("ONE", 1))
+                .with_case(("TWO", 1))
+                .with_case(("THREE", 0)),
+        )
+        .with_declaration(
+            Variant::from("Shape")
+                .with_case(("circle", Named("Circle".to_string()), 1))
+                .with_case(("side", Float64, 0)),
+        )
+        .with_declaration(
+            Service::from("Users")
+                .with_rpc(("Get", "User", "User"))
+                .with_rpc(("Get", "User", "Missing")),
+        );
+
+    let expected: ValidateError = vec![
+        Diagnostic::new("User", "duplicate name `User`"),
+        Diagnostic::new("User.TWO", "duplicate case number 1"),
+        Diagnostic::new(
+            "User.THREE",
+            "case number 0 is out of range (1..=536870911)",
+        ),
+        Diagnostic::new("Shape.circle", "unresolved type `Circle`"),
+        Diagnostic::new(
+            "Shape.side",
+            "field number 0 is out of range (1..=536870911)",
+        ),
+        Diagnostic::new("Users.Get", "duplicate name `Get`"),
+        Diagnostic::new("Users.Get", "unresolved message `Missing`"),
+    ]
+    .into();
+    let result: Result<(), ValidateError> = validate(&source_file);
+    assert_eq!(result, Err(expected));
+    assert_eq!(
+        result.unwrap_err().to_string().lines().next(),
+        Some("`User`: duplicate name `User`")
+    );
+}
+
+#[test]
+fn standalone_declarations() {
+    let message: Message = Message::from("User").with_field(
+        MessageField::from(("friend", Named("Friend".to_string()))).with_field_number(0),
+    );
+    let expected: ValidateError = vec![Diagnostic::new(
+        "User.friend",
+        "field number 0 is out of range (1..=536870911)",
+    )]
+    .into();
+    assert_eq!(validate_message(&message), Err(expected));
+
+    let e: Enum = Enum::from("my-enum").with_case(("ONE", 1));
+    let expected: ValidateError =
+        vec![Diagnostic::new("my-enum", "invalid identifier `my-enum`")].into();
+    assert_eq!(validate_enum(&e), Err(expected));
+
+    let variant: Variant =
+        Variant::from("Shape").with_case(("circle", Named("Circle".to_string()), 1));
+    assert_eq!(validate_variant(&variant), Ok(()));
+}
@@ -0,0 +1,88 @@
+use crate::tree::PrimitiveType::*;
+use crate::tree::TypeTag::{List, Named};
+use crate::tree::{Message, MessageField, Reserved, SourceFile};
+use crate::validate::{validate, Diagnostic, ValidateError, MAX_FIELD_NUMBER};
+
+#[test]
+fn valid() {
+    let message: Message = Message::from("User")
+        .with_field(("id", UnsignedInt64))
+        .with_field(MessageField::from(("age", UnsignedInt8)).with_field_number(1))
+        .with_field(MessageField::from(("name", UnsignedInt8)).with_field_number(MAX_FIELD_NUMBER));
+    let source_file: SourceFile = SourceFile::default().with_declaration(message);
+    assert_eq!(validate(&source_file), Ok(()));
+}
+
+#[test]
+fn all_diagnostics() {
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("id", UnsignedInt64)).with_field_number(0))
+        .with_field(MessageField::from(("one-two", UnsignedInt8)).with_field_number(1))
+        .with_field(MessageField::from(("three", UnsignedInt8)).with_field_number(1))
+        .with_field(
+            MessageField::from(("three", UnsignedInt8)).with_field_number(MAX_FIELD_NUMBER + 1),
+        )
+        .with_field(
+            MessageField::from(("friends", List(Box::new(Named("Friend".to_string())))))
+                .with_field_number(2),
+        );
+    let source_file: SourceFile = SourceFile::default().with_declaration(message);
+
+    let expected: ValidateError = vec![
+        Diagnostic::new("User.id", "field number 0 is out of range (1..=536870911)"),
+        Diagnostic::new("User.one-two", "invalid identifier `one-two`"),
+        Diagnostic::new("User.three", "duplicate field number 1"),
+        Diagnostic::new("User.three", "duplicate name `three`"),
+        Diagnostic::new(
+            "User.three",
+            "field number 536870912 is out of range (1..=536870911)",
+        ),
+        Diagnostic::new("User.friends", "unresolved type `Friend`"),
+    ]
+    .into();
+    assert_eq!(validate(&source_file), Err(expected));
+}
+
+#[test]
+fn reserved() {
+    let message: Message = Message::from("User")
+        .with_field(MessageField::from(("old_name", UnsignedInt8)).with_field_number(8))
+        .with_reserved(Reserved::Numbers(vec![(7, 9), (5, 4)]))
+        .with_reserved(Reserved::Names(vec!["old_name".to_string()]));
+    let source_file: SourceFile = SourceFile::default().with_declaration(message);
+
+    let expected: ValidateError = vec![
+        Diagnostic::new("User.old_name", "field name `old_name` is reserved"),
+        Diagnostic::new("User.old_name", "field number 8 is reserved"),
+        Diagnostic::new("User", "reserved range 5 to 4 is invalid (1..=536870911)"),
+    ]
+    .into();
+    assert_eq!(validate(&source_file), Err(expected));
+}
+
+#[test]
+fn overlapping_reserved() {
+    let message: Message = Message::from("User")
+        .with_reserved(Reserved::Numbers(vec![(3, 5), (4, 4)]))
+        .with_field(MessageField::from(("one", UnsignedInt8)).with_field_number(1))
+        .with_reserved(Reserved::Numbers(vec![(7, 7)]))
+        .with_reserved(Reserved::Numbers(vec![(7, 7), (5, 6)]));
+    let source_file: SourceFile = SourceFile::default().with_declaration(message);
+
+    let expected: ValidateError = vec![
+        Diagnostic::new(
+            "User",
+            "reserved range 4 to 4 overlaps reserved range 3 to 5",
+        ),
+        Diagnostic::new(
+            "User",
+            "reserved range 7 to 7 overlaps reserved range 7 to 7",
+        ),
+        Diagnostic::new(
+            "User",
+            "reserved range 5 to 6 overlaps reserved range 3 to 5",
+        ),
+    ]
+    .into();
+    assert_eq!(validate(&source_file), Err(expected));
+}
@@ -0,0 +1,3 @@
+mod declarations;
+mod messages;
+mod source_sets;
@@ -0,0 +1,74 @@
+use crate::tree::TypeTag::Named;
+use crate::tree::{Message, Service, SourceFile, SourceSet};
+use crate::validate::{validate, validate_source_set, Diagnostic, ValidateError};
+
+#[test]
+fn imported_types() {
+    let common: SourceFile = SourceFile::default()
+        .with_package("acme.common")
+        .with_declaration(Message::from("Money"));
+    let billing: SourceFile = SourceFile::default()
+        .with_package("acme.billing")
+        .with_import("common.pp")
+        .with_declaration(
+            Message::from("Invoice").with_field(("total", Named("Money".to_string()))),
+        )
+        .with_declaration(Service::from("Billing").with_rpc(("Pay", "Invoice", "Money")));
+
+    assert!(validate(&billing).is_err());
+
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("common.pp", common)
+        .with_file("billing.pp", billing);
+    assert_eq!(validate_source_set(&source_set), Ok(()));
+}
+
+#[test]
+fn unresolved() {
+    let common: SourceFile = SourceFile::default().with_declaration(Message::from("Money"));
+    let billing: SourceFile = SourceFile::default()
+        .with_package("acme.1billing")
+        .with_import("missing.pp")
+        .with_declaration(
+            Message::from("Invoice").with_field(("total", Named("Money".to_string()))),
+        );
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("common.pp", common)
+        .with_file("billing.pp", billing);
+
+    let expected: ValidateError = vec![
+        Diagnostic::new("billing.pp", "unresolved import `missing.pp`"),
+        Diagnostic::new("billing.pp:acme.1billing", "invalid identifier `1billing`"),
+        Diagnostic::new("billing.pp:Invoice.total", "unresolved type `Money`"),
+    ]
+    .into();
+    assert_eq!(validate_source_set(&source_set), Err(expected));
+}
+
+#[test]
+fn ambiguous_import() {
+    let common: SourceFile = SourceFile::default().with_declaration(Message::from("Money"));
+    let legacy: SourceFile = SourceFile::default()
+        .with_declaration(Message::from("Money"))
+        .with_declaration(Message::from("Unused"));
+    let other: SourceFile = SourceFile::default().with_declaration(Message::from("Unused"));
+    let billing: SourceFile = SourceFile::default()
+        .with_import("common.pp")
+        .with_import("legacy.pp")
+        .with_import("other.pp")
+        .with_declaration(
+            Message::from("Invoice").with_field(("total", Named("Money".to_string()))),
+        );
+    let source_set: SourceSet = SourceSet::default()
+        .with_file("common.pp", common)
+        .with_file("legacy.pp", legacy)
+        .with_file("other.pp", other)
+        .with_file("billing.pp", billing);
+
+    let expected: ValidateError = vec![Diagnostic::new(
+        "billing.pp",
+        "name `Money` is declared by more than one import (`common.pp`, `legacy.pp`)",
+    )]
+    .into();
+    assert_eq!(validate_source_set(&source_set), Err(expected));
+}
@@ -0,0 +1,385 @@
+use std::collections::HashSet;
+
+use code_gen::WithName;
+
+use crate::tree::{
+    is_identifier, Enum, Message, Reserved, Service, SourceDec, SourceFile, SourceSet, TypeTag,
+    Variant, WithTypeTag,
+};
+use crate::validate::{Diagnostic, ValidateError};
+
+/// The maximum field number. (the field number shifted past the 3-bit wire type fits a `u32`)
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// Validates the source file.
+///
+/// Returns all the diagnostics found, not just the first.
+pub fn validate(source_file: &SourceFile) -> Result<(), ValidateError> {
+    let mut validator: Validator = Validator::new(source_file);
+    validator.validate_source_file();
+    validator.finish()
+}
+
+/// Validates the source files in the source set.
+///
+/// Named types are resolved against the imported files. A referenced name declared by more than
+/// one imported file is ambiguous. The diagnostic locations are prefixed with the path of the
+/// source file. (`path:Message.field`)
+pub fn validate_source_set(source_set: &SourceSet) -> Result<(), ValidateError> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::default();
+    for (path, source_file) in source_set.files() {
+        for import in source_file.imports() {
+            if source_set.file(import.path()).is_none() {
+                diagnostics.push(Diagnostic::new(
+                    path,
+                    format!("unresolved import `{}`", import.path()),
+                ));
+            }
+        }
+        for name in source_file.referenced_names() {
+            if source_file.declaration(name).is_some() {
+                continue;
+            }
+            let declaring: Vec<&str> = source_set
+                .imported_files(path)
+                .filter(|(_, imported)| imported.declaration(name).is_some())
+                .map(|(import_path, _)| import_path)
+                .collect();
+            if declaring.len() > 1 {
+                diagnostics.push(Diagnostic::new(
+                    path,
+                    format!(
+                        "name `{}` is declared by more than one import (`{}`)",
+                        name,
+                        declaring.join("`, `")
+                    ),
+                ));
+            }
+        }
+
+        let mut validator: Validator = Validator::new(source_file).with_imports(
+            source_set
+                .imported_files(path)
+                .map(|(_, imported)| imported),
+        );
+        validator.validate_source_file();
+        if let Err(error) = validator.finish() {
+            diagnostics.extend(error.diagnostics().iter().map(|diagnostic| {
+                Diagnostic::new(
+                    format!("{}:{}", path, diagnostic.location()),
+                    diagnostic.message(),
+                )
+            }));
+        }
+    }
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(diagnostics.into())
+    }
+}
+
+/// Validates the message on its own.
+///
+/// Named types are not resolved. (the generators resolve them against their own declarations)
+pub fn validate_message(message: &Message) -> Result<(), ValidateError> {
+    validate_declaration(message.name(), |validator| {
+        validator.validate_message(message)
+    })
+}
+
+/// Validates the enum on its own.
+pub fn validate_enum(e: &Enum) -> Result<(), ValidateError> {
+    validate_declaration(e.name(), |validator| validator.validate_enum(e))
+}
+
+/// Validates the variant on its own.
+///
+/// Named types are not resolved. (the generators resolve them against their own declarations)
+pub fn validate_variant(variant: &Variant) -> Result<(), ValidateError> {
+    validate_declaration(variant.name(), |validator| {
+        validator.validate_variant(variant)
+    })
+}
+
+/// Validates the declaration name & the declaration without resolving named types.
+fn validate_declaration<F>(name: &str, validate: F) -> Result<(), ValidateError>
+where
+    F: FnOnce(&mut Validator),
+{
+    let source_file: SourceFile = SourceFile::default();
+    let mut validator: Validator = Validator::new(&source_file);
+    validator.resolve_types = false;
+    validator.check_identifier(name, name);
+    validate(&mut validator);
+    validator.finish()
+}
+
+/// Responsible for validating source files.
+#[derive(Debug)]
+pub struct Validator<'a> {
+    source_file: &'a SourceFile,
+    imports: Vec<&'a SourceFile>,
+    resolve_types: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    //! Construction
+
+    /// Creates a new validator for the source file.
+    pub fn new(source_file: &'a SourceFile) -> Self {
+        Self {
+            source_file,
+            imports: Vec::default(),
+            resolve_types: true,
+            diagnostics: Vec::default(),
+        }
+    }
+
+    /// Adds the imported source files for resolving named types.
+    pub fn with_imports<I>(mut self, imports: I) -> Self
+    where
+        I: IntoIterator<Item = &'a SourceFile>,
+    {
+        self.imports.extend(imports);
+        self
+    }
+
+    /// Finishes the validation. Returns the diagnostics found as an error.
+    pub fn finish(self) -> Result<(), ValidateError> {
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(self.diagnostics.into())
+        }
+    }
+}
+
+impl<'a> Validator<'a> {
+    //! Diagnostics
+
+    /// Adds a diagnostic.
+    fn report<L, M>(&mut self, location: L, message: M)
+    where
+        L: Into<String>,
+        M: Into<String>,
+    {
+        self.diagnostics.push(Diagnostic::new(location, message));
+    }
+
+    /// Checks the name is a valid identifier.
+    fn check_identifier(&mut self, location: &str, name: &str) {
+        if !is_identifier(name) {
+            self.report(location, format!("invalid identifier `{}`", name));
+        }
+    }
+
+    /// Checks the name has not been declared yet in the scope.
+    fn check_unique_name<'n>(
+        &mut self,
+        location: &str,
+        names: &mut HashSet<&'n str>,
+        name: &'n str,
+    ) {
+        if !names.insert(name) {
+            self.report(location, format!("duplicate name `{}`", name));
+        }
+    }
+
+    /// Checks the number is in range & has not been declared yet in the scope.
+    ///
+    /// The kind names the number in the diagnostics. (`field` or `case`)
+    fn check_number(
+        &mut self,
+        location: &str,
+        kind: &str,
+        numbers: &mut HashSet<u32>,
+        number: u32,
+    ) {
+        if number == 0 || number > MAX_FIELD_NUMBER {
+            self.report(
+                location,
+                format!(
+                    "{} number {} is out of range (1..={})",
+                    kind, number, MAX_FIELD_NUMBER
+                ),
+            );
+        } else if !numbers.insert(number) {
+            self.report(location, format!("duplicate {} number {}", kind, number));
+        }
+    }
+
+    /// Resolves the declaration with the name. (declared in the source file or an imported file)
+    fn resolve(&self, name: &str) -> Option<&'a SourceDec> {
+        self.source_file.declaration(name).or_else(|| {
+            self.imports
+                .iter()
+                .find_map(|import| import.declaration(name))
+        })
+    }
+
+    /// Checks the named types in the type tag resolve to declarations.
+    fn check_type_tag(&mut self, location: &str, type_tag: &TypeTag) {
+        if !self.resolve_types {
+            return;
+        }
+        match type_tag {
+            TypeTag::Named(name) => {
+                let resolved: bool = matches!(
+                    self.resolve(name),
+                    Some(
+                        SourceDec::MessageDec(_) | SourceDec::EnumDec(_) | SourceDec::VariantDec(_)
+                    )
+                );
+                if !resolved {
+                    self.report(location, format!("unresolved type `{}`", name));
+                }
+            }
+            TypeTag::List(element) => self.check_type_tag(location, element),
+            TypeTag::Map(key, value) => {
+                self.check_type_tag(location, key);
+                self.check_type_tag(location, value);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> Validator<'a> {
+    //! Source File
+
+    /// Validates the package & the declarations in the source file.
+    pub fn validate_source_file(&mut self) {
+        let source_file: &'a SourceFile = self.source_file;
+        if let Some(package) = source_file.package() {
+            for segment in package.segments() {
+                self.check_identifier(package.name(), segment);
+            }
+        }
+        let mut names: HashSet<&str> = HashSet::default();
+        for declaration in source_file.declarations() {
+            let name: &str = declaration.name();
+            self.check_identifier(name, name);
+            self.check_unique_name(name, &mut names, name);
+
+            match declaration {
+                SourceDec::MessageDec(message) => self.validate_message(message),
+                SourceDec::EnumDec(e) => self.validate_enum(e),
+                SourceDec::VariantDec(variant) => self.validate_variant(variant),
+                SourceDec::ServiceDec(service) => self.validate_service(service),
+            }
+        }
+    }
+}
+
+impl<'a> Validator<'a> {
+    //! Declarations
+
+    /// Validates the message fields & reserved statements.
+    pub fn validate_message(&mut self, message: &Message) {
+        let mut names: HashSet<&str> = HashSet::default();
+        let mut numbers: HashSet<u32> = HashSet::default();
+        for field in message.fields() {
+            let location: String = format!("{}.{}", message.name(), field.name());
+            self.check_identifier(&location, field.name());
+            self.check_unique_name(&location, &mut names, field.name());
+            if message.is_reserved_name(field.name()) {
+                self.report(
+                    &location,
+                    format!("field name `{}` is reserved", field.name()),
+                );
+            }
+            if let Some(field_number) = field.field_number() {
+                self.check_number(&location, "field", &mut numbers, field_number);
+                if message.is_reserved_number(field_number) {
+                    self.report(
+                        &location,
+                        format!("field number {} is reserved", field_number),
+                    );
+                }
+            }
+            self.check_type_tag(&location, field.type_tag());
+        }
+        let mut reserved_ranges: Vec<(u32, u32)> = Vec::default();
+        for reserved in message.reserved() {
+            self.validate_reserved(message, reserved, &mut reserved_ranges);
+        }
+    }
+
+    /// Validates the reserved field number ranges are in range, not reversed & do not overlap the
+    /// previously reserved ranges of the message.
+    fn validate_reserved(
+        &mut self,
+        message: &Message,
+        reserved: &Reserved,
+        reserved_ranges: &mut Vec<(u32, u32)>,
+    ) {
+        if let Reserved::Numbers(ranges) = reserved {
+            for (start, end) in ranges.iter().copied() {
+                if start == 0 || start > end || end > MAX_FIELD_NUMBER {
+                    self.report(
+                        message.name(),
+                        format!(
+                            "reserved range {} to {} is invalid (1..={})",
+                            start, end, MAX_FIELD_NUMBER
+                        ),
+                    );
+                } else if let Some((other_start, other_end)) = reserved_ranges
+                    .iter()
+                    .find(|(other_start, other_end)| start <= *other_end && *other_start <= end)
+                {
+                    self.report(
+                        message.name(),
+                        format!(
+                            "reserved range {} to {} overlaps reserved range {} to {}",
+                            start, end, other_start, other_end
+                        ),
+                    );
+                } else {
+                    reserved_ranges.push((start, end));
+                }
+            }
+        }
+    }
+
+    /// Validates the enum cases.
+    pub fn validate_enum(&mut self, e: &Enum) {
+        let mut names: HashSet<&str> = HashSet::default();
+        let mut numbers: HashSet<u32> = HashSet::default();
+        for case in e.cases() {
+            let location: String = format!("{}.{}", e.name(), case.name());
+            self.check_identifier(&location, case.name());
+            self.check_unique_name(&location, &mut names, case.name());
+            self.check_number(&location, "case", &mut numbers, case.number());
+        }
+    }
+
+    /// Validates the variant cases.
+    pub fn validate_variant(&mut self, variant: &Variant) {
+        let mut names: HashSet<&str> = HashSet::default();
+        let mut numbers: HashSet<u32> = HashSet::default();
+        for case in variant.cases() {
+            let location: String = format!("{}.{}", variant.name(), case.name());
+            self.check_identifier(&location, case.name());
+            self.check_unique_name(&location, &mut names, case.name());
+            self.check_number(&location, "field", &mut numbers, case.number());
+            self.check_type_tag(&location, case.type_tag());
+        }
+    }
+
+    /// Validates the service rpcs.
+    pub fn validate_service(&mut self, service: &Service) {
+        let mut names: HashSet<&str> = HashSet::default();
+        for rpc in service.rpcs() {
+            let location: String = format!("{}.{}", service.name(), rpc.name());
+            self.check_identifier(&location, rpc.name());
+            self.check_unique_name(&location, &mut names, rpc.name());
+            for message in [rpc.request(), rpc.response()] {
+                if !matches!(self.resolve(message), Some(SourceDec::MessageDec(_))) {
+                    self.report(&location, format!("unresolved message `{}`", message));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! Compiles the generated code against the `runtime` module & runs round-trip assertions for every
+//! supported type. (for the default generator, preserved unknown fields & `HashMap` backed maps)
+//!
+//! The schema is generated into a scratch crate in the cargo target directory. The crate includes
+//! the `runtime` module source & is built & run offline. (it has no dependencies)
+
+#![cfg(feature = "rust")]
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use code_gen::{CodeBuffer, Statement};
+
+use proto_packet_core::gen::rust::{
+    GenMessage, GenSourceFile, GenSourceSet, MapBacking, Naming, RustFile, RustGenOptions, Typing,
+    UnknownFields,
+};
+use proto_packet_core::parse::parse_source_file;
+use proto_packet_core::tree::{SourceDec, SourceSet};
+
+use crate::samples::Samples;
+
+mod samples;
+
+/// The schema source files. (path & source)
+const SCHEMA: &[(&str, &str)] = &[
+    ("common.pp", include_str!("schema/common.pp")),
+    ("types.pp", include_str!("schema/types.pp")),
+    ("versions.pp", include_str!("schema/versions.pp")),
+];
+
+/// The scratch crate manifest.
+const MANIFEST: &str = "[package]
+name = \"round_trip\"
+version = \"0.0.0\"
+edition = \"2021\"
+publish = false
+
+[workspace]
+";
+
+/// The scratch crate main file before the generated servers & assertions.
+const MAIN_PRELUDE: &str = r#"#![deny(warnings)]
+#![allow(dead_code)]
+
+#[path = "{runtime}"]
+mod runtime;
+mod schema;
+
+use std::fmt::Debug;
+use std::io;
+
+use runtime::*;
+
+/// Asserts the value encodes consistently & decodes to itself.
+fn round_trip<T>(name: &str, value: T)
+where
+    T: EncodeToSlice + EncodeToWrite + DecodeFromReadLengthPrefixed + PartialEq + Debug,
+{
+    let mut written: Vec<u8> = Vec::default();
+    let written_len: usize = value.encode_to_write(&mut written).unwrap();
+    assert_eq!(written_len, written.len(), "{}: written length", name);
+    assert_eq!(written_len, value.encoded_len(), "{}: encoded length", name);
+
+    let mut slice: Vec<u8> = vec![0; written_len];
+    assert_eq!(value.encode_to_slice(&mut slice), Some(written_len), "{}: slice length", name);
+    assert_eq!(slice, written, "{}: slice bytes", name);
+    if written_len > 0 {
+        assert_eq!(value.encode_to_slice(&mut slice[1..]), None, "{}: short slice", name);
+    }
+
+    let decoded: T = T::decode_from_read(&mut written.as_slice())
+        .unwrap_or_else(|e| panic!("{}: decode: {}", name, e));
+    assert_eq!(decoded, value, "{}: decoded value", name);
+
+    let mut prefixed: Vec<u8> = Vec::default();
+    write::write_length_prefixed(&mut prefixed, &value).unwrap();
+    prefixed.extend_from_slice(&[0xFF, 0xFF]);
+    let mut r: &[u8] = prefixed.as_slice();
+    let decoded: T = T::decode_from_read_length_prefixed(&mut r)
+        .unwrap_or_else(|e| panic!("{}: decode length-prefixed: {}", name, e));
+    assert_eq!(decoded, value, "{}: decoded length-prefixed value", name);
+    assert_eq!(r, &[0xFF, 0xFF], "{}: bytes after the length-prefixed value", name);
+}
+
+/// A transport reading from the input & writing to the output.
+struct Transport {
+    input: io::Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl Transport {
+    fn new(input: Vec<u8>) -> Self {
+        Self {
+            input: io::Cursor::new(input),
+            output: Vec::default(),
+        }
+    }
+}
+
+impl io::Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl io::Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Asserts the request & response round trip through the server & client.
+fn rpc_round_trip<Req, Resp, S, C>(
+    name: &str,
+    method_id: u32,
+    request: Req,
+    response: Resp,
+    serve: S,
+    call: C,
+) where
+    Req: EncodedLen + EncodeToWrite,
+    Resp: EncodedLen + EncodeToWrite + PartialEq + Debug,
+    S: FnOnce(&mut Transport) -> Result<(), io::Error>,
+    C: FnOnce(Transport) -> Result<(Resp, Transport), io::Error>,
+{
+    let mut input: Vec<u8> = Vec::default();
+    write::write_u32_var(&mut input, method_id).unwrap();
+    write::write_length_prefixed(&mut input, &request).unwrap();
+    let mut server: Transport = Transport::new(input.clone());
+    serve(&mut server).unwrap_or_else(|e| panic!("{}: serve: {}", name, e));
+
+    let mut output: Vec<u8> = Vec::default();
+    write::write_length_prefixed(&mut output, &response).unwrap();
+    assert_eq!(server.output, output, "{}: response bytes", name);
+
+    let (decoded, client): (Resp, Transport) = call(Transport::new(server.output))
+        .unwrap_or_else(|e| panic!("{}: call: {}", name, e));
+    assert_eq!(decoded, response, "{}: decoded response", name);
+    assert_eq!(client.output, input, "{}: request bytes", name);
+}
+"#;
+
+/// The scratch crate checks decoding hand-encoded input. (run after the generated assertions)
+///
+/// The checks of the unknown field handling are appended per generator configuration.
+const MAIN_CHECKS: &str = r#"
+/// Asserts the field numbers are absolute by decoding fields written out of order.
+fn decode_out_of_order() {
+    let mut encoded: Vec<u8> = Vec::default();
+    for (field_number, value) in [(9, 2u32), (2, 1), (30, 3)] {
+        FieldHeader::new(field_number, WireType::VarInt)
+            .encode_to_write(&mut encoded)
+            .unwrap();
+        write::write_u32_var(&mut encoded, value).unwrap();
+    }
+    let decoded: Sparse = Sparse::decode_from_read(&mut encoded.as_slice()).unwrap();
+    let expected: Sparse = Sparse::default()
+        .with_a(Some(1))
+        .with_b(Some(2))
+        .with_c(Some(3));
+    assert_eq!(decoded, expected, "Sparse: decoded out of order");
+}
+
+/// Asserts a missing required field is reported by name.
+fn decode_missing_required() {
+    let error: io::Error = Account::decode_from_read(&mut [0x07u8].as_slice()).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData, "Account: missing field");
+    assert_eq!(error.to_string(), "missing required field: name", "Account: missing field");
+}
+
+/// Gets the account & its encoding followed by unknown tagged fields.
+fn account_with_unknown_fields() -> (Account, Vec<u8>) {
+    let account: Account = Account::default()
+        .with_id(7)
+        .with_name("name".to_string())
+        .with_email("email".to_string());
+    let mut encoded: Vec<u8> = account.encode_to_vec();
+    FieldHeader::new(7, WireType::VarInt)
+        .encode_to_write(&mut encoded)
+        .unwrap();
+    write::write_u32_var(&mut encoded, 300).unwrap();
+    StringField::new(8, Some("unknown"))
+        .encode_to_write(&mut encoded)
+        .unwrap();
+    (account, encoded)
+}
+
+/// Gets the user written by the newer schema & the user the older schema reads from it.
+fn user_from_newer_writer() -> (UserV2, UserV1) {
+    let newer: UserV2 = UserV2::default()
+        .with_id(Some(7))
+        .with_name("name".to_string())
+        .with_email("email".to_string())
+        .with_tags(vec!["one".to_string(), "two".to_string()])
+        .with_status(Some(3));
+    let older: UserV1 = UserV1::default()
+        .with_id(Some(7))
+        .with_name("name".to_string());
+    (newer, older)
+}
+
+fn checks() {
+    decode_out_of_order();
+    decode_missing_required();
+    decode_unknown_fields();
+}
+"#;
+
+/// The scratch crate checks of skipping unknown fields.
+const SKIP_CHECKS: &str = r#"
+/// Asserts the unknown fields after the required fields & the fields of a newer writer are
+/// skipped.
+fn decode_unknown_fields() {
+    let (account, encoded): (Account, Vec<u8>) = account_with_unknown_fields();
+    let decoded: Account = Account::decode_from_read(&mut encoded.as_slice()).unwrap();
+    assert_eq!(decoded, account, "Account: decoded with unknown fields");
+
+    let (newer, older): (UserV2, UserV1) = user_from_newer_writer();
+    let encoded: Vec<u8> = newer.encode_to_vec();
+    let decoded: UserV1 = UserV1::decode_from_read(&mut encoded.as_slice()).unwrap();
+    assert_eq!(decoded, older, "UserV1: decoded from a newer writer");
+    assert_eq!(decoded.encode_to_vec(), older.encode_to_vec(), "UserV1: re-encoded");
+}
+"#;
+
+/// The scratch crate checks of preserving unknown fields.
+const PRESERVE_CHECKS: &str = r#"
+/// Asserts the unknown fields after the required fields & the fields of a newer writer are
+/// preserved & re-encoded.
+fn decode_unknown_fields() {
+    let (account, encoded): (Account, Vec<u8>) = account_with_unknown_fields();
+    let decoded: Account = Account::decode_from_read(&mut encoded.as_slice()).unwrap();
+    assert_eq!(decoded.id(), account.id(), "Account: decoded with unknown fields");
+    assert_eq!(decoded.name(), account.name(), "Account: decoded with unknown fields");
+    assert_eq!(decoded.email(), account.email(), "Account: decoded with unknown fields");
+    assert_eq!(
+        decoded.unknown_fields(),
+        &encoded[account.encoded_len()..],
+        "Account: unknown fields"
+    );
+    assert_eq!(decoded.encode_to_vec(), encoded, "Account: re-encoded");
+
+    let (newer, older): (UserV2, UserV1) = user_from_newer_writer();
+    let encoded: Vec<u8> = newer.encode_to_vec();
+    let decoded: UserV1 = UserV1::decode_from_read(&mut encoded.as_slice()).unwrap();
+    assert_eq!(decoded.id(), older.id(), "UserV1: decoded from a newer writer");
+    assert_eq!(decoded.name(), older.name(), "UserV1: decoded from a newer writer");
+    assert_eq!(decoded.encode_to_vec(), encoded, "UserV1: re-encoded");
+    let rewritten: UserV2 = UserV2::decode_from_read(&mut encoded.as_slice()).unwrap();
+    assert_eq!(rewritten, newer, "UserV2: decoded from an older reader");
+}
+"#;
+
+#[test]
+fn round_trip() -> Result<(), Box<dyn Error>> {
+    round_trip_gen("round_trip", GenMessage::default(), SKIP_CHECKS)
+}
+
+#[test]
+fn round_trip_preserve_unknown_fields() -> Result<(), Box<dyn Error>> {
+    let options: RustGenOptions =
+        RustGenOptions::default().with_unknown_fields(UnknownFields::Preserve);
+    let gen: GenMessage = GenMessage::default().with_options(options);
+    round_trip_gen("round_trip_preserve_unknown_fields", gen, PRESERVE_CHECKS)
+}
+
+#[test]
+fn round_trip_hash_maps() -> Result<(), Box<dyn Error>> {
+    let options: RustGenOptions = RustGenOptions::default().with_map_backing(MapBacking::HashMap);
+    let gen: GenMessage = GenMessage::default().with_options(options);
+    round_trip_gen("round_trip_hash_maps", gen, SKIP_CHECKS)
+}
+
+/// Generates the schema with the message generator into the scratch crate in the directory & runs
+/// it with the unknown field checks.
+fn round_trip_gen(dir: &str, gen: GenMessage, checks: &str) -> Result<(), Box<dyn Error>> {
+    let mut source_set: SourceSet = SourceSet::default();
+    for (path, source) in SCHEMA {
+        source_set.add_file(*path, parse_source_file(source)?);
+    }
+
+    let gen: GenSourceFile = GenSourceFile::from(gen)
+        .with_runtime_path("crate::runtime")
+        .with_mod_root("crate::schema");
+    let naming: Naming = gen.message_gen().naming().clone();
+    let mut typing: Typing = gen.message_gen().typing().clone();
+    for (_, source_file) in source_set.files() {
+        typing = typing.with_source_file(source_file);
+    }
+    let files: Vec<RustFile> = GenSourceSet::from(gen).gen(&source_set)?;
+
+    let dir: PathBuf = Path::new(env!("CARGO_TARGET_TMPDIR")).join(dir);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), MANIFEST)?;
+    let main: String = gen_main(&source_set, &Samples::new(&naming, &typing), checks)?;
+    fs::write(dir.join("src/main.rs"), main)?;
+    write_schema(&dir.join("src/schema"), &files)?;
+
+    run(&dir)
+}
+
+/// Generates the scratch crate main file.
+fn gen_main(
+    source_set: &SourceSet,
+    samples: &Samples,
+    checks: &str,
+) -> Result<String, Box<dyn Error>> {
+    let runtime: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/runtime/mod.rs");
+    let runtime: String = format!("{:?}", runtime.display().to_string());
+    let mut main: String = MAIN_PRELUDE.replace("\"{runtime}\"", &runtime);
+    let mut assertions: Vec<String> = Vec::default();
+    for (path, source_file) in source_set.files() {
+        let mod_path: Vec<String> = samples.naming().mod_path(source_file, path)?;
+        main.push_str(&format!("\nuse schema::{}::*;\n", mod_path.join("::")));
+        for declaration in source_file.declarations() {
+            match declaration {
+                SourceDec::MessageDec(message) => {
+                    assertions.extend(samples.message_assertions(message)?)
+                }
+                SourceDec::EnumDec(_) => {}
+                SourceDec::VariantDec(variant) => {
+                    assertions.extend(samples.variant_assertions(variant)?)
+                }
+                SourceDec::ServiceDec(service) => {
+                    main.push_str(&format!("\n{}", samples.service_server(service)?));
+                    assertions.extend(samples.service_assertions(service)?);
+                }
+            }
+        }
+    }
+    main.push_str(MAIN_CHECKS);
+    main.push_str(checks);
+    main.push_str("\nfn main() {\n");
+    for assertion in assertions {
+        main.push_str(&format!("    {}\n", assertion));
+    }
+    main.push_str("    checks();\n}\n");
+    Ok(main)
+}
+
+/// Writes the generated files to the directory.
+fn write_schema(dir: &Path, files: &[RustFile]) -> Result<(), Box<dyn Error>> {
+    for file in files {
+        let path: PathBuf = dir.join(file.path());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut b: CodeBuffer = CodeBuffer::new("    ", "\n", 16 * 1024);
+        file.source().write(&mut b, 0);
+        fs::write(path, b.export())?;
+    }
+    Ok(())
+}
+
+/// Builds & runs the scratch crate offline.
+fn run(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let cargo: String = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output: Output = Command::new(cargo)
+        .arg("run")
+        .arg("--offline")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(dir.join("target"))
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("RUSTFLAGS")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "the generated code failed to build or round trip: (see {})\n{}",
+        dir.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
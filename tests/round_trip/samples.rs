@@ -0,0 +1,247 @@
+use code_gen::WithName;
+
+use proto_packet_core::gen::rust::{Naming, Typing};
+use proto_packet_core::gen::GenError;
+use proto_packet_core::tree::{Message, PrimitiveType, Service, TypeTag, Variant, WithTypeTag};
+
+/// Responsible for generating the rust expressions of sample values & the round-trip assertions.
+pub struct Samples<'a> {
+    naming: &'a Naming,
+    typing: &'a Typing,
+}
+
+impl<'a> Samples<'a> {
+    //! Construction
+
+    /// Creates a new samples generator.
+    pub fn new(naming: &'a Naming, typing: &'a Typing) -> Self {
+        Self { naming, typing }
+    }
+}
+
+impl<'a> Samples<'a> {
+    //! Properties
+
+    /// Gets the naming.
+    pub fn naming(&self) -> &Naming {
+        self.naming
+    }
+}
+
+impl<'a> Samples<'a> {
+    //! Values
+
+    /// Generates the expression of a sample value of the declared type.
+    ///
+    /// Integers use their extreme values, lists hold two elements & maps hold one entry.
+    pub fn value(&self, declared_type: &TypeTag) -> Result<String, GenError> {
+        self.value_in(declared_type, &mut Vec::default())
+    }
+
+    /// Generates the expression of a sample value of the declared type within the messages being
+    /// sampled.
+    fn value_in(
+        &self,
+        declared_type: &TypeTag,
+        sampling: &mut Vec<String>,
+    ) -> Result<String, GenError> {
+        Ok(match declared_type {
+            TypeTag::Primitive(primitive) => match primitive {
+                PrimitiveType::UnsignedInt8 => "u8::MAX",
+                PrimitiveType::UnsignedInt16 => "u16::MAX",
+                PrimitiveType::UnsignedInt32 => "u32::MAX",
+                PrimitiveType::UnsignedInt64 => "u64::MAX",
+                PrimitiveType::SignedInt8 => "i8::MIN",
+                PrimitiveType::SignedInt16 => "i16::MIN",
+                PrimitiveType::SignedInt32 => "i32::MIN",
+                PrimitiveType::SignedInt64 => "i64::MIN",
+                PrimitiveType::Float32 => "1.5f32",
+                PrimitiveType::Float64 => "-2.25f64",
+                PrimitiveType::Boolean => "true",
+            }
+            .to_string(),
+            TypeTag::String => "\"round trip \\u{2713}\".to_string()".to_string(),
+            TypeTag::Bytes => "vec![0u8, 1, 128, 255]".to_string(),
+            TypeTag::Named(name) => {
+                let type_name: String = self.typing.type_name(declared_type)?;
+                if let Some(e) = self.typing.enumeration(name) {
+                    let number: u32 = e.cases().last().map(|case| case.number()).unwrap_or(1);
+                    format!("{}::from_number({})", type_name, number)
+                } else if let Some(variant) = self.typing.variant(name) {
+                    self.variant_cases_in(variant, sampling)?
+                        .into_iter()
+                        .next()
+                        .unwrap_or_else(|| format!("{}::default()", type_name))
+                } else {
+                    self.message_in(self.typing.message(name)?, sampling)?
+                }
+            }
+            TypeTag::List(element) => {
+                let element: String = self.value_in(element, sampling)?;
+                format!("vec![{}, {}]", element, element)
+            }
+            TypeTag::Map(key, value) => format!(
+                "std::collections::{}::from([({}, {})])",
+                self.typing.map_backing().type_name(),
+                self.value_in(key, sampling)?,
+                self.value_in(value, sampling)?
+            ),
+        })
+    }
+
+    /// Generates the expression of a sample message with every field set.
+    ///
+    /// Messages containing themselves are sampled two levels deep. (the fields containing the
+    /// message again are left unset at the second level)
+    pub fn message(&self, message: &Message) -> Result<String, GenError> {
+        self.message_in(message, &mut Vec::default())
+    }
+
+    fn message_in(
+        &self,
+        message: &Message,
+        sampling: &mut Vec<String>,
+    ) -> Result<String, GenError> {
+        sampling.push(message.name().to_string());
+        let mut exp: String = format!("{}::default()", self.naming.type_name(message.name())?);
+        for field in message.fields() {
+            if Self::is_sampled_twice(field.type_tag(), sampling) {
+                continue;
+            }
+            let mut value: String = self.value_in(field.type_tag(), sampling)?;
+            let optional_copy: bool = field.field_number().is_some()
+                && !Typing::is_collection(field.type_tag())
+                && self.typing.is_copy(field.type_tag())?;
+            if optional_copy {
+                value = format!("Some({})", value);
+            }
+            exp.push_str(&format!(
+                ".{}({})",
+                self.naming
+                    .field_fn_name(message.name(), field.name(), "with_{}")?,
+                value
+            ));
+        }
+        sampling.pop();
+        Ok(exp)
+    }
+
+    /// Checks if the declared type contains a message that is already sampled twice.
+    fn is_sampled_twice(declared_type: &TypeTag, sampling: &[String]) -> bool {
+        match declared_type {
+            TypeTag::Named(name) => sampling.iter().filter(|sampled| *sampled == name).count() >= 2,
+            TypeTag::List(element) => Self::is_sampled_twice(element, sampling),
+            TypeTag::Map(key, value) => {
+                Self::is_sampled_twice(key, sampling) || Self::is_sampled_twice(value, sampling)
+            }
+            _ => false,
+        }
+    }
+
+    /// Generates the expressions of a sample variant for each case.
+    pub fn variant_cases(&self, variant: &Variant) -> Result<Vec<String>, GenError> {
+        self.variant_cases_in(variant, &mut Vec::default())
+    }
+
+    fn variant_cases_in(
+        &self,
+        variant: &Variant,
+        sampling: &mut Vec<String>,
+    ) -> Result<Vec<String>, GenError> {
+        let type_name: String = self.naming.type_name(variant.name())?;
+        let mut cases: Vec<String> = Vec::default();
+        for case in variant.cases() {
+            cases.push(format!(
+                "{}::{}({})",
+                type_name,
+                self.naming.case_name(variant.name(), case.name())?,
+                self.value_in(case.type_tag(), sampling)?
+            ));
+        }
+        Ok(cases)
+    }
+}
+
+impl<'a> Samples<'a> {
+    //! Assertions
+
+    /// Generates the statements asserting the default & sample messages round trip.
+    pub fn message_assertions(&self, message: &Message) -> Result<Vec<String>, GenError> {
+        let type_name: String = self.naming.type_name(message.name())?;
+        Ok(vec![
+            format!(
+                "round_trip(\"{} (default)\", {}::default());",
+                message.name(),
+                type_name
+            ),
+            format!(
+                "round_trip(\"{} (sample)\", {});",
+                message.name(),
+                self.message(message)?
+            ),
+        ])
+    }
+
+    /// Generates the statements asserting the default variant & each case round trip.
+    pub fn variant_assertions(&self, variant: &Variant) -> Result<Vec<String>, GenError> {
+        let type_name: String = self.naming.type_name(variant.name())?;
+        let mut statements: Vec<String> = vec![format!(
+            "round_trip(\"{} (default)\", {}::default());",
+            variant.name(),
+            type_name
+        )];
+        for (case, exp) in variant.cases().iter().zip(self.variant_cases(variant)?) {
+            statements.push(format!(
+                "round_trip(\"{}.{}\", {});",
+                variant.name(),
+                case.name(),
+                exp
+            ));
+        }
+        Ok(statements)
+    }
+
+    /// Generates the server implementing the service with sample responses.
+    pub fn service_server(&self, service: &Service) -> Result<String, GenError> {
+        let type_name: String = self.naming.type_name(service.name())?;
+        let mut code: String = format!(
+            "struct {}Server;\n\nimpl {} for {}Server {{\n",
+            type_name, type_name, type_name
+        );
+        for rpc in service.rpcs() {
+            let response: TypeTag = TypeTag::Named(rpc.response().to_string());
+            code.push_str(&format!(
+                "    fn {}(&mut self, _request: {}) -> Result<{}, io::Error> {{\n        Ok({})\n    }}\n",
+                self.naming.fn_name(service.name(), rpc.name())?,
+                self.typing.type_name(&TypeTag::Named(rpc.request().to_string()))?,
+                self.typing.type_name(&response)?,
+                self.value(&response)?
+            ));
+        }
+        code.push_str("}\n");
+        Ok(code)
+    }
+
+    /// Generates the statements asserting each rpc round trips through the server & client.
+    pub fn service_assertions(&self, service: &Service) -> Result<Vec<String>, GenError> {
+        let type_name: String = self.naming.type_name(service.name())?;
+        let mut statements: Vec<String> = Vec::default();
+        for (method_id, rpc) in service.method_ids() {
+            let request: String = self.value(&TypeTag::Named(rpc.request().to_string()))?;
+            let response: String = self.value(&TypeTag::Named(rpc.response().to_string()))?;
+            statements.push(format!(
+                "rpc_round_trip(\"{}.{}\", {}, {}, {}, |t| {}Server.serve(t), |t| {{ let mut client = {}Client::new(t); let response = client.{}({})?; Ok((response, client.into_transport())) }});",
+                service.name(),
+                rpc.name(),
+                method_id,
+                request,
+                response,
+                type_name,
+                type_name,
+                self.naming.fn_name(service.name(), rpc.name())?,
+                request
+            ));
+        }
+        Ok(statements)
+    }
+}
@@ -0,0 +1,14 @@
+package round_trip.common;
+
+// A status with a case number gap.
+enum Status {
+    ACTIVE = 1;
+    DISABLED = 2;
+    DELETED = 5;
+}
+
+// A point.
+message Point {
+    x: f64 = 1;
+    y: f64 = 2;
+}
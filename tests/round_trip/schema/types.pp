@@ -0,0 +1,136 @@
+package round_trip.types;
+import "common.pp";
+
+// The optional primitive fields.
+message Scalars {
+    u8_field: u8 = 1;
+    u16_field: u16 = 2;
+    u32_field: u32 = 3;
+    u64_field: u64 = 4;
+    i8_field: i8 = 5;
+    i16_field: i16 = 6;
+    i32_field: i32 = 7;
+    i64_field: i64 = 8;
+    f32_field: f32 = 9;
+    f64_field: f64 = 10;
+    bool_field: bool = 11;
+}
+
+// The optional fixed-length integer fields.
+message FixedScalars {
+    u16_field: u16 = 1 [fixed];
+    u32_field: u32 = 2 [fixed];
+    u64_field: u64 = 3 [fixed];
+    i16_field: i16 = 4 [fixed];
+    i32_field: i32 = 5 [fixed];
+    i64_field: i64 = 6 [fixed];
+}
+
+// The required fields of every kind.
+message Required {
+    u8_field: u8;
+    u16_field: u16;
+    u32_field: u32 [fixed];
+    u64_field: u64;
+    i8_field: i8;
+    i16_field: i16 [fixed];
+    i32_field: i32;
+    i64_field: i64;
+    f32_field: f32;
+    f64_field: f64;
+    bool_field: bool;
+    string_field: string;
+    bytes_field: bytes;
+    status: Status;
+    point: Point;
+    shape: Shape;
+    optional: u32 = 1;
+}
+
+// The optional byte string & named fields.
+message Optionals {
+    string_field: string = 1;
+    bytes_field: bytes = 2;
+    status: Status = 3;
+    point: Point = 4;
+    shape: Shape = 5;
+}
+
+// The list fields.
+message Lists {
+    u8_list: [u8] = 1;
+    u16_list: [u16] = 2;
+    u32_packed: [u32] = 3 [packed];
+    u64_fixed: [u64] = 4 [fixed];
+    i8_packed: [i8] = 5 [packed];
+    i16_list: [i16] = 6;
+    i32_fixed_packed: [i32] = 7 [fixed, packed];
+    i64_packed: [i64] = 8 [packed];
+    f32_list: [f32] = 9;
+    f64_packed: [f64] = 10 [packed];
+    bool_list: [bool] = 11;
+    strings: [string] = 12;
+    bytes_list: [bytes] = 13;
+    statuses: [Status] = 14;
+    packed_statuses: [Status] = 15 [packed];
+    points: [Point] = 16;
+    shapes: [Shape] = 17;
+}
+
+// The map fields.
+message Maps {
+    by_name: map<string, u32> = 1;
+    by_id: map<u64, string> = 2;
+    points: map<i32, Point> = 3;
+    statuses: map<bool, Status> = 4;
+    blobs: map<u8, bytes> = 5;
+    shapes: map<i16, Shape> = 6;
+}
+
+// A message with reserved field numbers & keyword field names.
+message Keywords {
+    type: u8;
+    self: u32 = 1;
+    reserved 2 to 4;
+    reserved "old";
+}
+
+// A tree containing itself through a list & a map.
+message Node {
+    value: u32 = 1;
+    children: [Node] = 2;
+    by_name: map<string, Node> = 3;
+}
+
+// An account with required fields before its optional fields.
+message Account {
+    id: u64;
+    name: string;
+    email: string = 1;
+}
+
+// A message with field numbers declared out of order & with gaps.
+message Sparse {
+    c: u32 = 30;
+    a: u32 = 2;
+    b: u32 = 9;
+}
+
+// A message without fields.
+message Empty {}
+
+// A shape.
+variant Shape {
+    circle: Point = 1;
+    side: u32 = 2;
+    label: string = 3;
+    status: Status = 4;
+    data: bytes = 5;
+    fixed: f32 = 6;
+}
+
+// The drawings.
+service Drawings {
+    rpc Draw(Optionals) -> Lists;
+    rpc Clear(Empty) -> Empty;
+}
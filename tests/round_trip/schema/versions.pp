@@ -0,0 +1,16 @@
+package round_trip.versions;
+
+// A user as written by an older schema.
+message UserV1 {
+    id: u64 = 1;
+    name: string = 2;
+}
+
+// A user as written by a newer schema. (with fields the older schema does not know)
+message UserV2 {
+    id: u64 = 1;
+    name: string = 2;
+    email: string = 3;
+    tags: [string] = 4;
+    status: u32 = 5 [fixed];
+}